@@ -0,0 +1,106 @@
+use std::time::{Duration, Instant};
+
+use leftwm_core::models::ScratchPadAnchor;
+use smithay::utils::{Logical, Point, Size};
+
+const SLIDE_DURATION: Duration = Duration::from_millis(200);
+
+/// A window sliding in from, or out towards, the side of the workspace it is anchored to.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Slide {
+    anchor: ScratchPadAnchor,
+    showing: bool,
+    start: Instant,
+}
+
+impl Slide {
+    pub fn new(anchor: ScratchPadAnchor, showing: bool) -> Self {
+        Self {
+            anchor,
+            showing,
+            start: Instant::now(),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.start.elapsed() >= SLIDE_DURATION
+    }
+
+    /// Whether the window slides in, rather than out of sight.
+    pub fn is_showing(&self) -> bool {
+        self.showing
+    }
+
+    /// Offset of a window of the given size from its geometry at this moment.
+    pub fn offset(&self, size: Size<i32, Logical>) -> Point<i32, Logical> {
+        self.offset_after(self.start.elapsed(), size)
+    }
+
+    /// Offset of a window of the given size from its geometry, once the slide ran for `elapsed`.
+    fn offset_after(&self, elapsed: Duration, size: Size<i32, Logical>) -> Point<i32, Logical> {
+        let progress = (elapsed.as_secs_f64() / SLIDE_DURATION.as_secs_f64()).min(1.0);
+        // How far the window is outside of its geometry, from 0 to 1.
+        let hidden = if self.showing {
+            1.0 - progress
+        } else {
+            progress
+        };
+        let (x, y) = match self.anchor {
+            ScratchPadAnchor::Top => (0, -size.h),
+            ScratchPadAnchor::Bottom => (0, size.h),
+            ScratchPadAnchor::Left => (-size.w, 0),
+            ScratchPadAnchor::Right => (size.w, 0),
+            ScratchPadAnchor::Center => (0, 0),
+        };
+        (
+            (f64::from(x) * hidden).round() as i32,
+            (f64::from(y) * hidden).round() as i32,
+        )
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offsets(anchor: ScratchPadAnchor, showing: bool) -> Vec<(i32, i32)> {
+        let slide = Slide::new(anchor, showing);
+        [
+            Duration::ZERO,
+            SLIDE_DURATION / 2,
+            SLIDE_DURATION,
+            SLIDE_DURATION * 2,
+        ]
+        .into_iter()
+        .map(|elapsed| {
+            let offset = slide.offset_after(elapsed, (400, 300).into());
+            (offset.x, offset.y)
+        })
+        .collect()
+    }
+
+    #[test]
+    fn showing_slides_in_from_the_anchor() {
+        assert_eq!(
+            offsets(ScratchPadAnchor::Top, true),
+            vec![(0, -300), (0, -150), (0, 0), (0, 0)]
+        );
+        assert_eq!(
+            offsets(ScratchPadAnchor::Right, true),
+            vec![(400, 0), (200, 0), (0, 0), (0, 0)]
+        );
+    }
+
+    #[test]
+    fn hiding_slides_out_towards_the_anchor() {
+        assert_eq!(
+            offsets(ScratchPadAnchor::Bottom, false),
+            vec![(0, 0), (0, 150), (0, 300), (0, 300)]
+        );
+        assert_eq!(
+            offsets(ScratchPadAnchor::Left, false),
+            vec![(0, 0), (-200, 0), (-400, 0), (-400, 0)]
+        );
+    }
+}
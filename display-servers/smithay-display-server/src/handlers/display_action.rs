@@ -8,7 +8,7 @@ use smithay::{
 };
 use tracing::{info, warn};
 
-use crate::{internal_action::InternalAction, state::SmithayState};
+use crate::{animation::Slide, internal_action::InternalAction, state::SmithayState};
 
 impl SmithayState {
     pub fn handle_action(&mut self, action: InternalAction, display: &mut Display<Self>) {
@@ -52,6 +52,14 @@ impl SmithayState {
 
                         managed_window_data.floating = window.floating();
                         managed_window_data.visible = window.visible();
+                        // A window shown again without sliding in is drawn where it belongs.
+                        if window.visible()
+                            && managed_window_data
+                                .slide
+                                .map_or(false, |slide| !slide.is_showing())
+                        {
+                            managed_window_data.slide = None;
+                        }
 
                         managed_window
                             .get_window()
@@ -145,6 +153,16 @@ impl SmithayState {
                 //TODO: no `todo!()` here because crash
             }
             InternalAction::DisplayAction(DisplayAction::SetWindowTag(..)) => {}
+            InternalAction::DisplayAction(DisplayAction::SetTagLabels(_)) => {}
+            InternalAction::DisplayAction(DisplayAction::SetBorderColors(..)) => {}
+            InternalAction::DisplayAction(DisplayAction::SlideWindow(handle, anchor, showing)) => {
+                let WindowHandle::SmithayHandle(handle) = handle else {
+                    panic!("LeftWM passed an invalid handle");
+                };
+                if let Some(window) = self.window_registry.get(handle) {
+                    window.data.write().unwrap().slide = Some(Slide::new(anchor, showing));
+                }
+            }
             InternalAction::DisplayAction(DisplayAction::NormalMode) => {
                 todo!()
             }
@@ -27,6 +27,7 @@ use tokio::sync::oneshot;
 use tracing::{error, info, warn};
 
 use crate::state::{CalloopData, SmithayState};
+mod animation;
 mod drawing;
 mod event_channel;
 mod handlers;
@@ -26,6 +26,7 @@ use smithay::{
 };
 
 use crate::{
+    animation::Slide,
     drawing::border::{BorderRenderer, WindowState},
     leftwm_config::BorderConfig,
     state::SmithayState,
@@ -45,6 +46,7 @@ pub struct ManagedWindowData {
     pub floating: bool,
     pub visible: bool,
     pub geometry: Option<Rectangle<i32, Logical>>,
+    pub slide: Option<Slide>,
 }
 
 #[derive(Clone, Debug)]
@@ -262,6 +264,7 @@ impl ManagedWindow {
         <R as Renderer>::TextureId: 'static,
     {
         let mut elements = Vec::new();
+        let border_location = location.to_logical(1);
 
         // borders
         if self.handle == *focused_window {
@@ -270,7 +273,7 @@ impl ManagedWindow {
                 self,
                 borders,
                 WindowState::Focused,
-                self.data.read().unwrap().geometry.unwrap().loc,
+                border_location,
             )));
         } else if self.data.read().unwrap().floating {
             elements.push(C::from(BorderRenderer::render_element(
@@ -278,7 +281,7 @@ impl ManagedWindow {
                 self,
                 borders,
                 WindowState::Floating,
-                self.data.read().unwrap().geometry.unwrap().loc,
+                border_location,
             )));
         } else {
             elements.push(C::from(BorderRenderer::render_element(
@@ -286,7 +289,7 @@ impl ManagedWindow {
                 self,
                 borders,
                 WindowState::Default,
-                self.data.read().unwrap().geometry.unwrap().loc,
+                border_location,
             )));
         }
 
@@ -352,6 +355,30 @@ impl ManagedWindow {
         }
     }
 
+    /// Location the window is drawn at, which differs from its geometry while it slides.
+    pub fn render_location(&self) -> Point<i32, Logical> {
+        let mut data = self.data.write().unwrap();
+        let geometry = data.geometry.unwrap();
+        match data.slide {
+            Some(slide) if slide.is_finished() && slide.is_showing() => {
+                data.slide = None;
+                geometry.loc
+            }
+            // A window which slid out stays out of sight, until it is shown again.
+            Some(slide) => geometry.loc + slide.offset(geometry.size),
+            None => geometry.loc,
+        }
+    }
+
+    /// Whether the window slid out of sight, it is not drawn until it is shown again.
+    pub fn slid_out(&self) -> bool {
+        self.data
+            .read()
+            .unwrap()
+            .slide
+            .map_or(false, |slide| !slide.is_showing() && slide.is_finished())
+    }
+
     pub fn get_geometry(&self) -> Option<Rectangle<i32, Logical>> {
         match &self.window {
             InnerManagedWindow::Window(w) => Some(w.geometry()),
@@ -376,6 +376,16 @@ impl SmithayState {
             .cloned();
 
         if let Some(output) = output {
+            self.send_event(DisplayEvent::ScreenDestroy(Screen::new(
+                BBox {
+                    x: output.1.loc.x,
+                    y: output.1.loc.y,
+                    width: output.1.size.w,
+                    height: output.1.size.h,
+                },
+                output.0.name(),
+            )))
+            .unwrap();
             self.outputs.retain(|o| o != &output)
         }
     }
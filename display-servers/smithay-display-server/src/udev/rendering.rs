@@ -214,12 +214,12 @@ impl SmithayState {
 
         let windows = self.window_registry.windows_in_rect(output_geometry);
 
-        elements.extend(windows.iter().flat_map(|w| {
+        elements.extend(windows.iter().filter(|w| !w.slid_out()).flat_map(|w| {
             w.render_elements(
                 &mut renderer,
                 &self.focused_window,
                 &self.config.borders,
-                w.data.read().unwrap().geometry.unwrap().loc.to_physical(1),
+                w.render_location().to_physical(1),
                 Scale::from(1.0),
                 1.0,
             )
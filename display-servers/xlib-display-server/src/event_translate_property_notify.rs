@@ -36,6 +36,7 @@ pub fn from_event(xw: &XWrap, event: xlib::XPropertyEvent) -> Option<DisplayEven
             .map(|hints| build_change_hints(event, hints))
             .map(DisplayEvent::WindowChange),
         xlib::XA_WM_NAME => Some(update_title(xw, event.window)),
+        xlib::XA_WM_CLASS => Some(update_class(xw, event.window)),
         _ => {
             if event.atom == xw.atoms.NetWMName {
                 return Some(update_title(xw, event.window));
@@ -122,3 +123,12 @@ fn update_title(xw: &XWrap, window: xlib::Window) -> DisplayEvent {
     change.name = Some(title);
     DisplayEvent::WindowChange(change)
 }
+
+fn update_class(xw: &XWrap, window: xlib::Window) -> DisplayEvent {
+    let class = xw.get_window_class(window);
+    let handle = window.into();
+    let mut change = WindowChange::new(handle);
+    change.res_name = Some(class.as_ref().map(|(res_name, _)| res_name.clone()));
+    change.res_class = Some(class.map(|(_, res_class)| res_class));
+    DisplayEvent::WindowChange(change)
+}
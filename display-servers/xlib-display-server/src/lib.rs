@@ -10,8 +10,12 @@ pub use xwrap::XWrap;
 use self::xwrap::ICONIC_STATE;
 use event_translate::XEvent;
 use futures::prelude::*;
-use leftwm_core::config::Config;
-use leftwm_core::models::{Mode, Screen, TagId, Window, WindowHandle, WindowState, Workspace};
+use leftwm_core::config::{
+    numbered_workspaces, Config, Workspace as WorkspaceConfig, WorkspaceProfile,
+};
+use leftwm_core::models::{
+    BorderColors, Mode, Screen, TagId, Window, WindowHandle, WindowState, Workspace, WorkspaceId,
+};
 use leftwm_core::utils;
 use leftwm_core::{DisplayAction, DisplayEvent, DisplayServer};
 use std::os::raw::c_uint;
@@ -23,6 +27,12 @@ pub struct XlibDisplayServer {
     xw: XWrap,
     root: xlib::Window,
     initial_events: Vec<DisplayEvent>,
+    /// The configured workspaces with their IDs and profiles, see [`numbered_workspaces`].
+    workspaces: Vec<(WorkspaceId, Option<String>, WorkspaceConfig)>,
+    workspace_profiles: Vec<WorkspaceProfile>,
+    auto_derive_workspaces: bool,
+    /// The screens the manager has been told about.
+    screens: Vec<Screen>,
 }
 
 impl DisplayServer for XlibDisplayServer {
@@ -32,17 +42,18 @@ impl DisplayServer for XlibDisplayServer {
         wrap.init(config); // setup events masks
 
         let root = wrap.get_default_root();
-        let instance = Self {
+        let mut instance = Self {
             xw: wrap,
             root,
             initial_events: Vec::new(),
+            workspaces: numbered_workspaces(config),
+            workspace_profiles: config.workspace_profiles(),
+            auto_derive_workspaces: config.auto_derive_workspaces(),
+            screens: Vec::new(),
         };
-        let initial_events = instance.initial_events(config);
-
-        Self {
-            initial_events,
-            ..instance
-        }
+        instance.screens = instance.configured_screens();
+        instance.initial_events = instance.initial_events();
+        instance
     }
 
     fn load_config(
@@ -52,6 +63,9 @@ impl DisplayServer for XlibDisplayServer {
         windows: &[Window],
     ) {
         self.xw.load_config(config, focused, windows);
+        self.workspaces = numbered_workspaces(config);
+        self.workspace_profiles = config.workspace_profiles();
+        self.auto_derive_workspaces = config.auto_derive_workspaces();
     }
 
     fn update_windows(&self, windows: Vec<&Window>) {
@@ -69,9 +83,14 @@ impl DisplayServer for XlibDisplayServer {
     fn get_next_events(&mut self) -> Vec<DisplayEvent> {
         let mut events = std::mem::take(&mut self.initial_events);
 
+        let mut screens_changed = false;
         let events_in_queue = self.xw.queue_len();
         for _ in 0..events_in_queue {
-            let xlib_event = self.xw.get_next_event();
+            let mut xlib_event = self.xw.get_next_event();
+            if self.xw.is_screen_change(&mut xlib_event) {
+                screens_changed = true;
+                continue;
+            }
             let event = XEvent(&mut self.xw, xlib_event).into();
             if let Some(e) = event {
                 tracing::trace!("DisplayEvent: {:?}", e);
@@ -79,6 +98,10 @@ impl DisplayServer for XlibDisplayServer {
             }
         }
 
+        if screens_changed {
+            events.append(&mut self.screen_change_events());
+        }
+
         for event in &events {
             if let DisplayEvent::WindowDestroy(WindowHandle::XlibHandle(w)) = event {
                 self.xw.force_unmapped(*w);
@@ -106,6 +129,10 @@ impl DisplayServer for XlibDisplayServer {
             DisplayAction::ReadyToResizeWindow(h) => from_ready_to_resize_window(xw, h),
             DisplayAction::SetCurrentTags(t) => from_set_current_tags(xw, t),
             DisplayAction::SetWindowTag(h, t) => from_set_window_tag(xw, h, t),
+            DisplayAction::SetTagLabels(l) => from_set_tag_labels(xw, l),
+            DisplayAction::SetBorderColors(h, c) => from_set_border_colors(xw, h, c),
+            // Windows are not animated on X.
+            DisplayAction::SlideWindow(..) => None,
             DisplayAction::ConfigureXlibWindow(w) => from_configure_xlib_window(xw, &w),
 
             DisplayAction::WindowTakeFocus {
@@ -142,55 +169,136 @@ impl DisplayServer for XlibDisplayServer {
 
 impl XlibDisplayServer {
     /// Return a vec of events for setting up state of WM.
-    fn initial_events(&self, config: &impl Config) -> Vec<DisplayEvent> {
-        let mut events = vec![];
-        if let Some(workspaces) = config.workspaces() {
-            let screens = self.xw.get_screens();
-            for (i, wsc) in workspaces.iter().enumerate() {
-                let mut screen = Screen::from(wsc);
-                screen.root = self.root.into();
-                // If there is a screen corresponding to the given output, create the workspace
-                match screens.iter().find(|i| i.output == wsc.output) {
-                    Some(output_match) => {
-                        if wsc.relative.unwrap_or(false) {
-                            screen.bbox.add(output_match.bbox);
-                        }
-                        screen.id = Some(i + 1);
+    fn initial_events(&self) -> Vec<DisplayEvent> {
+        let mut events: Vec<DisplayEvent> = self
+            .screens
+            .iter()
+            .cloned()
+            .map(DisplayEvent::ScreenCreate)
+            .collect();
+
+        // Tell manager about existing windows.
+        events.append(&mut self.find_all_windows());
+
+        events
+    }
+
+    /// The screens of the workspaces in the config which are connected, and of the other
+    /// connected outputs if workspaces are derived automatically. The workspaces of the
+    /// first profile matching the connected outputs are used instead of the plain ones, also
+    /// when there are no plain ones.
+    ///
+    /// Screens which are already known keep their ID.
+    fn configured_screens(&self) -> Vec<Screen> {
+        let mut configured = vec![];
+        let numbered = &self.workspaces;
+        let screens = self.xw.get_screens();
+        let outputs: Vec<String> = screens.iter().map(|s| s.output.clone()).collect();
+        let edids = if self.workspace_profiles.iter().any(|p| !p.edids.is_empty()) {
+            self.xw.get_edid_identifiers()
+        } else {
+            vec![]
+        };
+        let profile = self
+            .workspace_profiles
+            .iter()
+            .find(|profile| profile.matches(&outputs, &edids))
+            .map(|profile| profile.name.clone());
+        tracing::debug!(
+            "Using workspace profile {:?} for outputs {:?}",
+            profile,
+            outputs
+        );
+
+        let workspaces: Vec<(WorkspaceId, &WorkspaceConfig)> = numbered
+            .iter()
+            .filter(|(_, ws_profile, _)| *ws_profile == profile)
+            .map(|(id, _, wsc)| (*id, wsc))
+            .collect();
+        for (id, wsc) in &workspaces {
+            let mut screen = Screen::from(wsc);
+            screen.root = self.root.into();
+            // If there is a screen corresponding to the given output, create the workspace
+            match screens.iter().find(|i| i.output == wsc.output) {
+                Some(output_match) => {
+                    if wsc.relative.unwrap_or(false) {
+                        screen.bbox.add(output_match.bbox);
                     }
-                    None => continue,
+                    screen.id = Some(*id);
                 }
-                let e = DisplayEvent::ScreenCreate(screen);
-                events.push(e);
-            }
-
-            let auto_derive_workspaces: bool = if config.auto_derive_workspaces() {
-                true
-            } else if events.is_empty() {
-                tracing::warn!("No Workspace in Workspace config matches connected screen. Falling back to \"auto_derive_workspaces: true\".");
-                true
-            } else {
-                false
-            };
-
-            let mut next_id = workspaces.len() + 1;
-
-            // If there is no hardcoded workspace layout, add every screen not mentioned in the config.
-            if auto_derive_workspaces {
-                screens
-                    .iter()
-                    .filter(|screen| !workspaces.iter().any(|wsc| wsc.output == screen.output))
-                    .for_each(|screen| {
-                        let mut s = screen.clone();
-                        s.id = Some(next_id);
-                        next_id += 1;
-                        events.push(DisplayEvent::ScreenCreate(s));
-                    });
+                None => continue,
             }
+            configured.push(screen);
         }
 
-        // Tell manager about existing windows.
-        events.append(&mut self.find_all_windows());
+        let auto_derive_workspaces: bool = if self.auto_derive_workspaces || workspaces.is_empty() {
+            true
+        } else if configured.is_empty() {
+            tracing::warn!("No Workspace in Workspace config matches connected screen. Falling back to \"auto_derive_workspaces: true\".");
+            true
+        } else {
+            false
+        };
+
+        let mut next_id = self
+            .screens
+            .iter()
+            .filter_map(|screen| screen.id)
+            .max()
+            .unwrap_or_default()
+            .max(numbered.len())
+            + 1;
+
+        // If there is no hardcoded workspace layout, add every screen not mentioned in the config.
+        if auto_derive_workspaces {
+            screens
+                .iter()
+                .filter(|screen| {
+                    !workspaces
+                        .iter()
+                        .any(|(_, wsc)| wsc.output == screen.output)
+                })
+                .for_each(|screen| {
+                    let mut s = screen.clone();
+                    let known = self
+                        .screens
+                        .iter()
+                        .find(|known| known.output == s.output && known.id > Some(numbered.len()));
+                    s.id = match known {
+                        Some(known) => known.id,
+                        None => {
+                            next_id += 1;
+                            Some(next_id - 1)
+                        }
+                    };
+                    configured.push(s);
+                });
+        }
+        configured
+    }
 
+    /// Compare the connected screens with the known ones, after randr notified about a change.
+    ///
+    /// New screens are announced before the removed ones, so that there is always a workspace
+    /// left to take over the tags of the removed ones, e.g. when switching profiles.
+    fn screen_change_events(&mut self) -> Vec<DisplayEvent> {
+        let screens = self.configured_screens();
+        let mut events = vec![];
+        for screen in &screens {
+            match self.screens.iter().find(|known| known.id == screen.id) {
+                Some(known) if known.bbox == screen.bbox => {}
+                Some(_) => events.push(DisplayEvent::ScreenUpdate(screen.clone())),
+                None => events.push(DisplayEvent::ScreenCreate(screen.clone())),
+            }
+        }
+        events.extend(
+            self.screens
+                .iter()
+                .filter(|known| !screens.iter().any(|s| s.id == known.id))
+                .cloned()
+                .map(DisplayEvent::ScreenDestroy),
+        );
+        self.screens = screens;
         events
     }
 
@@ -338,6 +446,25 @@ fn from_set_current_tags(xw: &mut XWrap, tag: Option<TagId>) -> Option<DisplayEv
     None
 }
 
+fn from_set_tag_labels(xw: &mut XWrap, labels: Vec<String>) -> Option<DisplayEvent> {
+    xw.tag_labels = labels;
+    xw.set_desktop_names();
+    None
+}
+
+fn from_set_border_colors(
+    xw: &mut XWrap,
+    handle: WindowHandle,
+    colors: BorderColors,
+) -> Option<DisplayEvent> {
+    let handle = handle.xlib_handle()?;
+    xw.set_border_colors(handle, colors);
+    if handle == xw.focused_window {
+        xw.set_window_border_color(handle, xw.border_color(handle, false, true));
+    }
+    None
+}
+
 fn from_set_window_tag(
     xw: &mut XWrap,
     handle: WindowHandle,
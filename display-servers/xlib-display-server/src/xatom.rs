@@ -12,6 +12,7 @@ pub struct XAtom {
     pub WMState: xlib::Atom,
     pub WMClass: xlib::Atom,
     pub WMTakeFocus: xlib::Atom,
+    pub WMWindowRole: xlib::Atom,
     pub NetActiveWindow: xlib::Atom,
     pub NetSupported: xlib::Atom,
     pub NetWMName: xlib::Atom,
@@ -123,6 +124,7 @@ impl XAtom {
             a if a == self.WMState => "WM_STATE",
             a if a == self.WMClass => "WM_CLASS",
             a if a == self.WMTakeFocus => "WM_TAKE_FOCUS",
+            a if a == self.WMWindowRole => "WM_WINDOW_ROLE",
             a if a == self.NetActiveWindow => "_NET_ACTIVE_WINDOW",
             a if a == self.NetSupported => "_NET_SUPPORTED",
             a if a == self.NetWMName => "_NET_WM_NAME",
@@ -177,6 +179,7 @@ impl XAtom {
             WMState: from(xlib, dpy, "WM_STATE"),
             WMClass: from(xlib, dpy, "WM_CLASS"),
             WMTakeFocus: from(xlib, dpy, "WM_TAKE_FOCUS"),
+            WMWindowRole: from(xlib, dpy, "WM_WINDOW_ROLE"),
             NetActiveWindow: from(xlib, dpy, "_NET_ACTIVE_WINDOW"),
             NetSupported: from(xlib, dpy, "_NET_SUPPORTED"),
             NetWMName: from(xlib, dpy, "_NET_WM_NAME"),
@@ -10,8 +10,9 @@ use super::xatom::XAtom;
 use super::xcursor::XCursor;
 use super::{utils, Screen, Window, WindowHandle};
 use leftwm_core::config::Config;
-use leftwm_core::models::{FocusBehaviour, Mode};
+use leftwm_core::models::{BorderColors, FocusBehaviour, Mode};
 use leftwm_core::utils::modmask_lookup::ModMask;
+use std::collections::HashMap;
 use std::ffi::CString;
 use std::os::raw::{c_char, c_double, c_int, c_long, c_short, c_ulong};
 use std::sync::Arc;
@@ -20,7 +21,7 @@ use tokio::sync::{oneshot, Notify};
 use tokio::time::Duration;
 
 use x11_dl::xlib;
-use x11_dl::xrandr::Xrandr;
+use x11_dl::xrandr::{self, Xrandr};
 
 mod getters;
 mod mouse;
@@ -92,6 +93,13 @@ pub struct Colors {
     background: c_ulong,
 }
 
+/// Border colors of a single window, a missing color falls back to the one of the theme.
+struct WindowColors {
+    normal: Option<c_ulong>,
+    floating: Option<c_ulong>,
+    active: Option<c_ulong>,
+}
+
 #[derive(Debug, Clone)]
 pub enum XlibError {
     FailedStatus,
@@ -107,6 +115,7 @@ pub struct XWrap {
     pub atoms: XAtom,
     cursors: XCursor,
     colors: Colors,
+    window_colors: HashMap<xlib::Window, WindowColors>,
     pub managed_windows: Vec<xlib::Window>,
     pub focused_window: xlib::Window,
     pub tag_labels: Vec<String>,
@@ -118,6 +127,7 @@ pub struct XWrap {
     pub task_notify: Arc<Notify>,
     pub motion_event_limiter: c_ulong,
     pub refresh_rate: c_short,
+    randr_event_base: Option<c_int>,
 }
 
 impl Default for XWrap {
@@ -228,6 +238,7 @@ impl XWrap {
             atoms,
             cursors,
             colors,
+            window_colors: HashMap::new(),
             managed_windows: vec![],
             focused_window: root,
             tag_labels: vec![],
@@ -239,6 +250,7 @@ impl XWrap {
             task_notify,
             motion_event_limiter: 0,
             refresh_rate,
+            randr_event_base: None,
         };
 
         // Check that another WM is not running.
@@ -297,6 +309,7 @@ impl XWrap {
         }
 
         self.subscribe_to_event(root, ROOT_EVENT_MASK);
+        self.subscribe_to_screen_changes();
 
         // EWMH compliance.
         unsafe {
@@ -319,21 +332,80 @@ impl XWrap {
         self.sync();
     }
 
+    /// Ask randr to notify about screens being connected, disconnected or resized.
+    // `XRRQueryExtension`: https://www.x.org/releases/current/doc/man/man3/Xrandr.3.xhtml
+    // `XRRSelectInput`: https://www.x.org/releases/current/doc/man/man3/Xrandr.3.xhtml
+    fn subscribe_to_screen_changes(&mut self) {
+        let Ok(xrandr) = Xrandr::open() else {
+            return;
+        };
+        let mut event_base: c_int = 0;
+        let mut error_base: c_int = 0;
+        unsafe {
+            if (xrandr.XRRQueryExtension)(self.display, &mut event_base, &mut error_base)
+                == xlib::False
+            {
+                return;
+            }
+            (xrandr.XRRSelectInput)(self.display, self.root, xrandr::RRScreenChangeNotifyMask);
+        }
+        self.randr_event_base = Some(event_base);
+    }
+
+    /// Returns `true` if the event notifies about a change of the screens. Xlib's view of the
+    /// screens is updated in that case.
+    // `XRRUpdateConfiguration`: https://www.x.org/releases/current/doc/man/man3/Xrandr.3.xhtml
+    pub fn is_screen_change(&self, event: &mut xlib::XEvent) -> bool {
+        let Some(event_base) = self.randr_event_base else {
+            return false;
+        };
+        if event.get_type() != event_base + xrandr::RRScreenChangeNotify {
+            return false;
+        }
+        if let Ok(xrandr) = Xrandr::open() {
+            unsafe { (xrandr.XRRUpdateConfiguration)(event) };
+        }
+        true
+    }
+
     /// EWMH support used for bars such as polybar.
     ///  # Panics
     ///
     ///  Panics if a new Cstring cannot be formed
+    pub fn init_desktops_hints(&self) {
+        self.set_desktop_names();
+        // Set a current desktop.
+        let data = vec![0_u32, xlib::CurrentTime as u32];
+        self.set_desktop_prop(&data, self.atoms.NetCurrentDesktop);
+
+        // Set the WM NAME.
+        self.set_desktop_prop_string("LeftWM", self.atoms.NetWMName, self.atoms.UTF8String);
+
+        self.set_desktop_prop_string("LeftWM", self.atoms.WMClass, xlib::XA_STRING);
+
+        self.set_desktop_prop_c_ulong(
+            self.root as c_ulong,
+            self.atoms.NetSupportingWmCheck,
+            xlib::XA_WINDOW,
+        );
+
+        // Set a viewport.
+        let data = vec![0_u32, 0_u32];
+        self.set_desktop_prop(&data, self.atoms.NetDesktopViewport);
+    }
+
+    /// Set the number and names of the desktops from the current tag labels.
+    ///  # Panics
+    ///
+    ///  Panics if a new Cstring cannot be formed
     // `Xutf8TextListToTextProperty`: https://linux.die.net/man/3/xutf8textlisttotextproperty
     // `XSetTextProperty`: https://tronche.com/gui/x/xlib/ICC/client-to-window-manager/XSetTextProperty.html
-    pub fn init_desktops_hints(&self) {
+    pub fn set_desktop_names(&self) {
         let tag_labels = &self.tag_labels;
         let tag_length = tag_labels.len();
         // Set the number of desktop.
         let data = vec![tag_length as u32];
         self.set_desktop_prop(&data, self.atoms.NetNumberOfDesktops);
-        // Set a current desktop.
-        let data = vec![0_u32, xlib::CurrentTime as u32];
-        self.set_desktop_prop(&data, self.atoms.NetCurrentDesktop);
         // Set desktop names.
         let mut text: xlib::XTextProperty = unsafe { std::mem::zeroed() };
         unsafe {
@@ -357,21 +429,6 @@ impl XWrap {
                 self.atoms.NetDesktopNames,
             );
         }
-
-        // Set the WM NAME.
-        self.set_desktop_prop_string("LeftWM", self.atoms.NetWMName, self.atoms.UTF8String);
-
-        self.set_desktop_prop_string("LeftWM", self.atoms.WMClass, xlib::XA_STRING);
-
-        self.set_desktop_prop_c_ulong(
-            self.root as c_ulong,
-            self.atoms.NetSupportingWmCheck,
-            xlib::XA_WINDOW,
-        );
-
-        // Set a viewport.
-        let data = vec![0_u32, 0_u32];
-        self.set_desktop_prop(&data, self.atoms.NetDesktopViewport);
     }
 
     /// Send a xevent atom for a window to X.
@@ -437,13 +494,7 @@ impl XWrap {
                 if let WindowHandle::XlibHandle(handle) = window.handle {
                     let is_focused =
                         matches!(focused, Some(&Some(focused)) if focused == window.handle);
-                    let color: c_ulong = if is_focused {
-                        self.colors.active
-                    } else if window.floating() {
-                        self.colors.floating
-                    } else {
-                        self.colors.normal
-                    };
+                    let color = self.border_color(handle, window.floating(), is_focused);
                     self.set_window_border_color(handle, color);
                 }
             }
@@ -451,6 +502,34 @@ impl XWrap {
         self.set_background_color(self.colors.background);
     }
 
+    /// Set the border colors of a window which differ from the theme.
+    pub fn set_border_colors(&mut self, handle: xlib::Window, colors: BorderColors) {
+        if colors.is_empty() {
+            self.window_colors.remove(&handle);
+            return;
+        }
+        let window_colors = WindowColors {
+            normal: colors.default.map(|color| self.get_color(color)),
+            floating: colors.floating.map(|color| self.get_color(color)),
+            active: colors.focused.map(|color| self.get_color(color)),
+        };
+        self.window_colors.insert(handle, window_colors);
+    }
+
+    /// The border color of a window, from its own colors or the theme.
+    pub fn border_color(&self, handle: xlib::Window, floating: bool, focused: bool) -> c_ulong {
+        let colors = self.window_colors.get(&handle);
+        if focused {
+            colors.and_then(|c| c.active).unwrap_or(self.colors.active)
+        } else if floating {
+            colors
+                .and_then(|c| c.floating)
+                .unwrap_or(self.colors.floating)
+        } else {
+            colors.and_then(|c| c.normal).unwrap_or(self.colors.normal)
+        }
+    }
+
     /// Sets the mode within our xwrapper.
     pub fn set_mode(&mut self, mode: Mode) {
         match mode {
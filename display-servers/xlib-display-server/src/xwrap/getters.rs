@@ -288,6 +288,67 @@ impl XWrap {
         (height, width)
     }
 
+    /// Returns the EDID identifiers (`MFG-PRODUCT-SERIAL`) of the connected monitors.
+    // `XRRGetOutputProperty`: https://www.x.org/releases/current/doc/man/man3/Xrandr.3.xhtml
+    #[must_use]
+    pub fn get_edid_identifiers(&self) -> Vec<String> {
+        use x11_dl::xrandr::Xrandr;
+        let Ok(xrandr) = Xrandr::open() else {
+            return vec![];
+        };
+        let Ok(name) = CString::new("EDID") else {
+            return vec![];
+        };
+        unsafe {
+            let edid_atom = (self.xlib.XInternAtom)(self.display, name.as_ptr(), xlib::True);
+            if edid_atom == 0 {
+                return vec![];
+            }
+            let screen_resources = (xrandr.XRRGetScreenResources)(self.display, self.root);
+            let outputs = slice::from_raw_parts(
+                (*screen_resources).outputs,
+                (*screen_resources).noutput as usize,
+            );
+            outputs
+                .iter()
+                .filter(|&&output| {
+                    let output_info =
+                        (xrandr.XRRGetOutputInfo)(self.display, screen_resources, output);
+                    (*output_info).crtc != 0
+                })
+                .filter_map(|&output| {
+                    let mut type_return: xlib::Atom = 0;
+                    let mut format_return: c_int = 0;
+                    let mut nitems_return: c_ulong = 0;
+                    let mut bytes_remaining: c_ulong = 0;
+                    let mut prop_return: *mut c_uchar = std::mem::zeroed();
+                    let status = (xrandr.XRRGetOutputProperty)(
+                        self.display,
+                        output,
+                        edid_atom,
+                        0,
+                        MAX_PROPERTY_VALUE_LEN / 4,
+                        xlib::False,
+                        xlib::False,
+                        xlib::AnyPropertyType as xlib::Atom,
+                        &mut type_return,
+                        &mut format_return,
+                        &mut nitems_return,
+                        &mut bytes_remaining,
+                        &mut prop_return,
+                    );
+                    if status != i32::from(xlib::Success) || prop_return.is_null() {
+                        return None;
+                    }
+                    let edid = slice::from_raw_parts(prop_return, nitems_return as usize);
+                    let identifier = edid_identifier(edid);
+                    (self.xlib.XFree)(prop_return.cast());
+                    identifier
+                })
+                .collect()
+        }
+    }
+
     /// Returns the transient parent of a window.
     // `XGetTransientForHint`: https://tronche.com/gui/x/xlib/ICC/client-to-window-manager/XGetTransientForHint.html
     #[must_use]
@@ -438,6 +499,12 @@ impl XWrap {
         None
     }
 
+    /// Returns a windows `WM_WINDOW_ROLE`.
+    #[must_use]
+    pub fn get_window_role(&self, window: xlib::Window) -> Option<String> {
+        self.get_text_prop(window, self.atoms.WMWindowRole).ok()
+    }
+
     /// Returns a windows `_NET_WM_PID`.
     #[must_use]
     pub fn get_window_pid(&self, window: xlib::Window) -> Option<u32> {
@@ -760,3 +827,22 @@ impl XWrap {
             .map(|screen_id| unsafe { *(self.xlib.XScreenOfDisplay)(self.display, screen_id) })
     }
 }
+
+/// Build the identifier of a monitor from the manufacturer ID, product code and serial number
+/// in its EDID.
+fn edid_identifier(edid: &[u8]) -> Option<String> {
+    if edid.len() < 16 {
+        return None;
+    }
+    // The manufacturer ID consists of three letters, 5 bits each.
+    let manufacturer = u16::from_be_bytes([edid[8], edid[9]]);
+    let letter = |shift: u16| char::from(b'@' + ((manufacturer >> shift) & 0x1f) as u8);
+    let product = u16::from_le_bytes([edid[10], edid[11]]);
+    let serial = u32::from_le_bytes([edid[12], edid[13], edid[14], edid[15]]);
+    Some(format!(
+        "{}{}{}-{product:04X}-{serial:08X}",
+        letter(10),
+        letter(5),
+        letter(0)
+    ))
+}
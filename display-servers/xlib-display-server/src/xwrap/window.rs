@@ -24,6 +24,7 @@ impl XWrap {
         let name = self.get_window_name(window);
         let legacy_name = self.get_window_legacy_name(window);
         let class = self.get_window_class(window);
+        let role = self.get_window_role(window);
         let pid = self.get_window_pid(window);
         let r#type = self.get_window_type(window);
         let states = self.get_window_states(window);
@@ -40,6 +41,7 @@ impl XWrap {
             w.res_class = Some(res_class);
         }
         w.legacy_name = legacy_name;
+        w.role = role;
         w.r#type = r#type.clone();
         w.set_states(states);
         if let Some(trans) = trans {
@@ -142,11 +144,7 @@ impl XWrap {
                 return Some(DisplayEvent::WindowChange(change));
             }
         } else {
-            let color = if floating {
-                self.colors.floating
-            } else {
-                self.colors.normal
-            };
+            let color = self.border_color(handle, floating, false);
             self.set_window_border_color(handle, color);
 
             if follow_mouse {
@@ -165,6 +163,7 @@ impl XWrap {
     pub fn teardown_managed_window(&mut self, h: &WindowHandle, destroyed: bool) {
         if let WindowHandle::XlibHandle(handle) = h {
             self.managed_windows.retain(|x| *x != *handle);
+            self.window_colors.remove(handle);
             if !destroyed {
                 unsafe {
                     (self.xlib.XGrabServer)(self.display);
@@ -245,11 +244,7 @@ impl XWrap {
             // Update previous window.
             if let Some(previous) = previous {
                 if let WindowHandle::XlibHandle(previous_handle) = previous.handle {
-                    let color = if previous.floating() {
-                        self.colors.floating
-                    } else {
-                        self.colors.normal
-                    };
+                    let color = self.border_color(previous_handle, previous.floating(), false);
                     self.set_window_border_color(previous_handle, color);
                     // Open up button1 clicking on the previously focused window.
                     if self.focus_behaviour.is_clickto() {
@@ -260,7 +255,7 @@ impl XWrap {
             self.focused_window = handle;
             self.grab_mouse_clicks(handle, true);
             self.set_window_urgency(handle, false);
-            self.set_window_border_color(handle, self.colors.active);
+            self.set_window_border_color(handle, self.border_color(handle, false, true));
             self.focus(handle, window.never_focus);
             self.sync();
         }
@@ -296,11 +291,7 @@ impl XWrap {
     // `XSetInputFocus`: https://tronche.com/gui/x/xlib/input/XSetInputFocus.html
     pub fn unfocus(&self, handle: Option<WindowHandle>, floating: bool) {
         if let Some(WindowHandle::XlibHandle(handle)) = handle {
-            let color = if floating {
-                self.colors.floating
-            } else {
-                self.colors.normal
-            };
+            let color = self.border_color(handle, floating, false);
             self.set_window_border_color(handle, color);
 
             self.grab_mouse_clicks(handle, false);
@@ -1,6 +1,17 @@
 pub use crate::handlers::command_handler::ReleaseScratchPadOption;
-use crate::models::{ScratchPadName, TagId, WindowHandle};
+use crate::layouts::SplitDirection;
+use crate::models::{ScratchPadName, Side, TagId, WindowHandle};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Why a [`Command`] could not be executed.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    #[error("The command could not be executed")]
+    NotExecuted,
+    #[error("{0}")]
+    Other(String),
+}
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub enum Command {
@@ -23,6 +34,9 @@ pub enum Command {
         scratchpad: ScratchPadName,
     },
     ToggleScratchPad(ScratchPadName),
+    CycleScratchPadGroup {
+        group: String,
+    },
     ToggleFullScreen,
     ToggleMaximized,
     ToggleSticky,
@@ -31,6 +45,15 @@ pub enum Command {
         swap: bool,
     },
     ReturnToLastTag,
+    /// Toggle a tag into or out of view on the focused workspace.
+    ToggleTagView {
+        tag: TagId,
+    },
+    /// Toggle the membership of a window in each of the tags.
+    ToggleWindowTags {
+        window: Option<WindowHandle>,
+        tags: Vec<TagId>,
+    },
     FloatingToTile,
     TileToFloating,
     ToggleFloating,
@@ -51,6 +74,16 @@ pub enum Command {
     FocusWindow(String),
     FocusWindowUp,
     FocusWindowDown,
+    /// Focus the closest visible window on the given side of the focused one, on the workspace
+    /// next to it if there is none.
+    FocusWindowInDirection {
+        direction: Side,
+    },
+    /// Swap the focused window with the closest tiled window on the given side of it, on the
+    /// workspace next to it if there is none.
+    SwapWindowInDirection {
+        direction: Side,
+    },
     FocusWindowTop {
         swap: bool,
     },
@@ -72,6 +105,17 @@ pub enum Command {
     NextLayout,
     PreviousLayout,
     SetLayout(String),
+    /// Split the next window off the focused one in the given direction, with the manual layout.
+    SplitWindow {
+        direction: SplitDirection,
+    },
+    /// Grow the focused window by the given percentage of its split, with the manual layout.
+    ResizeSplit(i32),
+    /// Move the focused window next to the closest window on the given side, with the manual
+    /// layout.
+    MoveWindowInTree {
+        direction: Side,
+    },
     RotateTag,
     IncreaseMainWidth(i32), // deprecated: use IncreaseMainSize instead
     DecreaseMainWidth(i32), // deprecated: use DecreaseMainSize instead
@@ -79,9 +123,38 @@ pub enum Command {
     DecreaseMainSize(i32),
     IncreaseMainCount(),
     DecreaseMainCount(),
+    /// Grow the share of its stack the focused window takes by the given percentage.
+    IncreaseWindowWeight(i32),
+    /// Shrink the share of its stack the focused window takes by the given percentage.
+    DecreaseWindowWeight(i32),
+    /// Share the stacks of the focused tag equally between their windows again.
+    ResetWindowWeights,
     SetMarginMultiplier(f32),
     SendWorkspaceToTag(usize, usize),
+    AddTag {
+        label: String,
+    },
+    RemoveTag {
+        tag: TagId,
+    },
+    RenameTag {
+        tag: TagId,
+        label: String,
+    },
+    MoveTag {
+        tag: TagId,
+        position: TagId,
+    },
     CloseAllOtherWindows,
+    /// Save the placement of the windows as the session with the given name.
+    SaveSession {
+        name: String,
+    },
+    /// Place the windows as in the session with the given name, windows which are not open yet
+    /// are placed once they appear.
+    RestoreSession {
+        name: String,
+    },
     Other(String),
 }
 
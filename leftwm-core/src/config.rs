@@ -1,21 +1,25 @@
 mod insert_behavior;
+mod tag_config;
 mod workspace_config;
 
 use crate::display_servers::DisplayServer;
 use crate::layouts::LayoutMode;
 pub use crate::models::ScratchPad;
 pub use crate::models::{FocusBehaviour, Gutter, Margins, Size};
-use crate::models::{Manager, Window, WindowType};
+use crate::models::{Manager, Window, WindowHandle, WindowType};
 use crate::state::State;
 pub use insert_behavior::InsertBehavior;
 use leftwm_layouts::Layout;
-pub use workspace_config::Workspace;
+pub use tag_config::TagLayouts;
+pub use workspace_config::{numbered_workspaces, Workspace, WorkspaceProfile};
 
 pub trait Config {
     fn create_list_of_tag_labels(&self) -> Vec<String>;
 
     fn workspaces(&self) -> Option<Vec<Workspace>>;
 
+    fn workspace_profiles(&self) -> Vec<WorkspaceProfile>;
+
     fn focus_behaviour(&self) -> FocusBehaviour;
 
     fn mousekey(&self) -> Vec<String>;
@@ -26,6 +30,9 @@ pub trait Config {
 
     fn layout_definitions(&self) -> Vec<Layout>;
 
+    /// The layouts of the tags which have their own, by the label of the tag.
+    fn tag_layouts(&self) -> Vec<TagLayouts>;
+
     fn layout_mode(&self) -> LayoutMode;
 
     fn insert_behavior(&self) -> InsertBehavior;
@@ -34,7 +41,22 @@ pub trait Config {
 
     fn focus_new_windows(&self) -> bool;
 
-    fn command_handler<SERVER>(command: &str, manager: &mut Manager<Self, SERVER>) -> bool
+    /// Classes of the terminals which may swallow the windows launched from them.
+    fn terminal_classes(&self) -> Vec<String>;
+
+    /// Whether the window should take the place of the terminal it was launched from.
+    fn swallow(&self, state: &State, window: &Window) -> bool;
+
+    /// Handles a [`Command::Other`](crate::Command::Other), returning whether the display needs
+    /// a refresh.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message for the client when the command is not recognized or failed.
+    fn command_handler<SERVER>(
+        command: &str,
+        manager: &mut Manager<Self, SERVER>,
+    ) -> Result<bool, String>
     where
         SERVER: DisplayServer,
         Self: Sized;
@@ -59,19 +81,32 @@ pub trait Config {
     fn create_follows_cursor(&self) -> bool;
     fn reposition_cursor_on_resize(&self) -> bool;
 
-    /// Attempt to write current state to a file.
+    /// Attempt to write current state to a file, in the versioned format of [`State::saved`].
     ///
     /// It will be used to restore the state after soft reload.
     ///
     /// **Note:** this function cannot fail.
     fn save_state(&self, state: &State);
 
-    /// Load saved state if it exists.
+    /// Load saved state if it exists, see [`State::restore_saved`].
     fn load_state(&self, state: &mut State);
 
     /// Handle window placement based on `WM_CLASS`
     fn setup_predefined_window(&self, state: &mut State, window: &mut Window) -> bool;
 
+    /// Apply the window rules which opted in to run again once the title or `WM_CLASS` of a
+    /// window changed. `old` is the window before the change.
+    fn reevaluate_predefined_window(
+        &self,
+        state: &mut State,
+        old: &Window,
+        window: &mut Window,
+    ) -> bool;
+
+    /// Describe how the window rules score for a managed window, which one wins and what
+    /// applying it changes. Answers [`crate::Query::ExplainWindowRules`].
+    fn explain_window_rules(&self, state: &State, handle: WindowHandle) -> String;
+
     fn load_window(&self, window: &mut Window) {
         if window.r#type == WindowType::Normal {
             window.margin = self.margin();
@@ -81,6 +116,12 @@ pub trait Config {
             window.margin = Margins::new(0);
             window.border = 0;
         }
+        if let Some(border) = window.overrides.border_width {
+            window.border = border;
+        }
+        if let Some(margin) = window.overrides.margin {
+            window.margin = margin;
+        }
     }
 }
 
@@ -98,9 +139,16 @@ pub(crate) mod tests {
         pub layouts: Vec<String>,
         pub layout_definitions: Vec<Layout>,
         pub workspaces: Option<Vec<Workspace>>,
+        pub workspace_profiles: Vec<WorkspaceProfile>,
+        pub tag_layouts: Vec<TagLayouts>,
+        pub layout_mode: Option<LayoutMode>,
         pub insert_behavior: InsertBehavior,
         pub border_width: i32,
         pub single_window_border: bool,
+        pub terminal_classes: Vec<String>,
+        pub swallow: bool,
+        /// Whether the rule of `setup_predefined_window` runs again once the `WM_CLASS` changed.
+        pub reevaluate_on_change: bool,
     }
 
     impl Config for TestConfig {
@@ -110,6 +158,9 @@ pub(crate) mod tests {
         fn workspaces(&self) -> Option<Vec<Workspace>> {
             self.workspaces.clone()
         }
+        fn workspace_profiles(&self) -> Vec<WorkspaceProfile> {
+            self.workspace_profiles.clone()
+        }
         fn focus_behaviour(&self) -> FocusBehaviour {
             FocusBehaviour::ClickTo
         }
@@ -125,8 +176,11 @@ pub(crate) mod tests {
         fn layout_definitions(&self) -> Vec<Layout> {
             self.layout_definitions.clone()
         }
+        fn tag_layouts(&self) -> Vec<TagLayouts> {
+            self.tag_layouts.clone()
+        }
         fn layout_mode(&self) -> LayoutMode {
-            LayoutMode::Workspace
+            self.layout_mode.unwrap_or(LayoutMode::Workspace)
         }
 
         fn insert_behavior(&self) -> InsertBehavior {
@@ -137,19 +191,30 @@ pub(crate) mod tests {
             self.single_window_border
         }
 
+        fn terminal_classes(&self) -> Vec<String> {
+            self.terminal_classes.clone()
+        }
+
+        fn swallow(&self, _state: &State, _window: &Window) -> bool {
+            self.swallow
+        }
+
         fn focus_new_windows(&self) -> bool {
             false
         }
-        fn command_handler<SERVER>(command: &str, manager: &mut Manager<Self, SERVER>) -> bool
+        fn command_handler<SERVER>(
+            command: &str,
+            manager: &mut Manager<Self, SERVER>,
+        ) -> Result<bool, String>
         where
             SERVER: DisplayServer,
         {
             match command {
-                "GoToTag2" => manager.command_handler(&crate::Command::GoToTag {
+                "GoToTag2" => Ok(manager.command_handler(&crate::Command::GoToTag {
                     tag: 2,
                     swap: false,
-                }),
-                _ => unimplemented!("custom command handler: {:?}", command),
+                })),
+                _ => Err(format!("Command not recognized: {command}")),
             }
         }
         fn always_float(&self) -> bool {
@@ -211,6 +276,20 @@ pub(crate) mod tests {
                 false
             }
         }
+        fn reevaluate_predefined_window(
+            &self,
+            state: &mut State,
+            old: &Window,
+            window: &mut Window,
+        ) -> bool {
+            self.reevaluate_on_change
+                && old.res_class != window.res_class
+                && self.setup_predefined_window(state, window)
+        }
+        fn explain_window_rules(&self, state: &State, handle: WindowHandle) -> String {
+            let managed = state.windows.iter().any(|w| w.handle == handle);
+            format!("{handle:?} managed: {managed}")
+        }
         fn sloppy_mouse_follows_focus(&self) -> bool {
             true
         }
@@ -232,7 +311,10 @@ pub(crate) mod tests {
     fn ensure_command_handler_trait_boundary() {
         let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
         manager.screen_create_handler(Screen::default());
-        assert!(TestConfig::command_handler("GoToTag2", &mut manager));
+        assert_eq!(
+            TestConfig::command_handler("GoToTag2", &mut manager),
+            Ok(true)
+        );
         assert_eq!(manager.state.focus_manager.tag_history, &[2, 1]);
     }
 
@@ -245,4 +327,32 @@ pub(crate) mod tests {
         manager.window_created_handler(subject, 0, 0);
         assert!(manager.state.windows.iter().all(|w| w.has_tag(&2)));
     }
+
+    #[test]
+    fn window_rules_are_reevaluated_when_the_wm_class_changes() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.config.reevaluate_on_change = true;
+        manager.screen_create_handler(Screen::default());
+        let handle = WindowHandle::MockHandle(1);
+        manager.window_created_handler(Window::new(handle, None, None), 0, 0);
+        assert!(manager.state.windows[0].has_tag(&1));
+
+        let mut change = crate::models::WindowChange::new(handle);
+        change.res_class = Some(Some("ShouldGoToTag2".to_string()));
+        assert!(manager.window_changed_handler(change));
+        assert!(manager.state.windows[0].has_tag(&2));
+    }
+
+    #[test]
+    fn window_rules_are_left_alone_without_reevaluate_on_change() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let handle = WindowHandle::MockHandle(1);
+        manager.window_created_handler(Window::new(handle, None, None), 0, 0);
+
+        let mut change = crate::models::WindowChange::new(handle);
+        change.res_class = Some(Some("ShouldGoToTag2".to_string()));
+        manager.window_changed_handler(change);
+        assert!(manager.state.windows[0].has_tag(&1));
+    }
 }
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// The layouts of a tag, used instead of the ones of its workspace when layouts are kept per
+/// tag.
+#[derive(Serialize, Default, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TagLayouts {
+    /// The label of the tag.
+    pub tag: String,
+    /// The layouts cycled through on the tag, the ones of its workspace if missing.
+    #[serde(default)]
+    pub layouts: Option<Vec<String>>,
+    /// The layout the tag starts with.
+    #[serde(default)]
+    pub default_layout: Option<String>,
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
-use crate::models::Size;
+use super::Config;
+use crate::models::{Size, TagId, WorkspaceId};
 
 #[derive(Serialize, Default, Deserialize, Debug, Clone, PartialEq)]
 pub struct Workspace {
@@ -12,4 +13,78 @@ pub struct Workspace {
     pub relative: Option<bool>,
     pub max_window_width: Option<Size>,
     pub layouts: Option<Vec<String>>,
+    /// The tag shown when the workspace is created, if it is not shown elsewhere already.
+    #[serde(default)]
+    pub default_tag: Option<TagId>,
+}
+
+/// A named set of workspaces, used instead of the plain `workspaces` while exactly the
+/// given outputs are connected.
+#[derive(Serialize, Default, Deserialize, Debug, Clone, PartialEq)]
+pub struct WorkspaceProfile {
+    pub name: String,
+    /// The names of the outputs which have to be connected, in any order.
+    pub outputs: Vec<String>,
+    /// EDID identifiers (`MFG-PRODUCT-SERIAL`) of monitors which have to be connected as well.
+    #[serde(default)]
+    pub edids: Vec<String>,
+    pub workspaces: Vec<Workspace>,
+}
+
+impl WorkspaceProfile {
+    /// Whether the profile applies to the connected outputs and monitors.
+    #[must_use]
+    pub fn matches(&self, outputs: &[String], edids: &[String]) -> bool {
+        let mut wanted: Vec<&String> = self.outputs.iter().collect();
+        let mut connected: Vec<&String> = outputs.iter().collect();
+        wanted.sort();
+        wanted.dedup();
+        connected.sort();
+        connected.dedup();
+        wanted == connected && self.edids.iter().all(|edid| edids.contains(edid))
+    }
+}
+
+/// Every configured workspace with its ID, and the name of the profile it belongs to.
+///
+/// The plain `workspaces` come first, followed by the workspaces of each profile in order,
+/// so that IDs are unique across profiles.
+pub fn numbered_workspaces(config: &impl Config) -> Vec<(WorkspaceId, Option<String>, Workspace)> {
+    let plain = config
+        .workspaces()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|wsc| (None, wsc));
+    let profiles = config.workspace_profiles().into_iter().flat_map(|profile| {
+        let name = profile.name;
+        profile
+            .workspaces
+            .into_iter()
+            .map(move |wsc| (Some(name.clone()), wsc))
+    });
+    plain
+        .chain(profiles)
+        .enumerate()
+        .map(|(i, (profile, wsc))| (i + 1, profile, wsc))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profiles_match_the_set_of_connected_outputs() {
+        let profile = WorkspaceProfile {
+            name: "desk".to_string(),
+            outputs: vec!["DP-1".to_string(), "eDP-1".to_string()],
+            edids: vec!["DEL-A0F3-00000000".to_string()],
+            workspaces: vec![],
+        };
+        let outputs = vec!["eDP-1".to_string(), "DP-1".to_string()];
+        let edids = vec!["DEL-A0F3-00000000".to_string()];
+        assert!(profile.matches(&outputs, &edids));
+        assert!(!profile.matches(&outputs, &[]));
+        assert!(!profile.matches(&outputs[..1], &edids));
+    }
 }
@@ -1,3 +1,5 @@
+use crate::models::BorderColors;
+use crate::models::ScratchPadAnchor;
 use crate::models::TagId;
 use crate::models::Window;
 use crate::models::WindowHandle;
@@ -63,6 +65,16 @@ pub enum DisplayAction {
     /// Used to let the WM know of the tag for a given window.
     SetWindowTag(WindowHandle, Option<TagId>),
 
+    /// Used to let the WM know that the labels of the tags changed.
+    SetTagLabels(Vec<String>),
+
+    /// Used to let the DM know of the border colors of a window which differ from the theme.
+    SetBorderColors(WindowHandle, BorderColors),
+
+    /// Used to let the DM know that a window is shown (`true`) or hidden at the side of the
+    /// workspace it is anchored to, so that it can slide in or out.
+    SlideWindow(WindowHandle, ScratchPadAnchor, bool),
+
     /// Tell the DM to return to normal mode if it is not (ie resize a
     /// window or moving a window).
     NormalMode,
@@ -1,12 +1,24 @@
+use crate::errors::LeftError;
+use crate::models::{ScratchPadBinding, Session, LAST_SESSION};
+use crate::utils::command_socket::{PendingRequest, RequestKind, Response};
 use crate::{child_process::Nanny, config::Config};
 use crate::{
-    Command, CommandPipe, DisplayEvent, DisplayServer, Manager, Mode, StateSocket, Window,
+    Command, CommandPipe, CommandSocket, DisplayEvent, DisplayServer, EventSocket, Manager, Mode,
+    StateSocket, Window,
 };
+use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
 use std::sync::{atomic::Ordering, Once};
+use std::time::{Duration, Instant};
 
 use tracing::error;
 
+/// Runtime file keeping the windows of the scratchpads across hard reloads and crashes.
+const SCRATCHPAD_BINDINGS_FILE: &str = "scratchpads.json";
+
+/// Least time between two saves of the last session, it is saved once more when the loop ends.
+const SESSION_SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
 /// Errors which can appear while running the event loop.
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Error {
@@ -17,6 +29,13 @@ pub enum Error {
     ConnectToFile(PathBuf),
 }
 
+/// The sockets clients can connect to, which have to be shutdown before the event loop ends.
+struct Sockets {
+    state: StateSocket,
+    events: EventSocket,
+    command: CommandSocket,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum EventResponse {
     None,
@@ -27,24 +46,30 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
     /// Starts the event loop of leftwm
     ///
     /// # Errors
-    /// `EventResponse` if the initialisation of the command pipe or/and the state socket failed.
+    /// `EventResponse` if the initialisation of the command pipe, the command socket, the state
+    /// socket or the event socket failed.
     pub async fn start_event_loop(mut self) -> Result<(), Error> {
-        let state_socket = get_state_socket().await?;
+        let sockets = Sockets {
+            state: get_state_socket().await?,
+            events: get_event_socket().await?,
+            command: get_command_socket().await?,
+        };
         let command_pipe = get_command_pipe().await?;
 
         self.call_up_scripts();
-        self.event_loop(state_socket, command_pipe).await
+        self.event_loop(sockets, command_pipe).await
     }
 
     async fn event_loop(
         &mut self,
-        mut state_socket: StateSocket,
+        mut sockets: Sockets,
         mut command_pipe: CommandPipe,
     ) -> Result<(), Error> {
         let after_first_loop: Once = Once::new();
         let mut event_buffer: Vec<DisplayEvent> = vec![];
-        while self.should_keep_running(&mut state_socket).await {
-            self.update_manager_state(&mut state_socket).await;
+        while self.should_keep_running(&mut sockets).await {
+            self.update_manager_state(&mut sockets.state).await;
+            self.publish_events(&mut sockets.events).await;
             self.display_server.flush();
 
             let response: EventResponse = tokio::select! {
@@ -62,6 +87,7 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
                         continue;
                     }
                 Some(cmd) = command_pipe.read_command(), if event_buffer.is_empty() => self.execute_command(&cmd),
+                Some(request) = sockets.command.read_request(), if event_buffer.is_empty() => self.execute_request(request),
                 else => self.execute_display_events(&mut event_buffer),
             };
 
@@ -76,12 +102,27 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             // before we can restore the previous state
             after_first_loop.call_once(|| {
                 self.config.load_state(&mut self.state);
+                self.reattach_scratchpads();
+                self.restore_last_session();
             });
+            self.save_scratchpad_bindings();
+            if self
+                .last_session_saved
+                .map_or(true, |saved| saved.elapsed() >= SESSION_SAVE_INTERVAL)
+            {
+                self.save_last_session();
+            }
 
             if self.reap_requested.swap(false, Ordering::SeqCst) {
                 self.children.remove_finished_children();
+                // Scratchpads whose process exited without a window are no longer waited for.
+                let children = &self.children;
+                self.state
+                    .spawned_scratchpads
+                    .retain(|pid, _| children.contains(*pid));
             }
         }
+        self.save_last_session();
 
         Ok(())
     }
@@ -92,9 +133,18 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         }
     }
 
-    async fn should_keep_running(&self, state_socket: &mut StateSocket) -> bool {
+    async fn publish_events(&mut self, event_socket: &mut EventSocket) {
+        event_socket
+            .write_events(self.state.events.drain(..))
+            .await
+            .ok();
+    }
+
+    async fn should_keep_running(&self, sockets: &mut Sockets) -> bool {
         if self.reload_requested {
-            state_socket.shutdown().await;
+            sockets.state.shutdown().await;
+            sockets.events.shutdown().await;
+            sockets.command.shutdown().await;
             false
         } else {
             true
@@ -119,8 +169,11 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         self.update_windows();
 
         match self.state.mode {
-            // When (resizing / moving) only deal with the single window.
-            Mode::ResizingWindow(h) | Mode::MovingWindow(h) => {
+            // When (resizing / moving) only deal with the single window, unless resizing moves a
+            // boundary between tiled windows.
+            Mode::ResizingWindow(h) | Mode::MovingWindow(h)
+                if self.state.tiled_resize.is_none() =>
+            {
                 if let Some(window) = self.state.windows.iter().find(|w| w.handle == h) {
                     self.display_server.update_windows(vec![window]);
                 }
@@ -140,6 +193,34 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         }
     }
 
+    fn execute_request(&mut self, pending: PendingRequest) -> EventResponse {
+        let id = pending.request.id;
+        match &pending.request.kind {
+            RequestKind::Command(command) => match self.try_command_handler(command) {
+                Ok(refresh) => {
+                    pending.respond(Response::ok(id, serde_json::Value::Null));
+                    if refresh {
+                        EventResponse::DisplayRefreshNeeded
+                    } else {
+                        EventResponse::None
+                    }
+                }
+                Err(err) => {
+                    pending.respond(Response::error(id, err.to_string()));
+                    EventResponse::None
+                }
+            },
+            RequestKind::Query(query) => {
+                let answer = match query.answer(&self.config, &self.state) {
+                    Ok(payload) => Response::ok(id, payload),
+                    Err(err) => Response::error(id, format!("Error answering query: {err}")),
+                };
+                pending.respond(answer);
+                EventResponse::None
+            }
+        }
+    }
+
     fn add_events(&mut self, event_buffer: &mut Vec<DisplayEvent>) -> EventResponse {
         event_buffer.append(&mut self.display_server.get_next_events());
         EventResponse::None
@@ -163,6 +244,61 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         }
     }
 
+    /// Reattach the windows of the scratchpads saved before a hard reload or a crash.
+    fn reattach_scratchpads(&mut self) {
+        match read_scratchpad_bindings() {
+            Ok(bindings) => {
+                self.state.reattach_scratchpads(&bindings);
+                // The file holds these bindings, it is only written again once they change.
+                self.scratchpad_bindings = bindings;
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => tracing::warn!("Cannot read the scratchpads: {}", err),
+        }
+    }
+
+    /// Save the windows of the scratchpads whenever they change, so that they survive the worker.
+    fn save_scratchpad_bindings(&mut self) {
+        let bindings = self.state.scratchpad_bindings();
+        if bindings == self.scratchpad_bindings {
+            return;
+        }
+        if let Err(err) = write_scratchpad_bindings(&bindings) {
+            tracing::warn!("Cannot save the scratchpads: {}", err);
+        }
+        self.scratchpad_bindings = bindings;
+    }
+
+    /// Restore the session saved last, the windows of the previous login are placed as they
+    /// appear again.
+    fn restore_last_session(&mut self) {
+        match Session::load(LAST_SESSION) {
+            Ok(session) => self.state.restore_session(session),
+            Err(LeftError::IoError(err)) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => tracing::warn!("Cannot read the last session: {}", err),
+        }
+    }
+
+    /// Save the session if it changed, so that it survives a logout. Windows of the restored
+    /// session which did not appear yet are kept in it.
+    fn save_last_session(&mut self) {
+        if self.state.mode != Mode::Normal {
+            return;
+        }
+        self.last_session_saved = Some(Instant::now());
+        let mut session = self.state.session();
+        session
+            .windows
+            .extend(self.state.restored_session.pending.windows.iter().cloned());
+        if session == self.last_session {
+            return;
+        }
+        if let Err(err) = session.save(LAST_SESSION) {
+            tracing::warn!("Cannot save the session: {}", err);
+        }
+        self.last_session = session;
+    }
+
     fn call_up_scripts(&mut self) {
         match Nanny::run_global_up_script() {
             Ok(child) => {
@@ -194,6 +330,21 @@ async fn get_state_socket() -> Result<StateSocket, Error> {
     Ok(state_socket)
 }
 
+async fn get_event_socket() -> Result<EventSocket, Error> {
+    let socket_filename = Path::new("current_events.sock");
+    let socket_file = place_runtime_file(socket_filename)
+        .map_err(|_| Error::CreateFile(socket_filename.into()))?;
+
+    let mut event_socket = EventSocket::default();
+
+    event_socket
+        .listen(socket_file)
+        .await
+        .map_err(|_| Error::ConnectToFile(socket_filename.into()))?;
+
+    Ok(event_socket)
+}
+
 async fn get_command_pipe() -> Result<CommandPipe, Error> {
     let file_name = CommandPipe::pipe_name();
 
@@ -205,6 +356,21 @@ async fn get_command_pipe() -> Result<CommandPipe, Error> {
         .map_err(|_| Error::ConnectToFile(file_name))
 }
 
+async fn get_command_socket() -> Result<CommandSocket, Error> {
+    let file_name = CommandSocket::socket_name();
+    let socket_file =
+        place_runtime_file(&file_name).map_err(|_| Error::CreateFile(file_name.clone()))?;
+
+    let mut command_socket = CommandSocket::default();
+
+    command_socket
+        .listen(socket_file)
+        .await
+        .map_err(|_| Error::ConnectToFile(file_name))?;
+
+    Ok(command_socket)
+}
+
 fn place_runtime_file<P>(path: P) -> std::io::Result<PathBuf>
 where
     P: AsRef<Path>,
@@ -212,7 +378,72 @@ where
     xdg::BaseDirectories::with_prefix("leftwm")?.place_runtime_file(path)
 }
 
+fn read_scratchpad_bindings() -> std::io::Result<Vec<ScratchPadBinding>> {
+    let path = place_runtime_file(SCRATCHPAD_BINDINGS_FILE)?;
+    Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+}
+
+fn write_scratchpad_bindings(bindings: &[ScratchPadBinding]) -> std::io::Result<()> {
+    let path = place_runtime_file(SCRATCHPAD_BINDINGS_FILE)?;
+    std::fs::write(path, serde_json::to_vec(bindings)?)
+}
+
 async fn timeout(mills: u64) {
     use tokio::time::{sleep, Duration};
     sleep(Duration::from_millis(mills)).await;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Screen;
+    use crate::utils::command_socket::{Request, Status};
+    use crate::utils::helpers::test::temp_path;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::UnixStream;
+
+    async fn send(socket_file: PathBuf, request: Request) -> Response {
+        let mut stream = UnixStream::connect(socket_file).await.unwrap();
+        let mut line = serde_json::to_string(&request).unwrap();
+        line.push('\n');
+        stream.write_all(line.as_bytes()).await.unwrap();
+        let mut lines = BufReader::new(stream).lines();
+        let reply = lines.next_line().await.expect("Read next line").unwrap();
+        serde_json::from_str(&reply).unwrap()
+    }
+
+    #[tokio::test]
+    async fn failed_commands_are_answered_with_an_error() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let socket_file = temp_path().await.unwrap();
+        let mut command_socket = CommandSocket::default();
+        command_socket.listen(socket_file.clone()).await.unwrap();
+
+        let requests = [
+            (1, Command::Other("NotACommand".to_string())),
+            (2, Command::CloseWindow),
+            (
+                3,
+                Command::GoToTag {
+                    tag: 2,
+                    swap: false,
+                },
+            ),
+        ];
+        for (id, command) in requests {
+            let request = Request {
+                id,
+                kind: RequestKind::Command(command),
+            };
+            let client = tokio::spawn(send(socket_file.clone(), request));
+            let pending = command_socket.read_request().await.unwrap();
+            manager.execute_request(pending);
+            let response = client.await.unwrap();
+            assert_eq!(response.id, id);
+            let expected = if id == 3 { Status::Ok } else { Status::Error };
+            assert_eq!(response.status, expected);
+        }
+        command_socket.shutdown().await;
+    }
+}
@@ -4,6 +4,9 @@ mod focus_handler;
 mod goto_tag_handler;
 mod mouse_combo_handler;
 mod screen_create_handler;
+mod screen_destroy_handler;
+mod screen_update_handler;
+mod tag_handler;
 mod window_handler;
 mod window_move_handler;
 mod window_resize_handler;
@@ -7,15 +7,17 @@ mod scratchpad_handler;
 pub use scratchpad_handler::{Direction, ReleaseScratchPadOption};
 
 use super::*;
-use crate::command::FocusDeltaBehavior;
+use crate::command::{CommandError, FocusDeltaBehavior};
 use crate::display_action::DisplayAction;
 use crate::display_servers::DisplayServer;
-use crate::layouts::{self, MAIN_AND_DECK, MONOCLE};
-use crate::models::{TagId, WindowState};
+use crate::layouts::{self, SplitDirection, SplitTree, MAIN_AND_DECK, MONOCLE};
+use crate::models::{Session, Side, TagId, WindowState, Xyhw};
 use crate::state::State;
 use crate::utils::helpers;
 use crate::utils::helpers::relative_find;
+use crate::StateEvent;
 use crate::{config::Config, models::FocusBehaviour};
+use leftwm_layouts::geometry::Rect;
 
 impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
     /* When adding a command
@@ -28,7 +30,21 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
      *  */
     /// Processes a command and invokes the associated function.
     pub fn command_handler(&mut self, command: &Command) -> bool {
-        process_internal(self, command).unwrap_or(false)
+        self.try_command_handler(command).unwrap_or(false)
+    }
+
+    /// Processes a command like [`Manager::command_handler`], returning why it could not be
+    /// executed instead of ignoring it.
+    ///
+    /// # Errors
+    ///
+    /// Will error if there was nothing to execute the command on, or if the handler of a
+    /// [`Command::Other`] failed.
+    pub fn try_command_handler(&mut self, command: &Command) -> Result<bool, CommandError> {
+        if let Command::Other(cmd) = command {
+            return C::command_handler(cmd, self).map_err(CommandError::Other);
+        }
+        process_internal(self, command).ok_or(CommandError::NotExecuted)
     }
 }
 
@@ -36,11 +52,10 @@ macro_rules! move_focus_common_vars {
     ($func:ident ($state:expr $(, $arg:expr )* $(,)? )) => {{
         let handle = $state.focus_manager.window(&$state.windows)?.handle;
         let tag_id = $state.focus_manager.tag(0)?;
-        let ws_id = $state.focus_manager.workspace(&$state.workspaces)?.id;
-        let layout = Some($state.layout_manager.layout(ws_id, tag_id).name.to_owned());
+        let workspace = $state.focus_manager.workspace(&$state.workspaces)?.clone();
+        let layout = Some($state.layout_manager.layout(workspace.id, tag_id).name.to_owned());
 
-        let for_active_workspace =
-            |x: &Window| -> bool { x.tag == Some(tag_id) && x.is_managed() };
+        let for_active_workspace = |x: &Window| -> bool { workspace.is_managed(x) };
 
         let to_reorder = helpers::vec_extract(&mut $state.windows, for_active_workspace);
         $func($state, handle, &layout, to_reorder, $($arg),*)
@@ -67,6 +82,9 @@ fn process_internal<C: Config, SERVER: DisplayServer>(
         Command::PrevScratchPadWindow { scratchpad } => {
             scratchpad_handler::cycle_scratchpad_window(manager, scratchpad, Direction::Backward)
         }
+        Command::CycleScratchPadGroup { group } => {
+            scratchpad_handler::cycle_scratchpad_group(manager, group)
+        }
 
         Command::ToggleMaximized => toggle_state(state, WindowState::Maximized),
         Command::ToggleFullScreen => toggle_state(state, WindowState::Fullscreen),
@@ -83,8 +101,13 @@ fn process_internal<C: Config, SERVER: DisplayServer>(
         Command::MoveWindowDown => move_focus_common_vars!(move_window_change(state, 1)),
         Command::MoveWindowTop { swap } => move_focus_common_vars!(move_window_top(state, *swap)),
         Command::SwapWindowTop { swap } => move_focus_common_vars!(swap_window_top(state, *swap)),
+        Command::SwapWindowInDirection { direction } => {
+            swap_window_in_direction(manager, direction)
+        }
 
         Command::GoToTag { tag, swap } => goto_tag(state, *tag, *swap),
+        Command::ToggleTagView { tag } => toggle_tag_view(state, *tag),
+        Command::ToggleWindowTags { window, tags } => toggle_window_tags(state, *window, tags),
         Command::ReturnToLastTag => return_to_last_tag(state),
 
         Command::CloseWindow => close_window(state),
@@ -93,6 +116,9 @@ fn process_internal<C: Config, SERVER: DisplayServer>(
         Command::PreviousLayout => previous_layout(state),
 
         Command::SetLayout(layout) => set_layout(layout.as_str(), state),
+        Command::SplitWindow { direction } => split_window(state, *direction),
+        Command::ResizeSplit(delta) => resize_split(state, *delta),
+        Command::MoveWindowInTree { direction } => move_window_in_tree(state, direction),
 
         Command::FloatingToTile => floating_to_tile(state),
         Command::TileToFloating => tile_to_floating(state),
@@ -111,6 +137,9 @@ fn process_internal<C: Config, SERVER: DisplayServer>(
         Command::FocusWindow(param) => focus_window(state, param),
         Command::FocusWindowUp => move_focus_common_vars!(focus_window_change(state, -1)),
         Command::FocusWindowDown => move_focus_common_vars!(focus_window_change(state, 1)),
+        Command::FocusWindowInDirection { direction } => {
+            focus_window_in_direction(state, direction)
+        }
         Command::FocusWindowTop { swap } => focus_window_top(state, *swap),
         Command::FocusWorkspaceNext => focus_workspace_change(state, 1),
         Command::FocusWorkspacePrevious => focus_workspace_change(state, -1),
@@ -131,11 +160,11 @@ fn process_internal<C: Config, SERVER: DisplayServer>(
             }
             manager.config.save_state(&manager.state);
             manager.hard_reload();
-            None
+            Some(false)
         }
         Command::HardReload => {
             manager.hard_reload();
-            None
+            Some(false)
         }
 
         Command::RotateTag => rotate_tag(state),
@@ -148,12 +177,25 @@ fn process_internal<C: Config, SERVER: DisplayServer>(
         }
         Command::IncreaseMainCount() => change_main_count(state, 1),
         Command::DecreaseMainCount() => change_main_count(state, -1),
+        Command::IncreaseWindowWeight(delta) => change_window_weight(state, *delta, 1),
+        Command::DecreaseWindowWeight(delta) => change_window_weight(state, *delta, -1),
+        Command::ResetWindowWeights => reset_window_weights(state),
         Command::SetMarginMultiplier(multiplier) => set_margin_multiplier(state, *multiplier),
         Command::SendWorkspaceToTag(ws_index, tag_index) => {
             Some(send_workspace_to_tag(state, *ws_index, *tag_index))
         }
+        Command::AddTag { label } => {
+            state.add_tag(label);
+            Some(true)
+        }
+        Command::RemoveTag { tag } => Some(state.remove_tag(*tag)),
+        Command::RenameTag { tag, label } => Some(state.rename_tag(*tag, label)),
+        Command::MoveTag { tag, position } => Some(state.move_tag(*tag, *position)),
         Command::CloseAllOtherWindows => close_all_other_windows(state),
-        Command::Other(cmd) => Some(C::command_handler(cmd, manager)),
+        Command::SaveSession { name } => Some(save_session(state, name)),
+        Command::RestoreSession { name } => Some(restore_session(state, name)),
+        // Handled by `Manager::try_command_handler`, so that its error is not lost.
+        Command::Other(_) => None,
     }
 }
 
@@ -379,6 +421,40 @@ fn goto_tag(state: &mut State, input_tag: TagId, current_tag_swap: bool) -> Opti
     state.goto_tag_handler(destination_tag)
 }
 
+fn toggle_tag_view(state: &mut State, tag: TagId) -> Option<bool> {
+    if tag < 1 || tag > state.tags.len_normal() {
+        return Some(false);
+    }
+    let ws_id = state.focus_manager.workspace(&state.workspaces)?.id;
+    // A tag can only be in view on a single workspace.
+    if state
+        .workspaces
+        .iter()
+        .any(|ws| ws.id != ws_id && ws.shows_tag(&tag))
+    {
+        return Some(false);
+    }
+    let workspace = state.focus_manager.workspace_mut(&mut state.workspaces)?;
+    Some(workspace.toggle_tag(&tag))
+}
+
+fn toggle_window_tags(
+    state: &mut State,
+    window: Option<WindowHandle>,
+    tags: &[TagId],
+) -> Option<bool> {
+    let tags_len = state.tags.len_normal();
+    if tags.iter().any(|tag| *tag < 1 || *tag > tags_len) {
+        return Some(false);
+    }
+    let handle = window.or(*state.focus_manager.window_history.get(0)?)?;
+    let window = state.windows.iter_mut().find(|w| w.handle == handle)?;
+    tags.iter().for_each(|tag| window.toggle_tag(tag));
+    let act = DisplayAction::SetWindowTag(window.handle, window.tag);
+    state.actions.push_back(act);
+    Some(true)
+}
+
 fn return_to_last_tag(state: &mut State) -> Option<bool> {
     let previous_tag = state.focus_manager.tag(1).unwrap_or_default();
     state.goto_tag_handler(previous_tag)
@@ -396,7 +472,7 @@ fn focus_window(state: &mut State, param: &str) -> Option<bool> {
                 .handle;
 
             state.handle_window_focus(&handle);
-            None
+            Some(false)
         }
         Err(_) => focus_window_by_class(state, param),
         Ok(_) => None,
@@ -429,7 +505,7 @@ fn focus_window_by_class(state: &mut State, window_class: &str) -> Option<bool>
 
     if target_window.visible() {
         state.handle_window_focus(&handle);
-        return None;
+        return Some(false);
     }
 
     let tag_id = target_window.tag?;
@@ -508,7 +584,7 @@ fn close_window(state: &mut State) -> Option<bool> {
         let act = DisplayAction::KillWindow(window.handle);
         state.actions.push_back(act);
     }
-    None
+    Some(false)
 }
 
 fn move_to_last_workspace(state: &mut State) -> Option<bool> {
@@ -524,20 +600,29 @@ fn move_to_last_workspace(state: &mut State) -> Option<bool> {
 
 fn next_layout(state: &mut State) -> Option<bool> {
     let workspace = state.focus_manager.workspace_mut(&mut state.workspaces)?;
-    state
-        .layout_manager
-        .cycle_next_layout(workspace.id, workspace.tag.unwrap_or(1));
+    let (ws_id, tag_id) = (workspace.id, workspace.tag.unwrap_or(1));
+    state.layout_manager.cycle_next_layout(ws_id, tag_id);
+    push_layout_changed(state, ws_id, tag_id);
     Some(true)
 }
 
 fn previous_layout(state: &mut State) -> Option<bool> {
     let workspace = state.focus_manager.workspace_mut(&mut state.workspaces)?;
-    state
-        .layout_manager
-        .cycle_previous_layout(workspace.id, workspace.tag.unwrap_or(1));
+    let (ws_id, tag_id) = (workspace.id, workspace.tag.unwrap_or(1));
+    state.layout_manager.cycle_previous_layout(ws_id, tag_id);
+    push_layout_changed(state, ws_id, tag_id);
     Some(true)
 }
 
+fn push_layout_changed(state: &mut State, ws_id: usize, tag_id: TagId) {
+    let layout = state.layout_manager.layout(ws_id, tag_id).name.clone();
+    state.events.push_back(StateEvent::LayoutChanged {
+        workspace: ws_id,
+        tag: tag_id,
+        layout,
+    });
+}
+
 fn set_layout(layout: &str, state: &mut State) -> Option<bool> {
     let tag_id = state.focus_manager.tag(0)?;
     // When switching to Monocle or MainAndDeck layout while in Driven
@@ -587,12 +672,41 @@ fn set_layout(layout: &str, state: &mut State) -> Option<bool> {
         }
     }
     let workspace = state.focus_manager.workspace_mut(&mut state.workspaces)?;
-    state
-        .layout_manager
-        .set_layout(workspace.id, tag_id, layout);
+    let ws_id = workspace.id;
+    state.layout_manager.set_layout(ws_id, tag_id, layout);
+    push_layout_changed(state, ws_id, tag_id);
     Some(true)
 }
 
+/// The focused window, the split tree of its tag and the area of the tree, if the tag is
+/// arranged by hand.
+fn focused_split_tree(state: &mut State) -> Option<(WindowHandle, &mut SplitTree, Rect)> {
+    let handle = state.focus_manager.window(&state.windows)?.handle;
+    let workspace = state.focus_manager.workspace(&state.workspaces)?;
+    let (ws_id, tag_id, area) = (workspace.id, workspace.tag?, workspace.rect());
+    if state.layout_manager.layout(ws_id, tag_id).name != layouts::MANUAL {
+        return None;
+    }
+    let tree = state.split_trees.get_mut(&tag_id)?;
+    Some((handle, tree, area))
+}
+
+fn split_window(state: &mut State, direction: SplitDirection) -> Option<bool> {
+    let (handle, tree, _) = focused_split_tree(state)?;
+    tree.split(handle, direction);
+    Some(false)
+}
+
+fn resize_split(state: &mut State, delta: i32) -> Option<bool> {
+    let (handle, tree, _) = focused_split_tree(state)?;
+    Some(tree.resize(handle, delta as f32 / 100.0))
+}
+
+fn move_window_in_tree(state: &mut State, direction: &Side) -> Option<bool> {
+    let (handle, tree, area) = focused_split_tree(state)?;
+    Some(tree.move_window(handle, direction, area))
+}
+
 fn floating_to_tile(state: &mut State) -> Option<bool> {
     let workspace = state.focus_manager.workspace(&state.workspaces)?;
     let window = state.focus_manager.window_mut(&mut state.windows)?;
@@ -800,7 +914,137 @@ fn focus_window_top(state: &mut State, swap: bool) -> Option<bool> {
         (Some(next), Some(cur), _) if next != cur => state.handle_window_focus(&next),
         _ => {}
     }
-    None
+    Some(false)
+}
+
+/// The closest window on the given side of `from` for which `candidate` holds.
+fn closest_window(
+    state: &State,
+    from: &Xyhw,
+    side: &Side,
+    candidate: impl Fn(&Window) -> bool,
+) -> Option<WindowHandle> {
+    state
+        .windows
+        .iter()
+        .filter(|w| w.visible() && candidate(w))
+        .filter_map(|w| {
+            let distance = from.distance_towards(&w.calculated_xyhw(), side);
+            distance.map(|d| (d, w.handle))
+        })
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, handle)| handle)
+}
+
+/// The workspace next to the given one on the given side, on the same or on another screen.
+fn adjacent_workspace(state: &State, workspace: &Workspace, side: &Side) -> Option<Workspace> {
+    state
+        .workspaces
+        .iter()
+        .filter(|ws| ws.id != workspace.id)
+        .filter_map(|ws| {
+            workspace
+                .xyhw
+                .distance_towards(&ws.xyhw, side)
+                .map(|d| (d, ws))
+        })
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, ws)| ws.clone())
+}
+
+fn focus_window_in_direction(state: &mut State, side: &Side) -> Option<bool> {
+    let workspace = state.focus_manager.workspace(&state.workspaces)?.clone();
+    let focused = state
+        .focus_manager
+        .window(&state.windows)
+        .filter(|w| workspace.is_managed(w));
+    let handle = focused.map(|w| w.handle);
+    let from = focused.map_or(workspace.xyhw, Window::calculated_xyhw);
+
+    let is_other = |w: &Window| Some(w.handle) != handle;
+    if let Some(target) = closest_window(state, &from, side, |w| {
+        is_other(w) && workspace.is_managed(w)
+    }) {
+        state.handle_window_focus(&target);
+        return Some(false);
+    }
+
+    let next = adjacent_workspace(state, &workspace, side)?;
+    let target = closest_window(state, &from, side, |w| next.is_managed(w)).or_else(|| {
+        next.tag
+            .and_then(|tag| state.focus_manager.tags_last_window.get(&tag).copied())
+    });
+    match target {
+        Some(target) => state.handle_window_focus(&target),
+        None => {
+            if state.focus_manager.behaviour.is_sloppy()
+                && state.focus_manager.sloppy_mouse_follows_focus
+            {
+                let act = DisplayAction::MoveMouseOverPoint(next.xyhw.center());
+                state.actions.push_back(act);
+            }
+            state.focus_workspace(&next);
+        }
+    }
+    Some(false)
+}
+
+fn swap_window_in_direction<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    side: &Side,
+) -> Option<bool> {
+    let state = &mut manager.state;
+    let workspace = state.focus_manager.workspace(&state.workspaces)?.clone();
+    let window = state
+        .focus_manager
+        .window(&state.windows)
+        .filter(|w| workspace.is_managed(w) && !w.floating())?;
+    let (handle, from) = (window.handle, window.calculated_xyhw());
+
+    let is_tiled = |w: &Window| w.handle != handle && !w.floating();
+    if let Some(target) = closest_window(state, &from, side, |w| {
+        is_tiled(w) && workspace.is_managed(w)
+    }) {
+        swap_windows(state, handle, target);
+    } else {
+        let next = adjacent_workspace(state, &workspace, side)?;
+        match closest_window(state, &from, side, |w| is_tiled(w) && next.is_managed(w)) {
+            Some(target) => {
+                swap_windows(state, handle, target);
+            }
+            // Without a window to swap with, the window is moved onto the workspace.
+            None => {
+                _ = move_to_tag(Some(handle), next.tag?, manager);
+            }
+        }
+    }
+    manager.state.handle_window_focus(&handle);
+    Some(true)
+}
+
+/// Exchange the places of two windows, and their tags if they are on different ones.
+fn swap_windows(state: &mut State, a: WindowHandle, b: WindowHandle) -> Option<()> {
+    let first = state.windows.iter().position(|w| w.handle == a)?;
+    let second = state.windows.iter().position(|w| w.handle == b)?;
+    let (low, high) = (first.min(second), first.max(second));
+    let (head, tail) = state.windows.split_at_mut(high);
+    let (x, y) = (&mut head[low], &mut tail[0]);
+    if x.tag != y.tag || x.extra_tags != y.extra_tags {
+        std::mem::swap(&mut x.tag, &mut y.tag);
+        std::mem::swap(&mut x.extra_tags, &mut y.extra_tags);
+        let (x_margin, y_margin) = (x.margin_multiplier(), y.margin_multiplier());
+        x.apply_margin_multiplier(y_margin);
+        y.apply_margin_multiplier(x_margin);
+        for w in [&*x, &*y] {
+            let act = DisplayAction::SetWindowTag(w.handle, w.tag);
+            state.actions.push_back(act);
+        }
+    }
+    state.windows.swap(first, second);
+    for tree in state.split_trees.values_mut() {
+        tree.swap(a, b);
+    }
+    Some(())
 }
 
 fn close_all_other_windows(state: &mut State) -> Option<bool> {
@@ -820,6 +1064,26 @@ fn close_all_other_windows(state: &mut State) -> Option<bool> {
     Some(true)
 }
 
+fn save_session(state: &State, name: &str) -> bool {
+    if let Err(err) = state.session().save(name) {
+        tracing::warn!("Cannot save the session {}: {}", name, err);
+    }
+    false
+}
+
+fn restore_session(state: &mut State, name: &str) -> bool {
+    match Session::load(name) {
+        Ok(session) => {
+            state.restore_session(session);
+            true
+        }
+        Err(err) => {
+            tracing::warn!("Cannot restore the session {}: {}", name, err);
+            false
+        }
+    }
+}
+
 fn focus_workspace_change(state: &mut State, val: i32) -> Option<bool> {
     let current = state.focus_manager.workspace(&state.workspaces)?;
     let workspace = helpers::relative_find(&state.workspaces, |w| w == current, val, true)?.clone();
@@ -836,7 +1100,7 @@ fn focus_workspace_change(state: &mut State, val: i32) -> Option<bool> {
         state.actions.push_back(action);
     }
     state.focus_workspace(&workspace);
-    None
+    Some(false)
 }
 
 fn rotate_tag(state: &mut State) -> Option<bool> {
@@ -870,6 +1134,24 @@ fn change_main_count(state: &mut State, factor: i8) -> Option<bool> {
     Some(true)
 }
 
+fn change_window_weight(state: &mut State, delta: i32, factor: i8) -> Option<bool> {
+    let tag_id = state.focus_manager.tag(0)?;
+    let window = state.focus_manager.window_mut(&mut state.windows)?;
+    if window.floating() {
+        return None;
+    }
+    window.change_weight(tag_id, (delta * i32::from(factor)) as f32 / 100.0);
+    Some(true)
+}
+
+fn reset_window_weights(state: &mut State) -> Option<bool> {
+    let tag_id = state.focus_manager.tag(0)?;
+    for window in &mut state.windows {
+        window.weights.remove(&tag_id);
+    }
+    Some(true)
+}
+
 fn set_margin_multiplier(state: &mut State, margin_multiplier: f32) -> Option<bool> {
     let ws = state.focus_manager.workspace_mut(&mut state.workspaces)?;
     ws.set_margin_multiplier(margin_multiplier);
@@ -1423,6 +1705,145 @@ mod tests {
         assert_eq!(manager.state.focus_manager.tag(3).unwrap_or_default(), 6);
     }
 
+    #[test]
+    fn toggle_tag_view_should_tile_windows_of_both_tags() {
+        let mut manager =
+            Manager::new_test(vec!["1".to_string(), "2".to_string(), "3".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.screen_create_handler(Screen::default());
+        for i in 1..=2 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.state.windows[1].tag(&3);
+
+        // Tag 2 is focused on the second workspace.
+        assert!(!manager.command_handler(&Command::ToggleTagView { tag: 2 }));
+        assert!(manager.command_handler(&Command::ToggleTagView { tag: 3 }));
+        let workspace = &manager.state.workspaces[0];
+        assert!(manager
+            .state
+            .windows
+            .iter()
+            .all(|w| workspace.is_managed(w)));
+
+        manager.update_windows();
+        assert!(manager.state.windows.iter().all(Window::visible));
+        assert_ne!(
+            manager.state.windows[0].normal, manager.state.windows[1].normal,
+            "both windows should be tiled"
+        );
+
+        // Going to a tag resets the view.
+        assert!(manager.command_handler(&Command::GoToTag {
+            tag: 1,
+            swap: false
+        }));
+        assert!(manager.state.workspaces[0].extra_tags.is_empty());
+    }
+
+    #[test]
+    fn directional_commands_should_cross_into_the_adjacent_workspace() {
+        use crate::models::BBox;
+        let mut manager =
+            Manager::new_test(vec!["1".to_string(), "2".to_string(), "3".to_string()]);
+        for x in [0, 800] {
+            let bbox = BBox {
+                x,
+                y: 0,
+                width: 800,
+                height: 600,
+            };
+            manager.screen_create_handler(Screen::new(bbox, format!("screen-{x}")));
+        }
+        for i in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+        }
+        for window in &mut manager.state.windows {
+            let tag = if window.handle == WindowHandle::MockHandle(3) {
+                2
+            } else {
+                1
+            };
+            window.tag(&tag);
+        }
+        let ws_id = manager.state.workspaces[0].id;
+        manager
+            .state
+            .layout_manager
+            .set_layout(ws_id, 1, layouts::MAIN_AND_VERT_STACK);
+        manager.update_windows();
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+
+        let right = Command::FocusWindowInDirection {
+            direction: Side::Right,
+        };
+        let focused = |manager: &Manager<_, _>| {
+            manager
+                .state
+                .focus_manager
+                .window(&manager.state.windows)
+                .map(|w| w.handle)
+        };
+        manager.command_handler(&right);
+        assert_eq!(focused(&manager), Some(WindowHandle::MockHandle(2)));
+        manager.command_handler(&right);
+        assert_eq!(focused(&manager), Some(WindowHandle::MockHandle(3)));
+
+        assert!(manager.command_handler(&Command::SwapWindowInDirection {
+            direction: Side::Left
+        }));
+        let tag_of = |handle| {
+            manager
+                .state
+                .windows
+                .iter()
+                .find(|w| w.handle == WindowHandle::MockHandle(handle))
+                .and_then(|w| w.tag)
+        };
+        assert_eq!(tag_of(3), Some(1));
+        assert_eq!(tag_of(2), Some(2));
+    }
+
+    #[test]
+    fn toggle_window_tags_should_add_and_remove_tags() {
+        let mut manager =
+            Manager::new_test(vec!["1".to_string(), "2".to_string(), "3".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+
+        assert!(manager.command_handler(&Command::ToggleWindowTags {
+            window: None,
+            tags: vec![2, 3],
+        }));
+        assert_eq!(
+            manager.state.windows[0].tags().collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+
+        assert!(manager.command_handler(&Command::ToggleWindowTags {
+            window: None,
+            tags: vec![1, 3],
+        }));
+        assert_eq!(manager.state.windows[0].tags().collect::<Vec<_>>(), vec![2]);
+
+        assert!(!manager.command_handler(&Command::ToggleWindowTags {
+            window: None,
+            tags: vec![4],
+        }));
+    }
+
     #[test]
     fn focus_tag_change_should_go_to_previous_and_next_tag() {
         let mut manager = Manager::new_test(vec![
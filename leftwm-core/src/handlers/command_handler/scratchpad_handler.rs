@@ -9,8 +9,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     child_process::{exec_shell, ChildID},
-    models::{ScratchPadName, TagId, WindowHandle},
-    Command, Config, DisplayAction, DisplayServer, Manager, Window,
+    models::{ScratchPad, ScratchPadName, TagId, WindowHandle},
+    Command, Config, DisplayAction, DisplayServer, Manager, State, StateEvent, Window,
 };
 
 /// Describes the options for the release scratchpad command
@@ -32,26 +32,42 @@ fn hide_scratchpad<C: Config, SERVER: DisplayServer>(
     scratchpad_window: &WindowHandle,
 ) -> Result<(), &'static str> {
     tracing::trace!("Hide scratchpad window {:?}", scratchpad_window);
-    let nsp_tag = manager
+    let anchor = manager
         .state
-        .tags
-        .get_hidden_by_label("NSP")
-        .ok_or("Could not find NSP tag")?;
-    let window = manager
+        .windows
+        .iter()
+        .find(|w| w.handle == *scratchpad_window)
+        .and_then(|w| manager.state.scratchpad_of(w))
+        .and_then(|s| s.anchor);
+    let mut window = manager
         .state
         .windows
-        .iter_mut()
+        .iter()
         .find(|w| w.handle == *scratchpad_window)
+        .cloned()
         .ok_or("Could not find window from scratchpad_window")?;
 
-    window.untag();
     // Hide the scratchpad.
-    window.tag(&nsp_tag.id);
-    window.set_visible(false);
+    if !manager.state.hide_in_scratchpad(&mut window) {
+        return Err("Could not find NSP tag");
+    }
+    let tag = window.tag;
+    if let Some(w) = manager
+        .state
+        .windows
+        .iter_mut()
+        .find(|w| w.handle == *scratchpad_window)
+    {
+        *w = window;
+    }
 
     // Send tag changement to X
-    let act = DisplayAction::SetWindowTag(*scratchpad_window, window.tag);
+    let act = DisplayAction::SetWindowTag(*scratchpad_window, tag);
     manager.state.actions.push_back(act);
+    if let Some(anchor) = anchor {
+        let act = DisplayAction::SlideWindow(*scratchpad_window, anchor, false);
+        manager.state.actions.push_back(act);
+    }
     manager.state.sort_windows();
     manager
         .state
@@ -104,10 +120,20 @@ fn show_scratchpad<C: Config, SERVER: DisplayServer>(
     scratchpad_window: &WindowHandle,
 ) -> Result<(), &'static str> {
     tracing::trace!("Show scratchpad window {:?}", scratchpad_window);
-    let current_tag = &manager
+    let scratchpad = manager
         .state
-        .focus_manager
-        .tag(0)
+        .windows
+        .iter()
+        .find(|w| w.handle == *scratchpad_window)
+        .and_then(|w| manager.state.scratchpad_of(w))
+        .cloned();
+    let workspace = scratchpad
+        .as_ref()
+        .and_then(|s| manager.state.scratchpad_workspace(s))
+        .map(|ws| (ws.tag, ws.xyhw));
+    let current_tag = &workspace
+        .and_then(|(tag, _)| tag)
+        .or_else(|| manager.state.focus_manager.tag(0))
         .ok_or("Could not retrieve the current tag")?;
     let window = manager
         .state
@@ -130,10 +156,22 @@ fn show_scratchpad<C: Config, SERVER: DisplayServer>(
     // Show the scratchpad.
     window.tag(current_tag);
     window.set_visible(true);
+    // Anchored scratchpads are put back in place, the workspace may have changed.
+    let anchored = scratchpad
+        .zip(workspace)
+        .and_then(|(scratchpad, (_, xyhw))| {
+            let anchor = scratchpad.anchor?;
+            scratchpad.place(window, &xyhw);
+            Some(anchor)
+        });
 
     // Send tag changement to X
     let act = DisplayAction::SetWindowTag(*scratchpad_window, window.tag);
     manager.state.actions.push_back(act);
+    if let Some(anchor) = anchored {
+        let act = DisplayAction::SlideWindow(*scratchpad_window, anchor, true);
+        manager.state.actions.push_back(act);
+    }
     manager.state.sort_windows();
     manager
         .state
@@ -145,19 +183,19 @@ fn show_scratchpad<C: Config, SERVER: DisplayServer>(
 }
 
 /// With the introduction of `VecDeque` for scratchpads, it is possible that a window gets destroyed
-/// in the middle of the `VecDeque`. This is an abstraction to retrieve the next valid pid from a
-/// scratchpad. While walking the scratchpad windows, invalid pids will get removed.
-fn next_valid_scratchpad_pid(
-    scratchpad_windows: &mut VecDeque<u32>,
+/// in the middle of the `VecDeque`. This is an abstraction to retrieve the next valid window from
+/// a scratchpad. While walking the scratchpad windows, invalid windows will get removed.
+fn next_valid_scratchpad_window(
+    scratchpad_windows: &mut VecDeque<WindowHandle>,
     managed_windows: &[Window],
     direction: Direction,
-) -> Option<u32> {
+) -> Option<WindowHandle> {
     while let Some(window) = if direction == Direction::Forward {
         scratchpad_windows.pop_front()
     } else {
         scratchpad_windows.pop_back()
     } {
-        if managed_windows.iter().any(|w| w.pid == Some(window)) {
+        if managed_windows.iter().any(|w| w.handle == window) {
             if direction == Direction::Forward {
                 scratchpad_windows.push_front(window);
             } else {
@@ -167,7 +205,7 @@ fn next_valid_scratchpad_pid(
         }
 
         tracing::info!(
-            "Dead window in scratchpad found, discard: window PID: {}",
+            "Dead window in scratchpad found, discard: window {:?}",
             window
         );
     }
@@ -175,31 +213,59 @@ fn next_valid_scratchpad_pid(
     None
 }
 
-/// Check if the scratchpad is visible on the current tag.
+/// Check if the scratchpad is visible on the tag of its workspace.
 /// Returns `false` immediately if the scratchpad name isn't defined in the config
 fn is_scratchpad_visible<C: Config, SERVER: DisplayServer>(
     manager: &Manager<C, SERVER>,
     scratchpad_name: &ScratchPadName,
 ) -> bool {
-    // Like Try operator but returns false and only works on `Option`s
-    macro_rules! try_bool {
-        ($cond:expr) => {
-            if let Some(value) = $cond {
-                value
-            } else {
-                return false;
-            }
-        };
-    }
-    let current_tag = try_bool!(manager.state.focus_manager.tag(0));
-    let scratchpad = try_bool!(manager.state.active_scratchpads.get(scratchpad_name));
+    manager
+        .state
+        .scratchpads
+        .iter()
+        .find(|s| &s.name == scratchpad_name)
+        .map_or(false, |s| {
+            manager.state.shown_scratchpad_windows(s).next().is_some()
+        })
+}
 
-    // Filter out all the non existing windows (invalid pid) and map to window
-    // Check if any of them is in the current tag
-    scratchpad
+/// Hide the other visible scratchpads of the group of the scratchpad about to be shown, and
+/// remember it as the member of its group shown last.
+fn hide_other_group_members<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    scratchpad: &ScratchPad,
+) {
+    let Some(group) = &scratchpad.group else {
+        return;
+    };
+    manager
+        .state
+        .scratchpad_groups
+        .insert(group.clone(), scratchpad.name.clone());
+    let state = &manager.state;
+    let shown: Vec<(ScratchPadName, WindowHandle)> = state
+        .scratchpads
         .iter()
-        .filter_map(|pid| manager.state.windows.iter().find(|w| w.pid == Some(*pid)))
-        .any(|window| window.has_tag(&current_tag))
+        .filter(|s| s.group.as_ref() == Some(group) && s.name != scratchpad.name)
+        .flat_map(|s| {
+            state
+                .shown_scratchpad_windows(s)
+                .map(|w| (s.name.clone(), w.handle))
+        })
+        .collect();
+    for (name, handle) in shown {
+        if let Err(msg) = hide_scratchpad(manager, &handle) {
+            tracing::error!("{}", msg);
+            continue;
+        }
+        manager
+            .state
+            .events
+            .push_back(StateEvent::ScratchpadToggled {
+                name: name.into(),
+                visible: false,
+            });
+    }
 }
 
 /// Handle the command to toggle the scratchpad
@@ -207,24 +273,24 @@ pub fn toggle_scratchpad<C: Config, SERVER: DisplayServer>(
     manager: &mut Manager<C, SERVER>,
     name: &ScratchPadName,
 ) -> Option<bool> {
-    let current_tag = &manager.state.focus_manager.tag(0)?;
     let scratchpad = manager
         .state
         .scratchpads
         .iter()
         .find(|s| name == &s.name)?
         .clone();
+    let current_tag = &manager.state.scratchpad_workspace(&scratchpad)?.tag?;
 
     // Check if there is a valid scratchpad, if so handle it and return immediately
     if let Some(id) = manager.state.active_scratchpads.get_mut(&scratchpad.name) {
         if let Some(first_in_scratchpad) =
-            next_valid_scratchpad_pid(id, &manager.state.windows, Direction::Forward)
+            next_valid_scratchpad_window(id, &manager.state.windows, Direction::Forward)
         {
             if let Some((is_visible, window_handle)) = manager
                 .state
                 .windows
                 .iter()
-                .find(|w| w.pid == Some(first_in_scratchpad))
+                .find(|w| w.handle == first_in_scratchpad)
                 .map(|w| (w.has_tag(current_tag), w.handle))
             {
                 let action_result = if is_visible {
@@ -232,12 +298,22 @@ pub fn toggle_scratchpad<C: Config, SERVER: DisplayServer>(
                     hide_scratchpad(manager, &window_handle)
                 } else {
                     // Window is hidden => show the scratchpad
+                    hide_other_group_members(manager, &scratchpad);
                     show_scratchpad(manager, &window_handle)
                 };
 
                 // Report the result of hiding/showing the scratchpad
                 return match action_result {
-                    Ok(()) => Some(true),
+                    Ok(()) => {
+                        manager
+                            .state
+                            .events
+                            .push_back(StateEvent::ScratchpadToggled {
+                                name: scratchpad.name.into(),
+                                visible: !is_visible,
+                            });
+                        Some(true)
+                    }
                     Err(msg) => {
                         tracing::error!("{}", msg);
                         return Some(false);
@@ -247,26 +323,89 @@ pub fn toggle_scratchpad<C: Config, SERVER: DisplayServer>(
         }
     }
 
+    // Adopt an existing window before launching a new one.
+    if let Some(window_handle) = adoptable_window(&manager.state, &scratchpad) {
+        tracing::debug!(
+            "Adopting window {:?} as scratchpad {:?}",
+            window_handle,
+            scratchpad.name
+        );
+        attach_scratchpad(Some(window_handle), &scratchpad.name, manager)?;
+        hide_other_group_members(manager, &scratchpad);
+        if let Err(msg) = show_scratchpad(manager, &window_handle) {
+            tracing::error!("{}", msg);
+            return Some(false);
+        }
+        manager
+            .state
+            .events
+            .push_back(StateEvent::ScratchpadToggled {
+                name: scratchpad.name.into(),
+                visible: true,
+            });
+        return Some(true);
+    }
+    if scratchpad.value.is_empty() {
+        tracing::debug!(
+            "No window to adopt for scratchpad {:?} and nothing to launch",
+            scratchpad.name
+        );
+        return Some(false);
+    }
+
+    // Launches whose process is gone, or was started before a reload, are forgotten.
+    let children = &manager.children;
+    manager
+        .state
+        .spawned_scratchpads
+        .retain(|pid, name| *name != scratchpad.name || children.contains(*pid));
+    if manager
+        .state
+        .spawned_scratchpads
+        .values()
+        .any(|name| *name == scratchpad.name)
+    {
+        tracing::debug!(
+            "Scratchpad {:?} was launched already, waiting for its window",
+            scratchpad.name
+        );
+        return Some(false);
+    }
+
     tracing::debug!(
         "No active scratchpad found for name {:?}. Creating a new one",
         scratchpad.name
     );
     let name = scratchpad.name.clone();
     let pid: ChildID = exec_shell(&scratchpad.value, &mut manager.children)?;
+    hide_other_group_members(manager, &scratchpad);
+    manager
+        .state
+        .events
+        .push_back(StateEvent::ScratchpadToggled {
+            name: name.clone().into(),
+            visible: true,
+        });
 
-    match manager.state.active_scratchpads.get_mut(&name) {
-        Some(windows) => {
-            windows.push_front(pid);
-        }
-        None => {
-            manager
-                .state
-                .active_scratchpads
-                .insert(name, VecDeque::from([pid]));
-        }
-    }
+    // The window of the scratchpad is recognized by the pid once it appears.
+    manager.state.spawned_scratchpads.insert(pid, name);
 
-    None
+    Some(false)
+}
+
+/// The first window matched by the scratchpad which does not belong to a scratchpad yet.
+fn adoptable_window(state: &State, scratchpad: &ScratchPad) -> Option<WindowHandle> {
+    state
+        .windows
+        .iter()
+        .filter(|w| scratchpad.matches(w))
+        .find(|w| {
+            !state
+                .active_scratchpads
+                .values()
+                .any(|handles| handles.contains(&w.handle))
+        })
+        .map(|w| w.handle)
 }
 
 /// Attaches the `WindowHandle` or the currently selected window to the selected `scratchpad`
@@ -289,17 +428,14 @@ pub fn attach_scratchpad<C: Config, SERVER: DisplayServer>(
     };
 
     // Retrieve and prepare window information
-    let window_pid = {
-        let ws = manager
-            .state
-            .focus_manager
-            .workspace(&manager.state.workspaces)?;
+    {
         let to_scratchpad = manager
             .state
             .scratchpads
             .iter()
-            .find(|s| &s.name == scratchpad)?;
-        let new_float_exact = to_scratchpad.xyhw(&ws.xyhw);
+            .find(|s| &s.name == scratchpad)?
+            .clone();
+        let ws_xyhw = manager.state.scratchpad_workspace(&to_scratchpad)?.xyhw;
 
         let window = manager
             .state
@@ -308,13 +444,9 @@ pub fn attach_scratchpad<C: Config, SERVER: DisplayServer>(
             .find(|w| w.handle == window_handle)?;
 
         // Put window in correct position
-        window.set_floating(true);
-        window.normal = ws.xyhw;
-        window.set_floating_exact(new_float_exact);
+        to_scratchpad.place(window, &ws_xyhw);
         tracing::debug!("Set window to floating: {:?}", window);
-
-        window.pid?
-    };
+    }
 
     if let Some(windows) = manager.state.active_scratchpads.get_mut(scratchpad) {
         tracing::debug!(
@@ -325,15 +457,15 @@ pub fn attach_scratchpad<C: Config, SERVER: DisplayServer>(
             .state
             .windows
             .iter()
-            .find(|w| w.pid.as_ref() == windows.front())
+            .find(|w| Some(&w.handle) == windows.front())
             .map(|w| w.handle);
 
         // Check if window already in scratchpad
-        if windows.iter().any(|pid| *pid == window_pid) {
+        if windows.contains(&window_handle) {
             return Some(false);
         }
 
-        windows.push_front(window_pid);
+        windows.push_front(window_handle);
         if let Some(previous_scratchpad_handle) = previous_scratchpad_handle {
             hide_scratchpad(manager, &previous_scratchpad_handle).ok()?; // first hide current scratchpad window
         }
@@ -345,7 +477,7 @@ pub fn attach_scratchpad<C: Config, SERVER: DisplayServer>(
         manager
             .state
             .active_scratchpads
-            .insert(scratchpad.clone(), VecDeque::from([window_pid]));
+            .insert(scratchpad.clone(), VecDeque::from([window_handle]));
     }
     manager.state.sort_windows();
 
@@ -381,17 +513,20 @@ pub fn release_scratchpad<C: Config, SERVER: DisplayServer>(
     match window {
         ReleaseScratchPadOption::Handle(window_handle) => {
             // Check if window is in active scratchpad
-            let window = manager
+            if !manager
                 .state
                 .windows
-                .iter_mut()
-                .find(|w| w.handle == window_handle)?;
+                .iter()
+                .any(|w| w.handle == window_handle)
+            {
+                return None;
+            }
 
             let scratchpad_name: ScratchPadName = manager
                 .state
                 .active_scratchpads
-                .iter_mut()
-                .find(|(_, id)| window.pid.as_ref() == id.front())
+                .iter()
+                .find(|(_, handles)| handles.front() == Some(&window_handle))
                 .map(|(name, _)| name.clone())?;
 
             tracing::debug!(
@@ -405,11 +540,7 @@ pub fn release_scratchpad<C: Config, SERVER: DisplayServer>(
                 if windows.len() > 1 {
                     // If more than 1, pop of the stack
                     tracing::debug!("Removed 1 window from scratchpad {:?}", &scratchpad_name);
-                    windows.remove(
-                        windows
-                            .iter()
-                            .position(|w| Some(w) == window.pid.as_ref())?,
-                    );
+                    windows.remove(windows.iter().position(|w| *w == window_handle)?);
                 } else {
                     // If only 1, remove entire vec, not needed anymore
                     tracing::debug!(
@@ -427,26 +558,23 @@ pub fn release_scratchpad<C: Config, SERVER: DisplayServer>(
         }
         ReleaseScratchPadOption::ScratchpadName(scratchpad_name) => {
             // Remove and get value from active_scratchpad
-            let window_pid = manager
+            let window_handle = manager
                 .state
                 .active_scratchpads
                 .get_mut(&scratchpad_name)
-                .and_then(|pids| {
-                    next_valid_scratchpad_pid(pids, &manager.state.windows, Direction::Forward)
+                .and_then(|handles| {
+                    next_valid_scratchpad_window(
+                        handles,
+                        &manager.state.windows,
+                        Direction::Forward,
+                    )
                 })?;
-            manager // We found already a working pid, discard from scratchpad
+            manager // We found already a working window, discard from scratchpad
                 .state
                 .active_scratchpads
                 .get_mut(&scratchpad_name)?
                 .pop_front();
 
-            let window_handle = manager
-                .state
-                .windows
-                .iter()
-                .find(|w| w.pid == Some(window_pid))
-                .map(|w| w.handle);
-
             tracing::debug!(
                 "Releasing scratchpad {:?} to tag {}",
                 scratchpad_name,
@@ -454,7 +582,7 @@ pub fn release_scratchpad<C: Config, SERVER: DisplayServer>(
             );
 
             Some(manager.command_handler(&Command::SendWindowToTag {
-                window: window_handle,
+                window: Some(window_handle),
                 tag: destination_tag,
             }))
         }
@@ -486,18 +614,18 @@ pub fn cycle_scratchpad_window<C: Config, SERVER: DisplayServer>(
         .state
         .windows
         .iter()
-        .find(|w| w.pid.as_ref() == scratchpad.front()) // scratchpad.front() ok because checked in is_scratchpad_visible
+        .find(|w| Some(&w.handle) == scratchpad.front()) // scratchpad.front() ok because checked in is_scratchpad_visible
         .map(|w| w.handle);
 
     // Reorder the scratchpads
     // Clean scratchpad and exit if no next exists
-    next_valid_scratchpad_pid(scratchpad, &manager.state.windows, direction)?;
+    next_valid_scratchpad_window(scratchpad, &manager.state.windows, direction)?;
     // Perform cycle
     match direction {
         Direction::Forward => scratchpad.rotate_left(1),
         Direction::Backward => scratchpad.rotate_right(1),
     };
-    let new_window_pid = *scratchpad.front()?;
+    let new_window_handle = *scratchpad.front()?;
 
     // Hide the previous visible window
     if let Err(msg) = hide_scratchpad(manager, &visible_window_handle?) {
@@ -506,12 +634,6 @@ pub fn cycle_scratchpad_window<C: Config, SERVER: DisplayServer>(
     }
 
     // Show the new front window
-    let new_window_handle = manager
-        .state
-        .windows
-        .iter()
-        .find(|w| w.pid == Some(new_window_pid))
-        .map(|w| w.handle)?;
     if let Err(msg) = show_scratchpad(manager, &new_window_handle) {
         tracing::error!("{}", msg);
         return Some(false);
@@ -523,12 +645,58 @@ pub fn cycle_scratchpad_window<C: Config, SERVER: DisplayServer>(
     Some(true)
 }
 
+/// Show the next scratchpad of the group and hide the one shown before. When no scratchpad of
+/// the group is visible, the one shown last is shown again.
+pub fn cycle_scratchpad_group<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    group: &str,
+) -> Option<bool> {
+    let members: Vec<&ScratchPad> = manager
+        .state
+        .scratchpads
+        .iter()
+        .filter(|s| s.group.as_deref() == Some(group))
+        .collect();
+    if members.is_empty() {
+        tracing::warn!("No scratchpad in group {:?}", group);
+        return Some(false);
+    }
+    let shown = members
+        .iter()
+        .position(|s| manager.state.shown_scratchpad_windows(s).next().is_some());
+    let next = match shown {
+        Some(index) => members[(index + 1) % members.len()],
+        None => manager
+            .state
+            .scratchpad_groups
+            .get(group)
+            .and_then(|name| members.iter().find(|s| &s.name == name))
+            .copied()
+            .unwrap_or(members[0]),
+    };
+    let name = next.name.clone();
+    toggle_scratchpad(manager, &name)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{config::ScratchPad, models::ScratchPadName};
+    use crate::{
+        config::{tests::TestConfig, ScratchPad},
+        display_servers::MockDisplayServer,
+        models::{
+            dto::ScratchPadGroupInfo, BBox, ScratchPadAnchor, ScratchPadBinding, ScratchPadName,
+            Screen, Size,
+        },
+    };
 
     use super::*;
 
+    fn handles<const N: usize>(pids: [u32; N]) -> VecDeque<WindowHandle> {
+        pids.into_iter()
+            .map(|pid| WindowHandle::MockHandle(pid as i32))
+            .collect()
+    }
+
     #[test]
     fn show_scratchpad_test() {
         let mut manager = Manager::new_test(vec!["AO".to_string(), "EU".to_string()]);
@@ -612,15 +780,21 @@ mod tests {
         manager.state.scratchpads.push(ScratchPad {
             name: scratchpad_name.clone(),
             value: String::new(),
+            class: None,
+            title: None,
             x: None,
             y: None,
             height: None,
             width: None,
+            anchor: None,
+            workspace: None,
+            follow_focus: false,
+            group: None,
         });
         manager
             .state
             .active_scratchpads
-            .insert(scratchpad_name.clone(), VecDeque::from([mock_window]));
+            .insert(scratchpad_name.clone(), handles([mock_window]));
 
         manager.command_handler(&Command::ToggleScratchPad(scratchpad_name.clone()));
 
@@ -675,7 +849,7 @@ mod tests {
         manager
             .state
             .active_scratchpads
-            .insert(scratchpad_name.clone(), VecDeque::from([mock_window1]));
+            .insert(scratchpad_name.clone(), handles([mock_window1]));
         manager.window_created_handler(
             Window::new(
                 WindowHandle::MockHandle(mock_window1 as i32),
@@ -721,7 +895,7 @@ mod tests {
         let scratchpad_name: ScratchPadName = "Alacritty".into();
         manager.state.active_scratchpads.insert(
             scratchpad_name.clone(),
-            VecDeque::from([mock_window1, mock_window2, mock_window3]),
+            handles([mock_window1, mock_window2, mock_window3]),
         );
         for window in [mock_window1, mock_window2, mock_window3] {
             manager.window_created_handler(
@@ -754,13 +928,16 @@ mod tests {
             .map(|w| !w.has_tag(&nsp_tag))
             .unwrap());
         for mock_window_pid in [mock_window2, mock_window3] {
-            let window_pid = scratchpad.pop_front();
-            assert_eq!(window_pid, Some(mock_window_pid));
+            let window_handle = scratchpad.pop_front();
+            assert_eq!(
+                window_handle,
+                Some(WindowHandle::MockHandle(mock_window_pid as i32))
+            );
             assert!(!manager
                 .state
                 .windows
                 .iter()
-                .find(|w| w.pid == window_pid)
+                .find(|w| Some(w.handle) == window_handle)
                 .map(|w| w.has_tag(&nsp_tag))
                 .unwrap());
         }
@@ -786,14 +963,20 @@ mod tests {
         manager.state.scratchpads.push(ScratchPad {
             name: scratchpad_name.clone(),
             value: "scratchpad".to_string(),
+            class: None,
+            title: None,
             x: None,
             y: None,
             height: None,
             width: None,
+            anchor: None,
+            workspace: None,
+            follow_focus: false,
+            group: None,
         });
         manager.state.active_scratchpads.insert(
             scratchpad_name.clone(),
-            VecDeque::from([mock_window2, mock_window3]),
+            handles([mock_window2, mock_window3]),
         );
         for mock_window in [mock_window1, mock_window2, mock_window3] {
             let mut window = Window::new(
@@ -821,7 +1004,10 @@ mod tests {
             .get_mut(&scratchpad_name)
             .unwrap();
 
-        assert_eq!(scratchpad.pop_front(), Some(mock_window1));
+        assert_eq!(
+            scratchpad.pop_front(),
+            Some(WindowHandle::MockHandle(mock_window1 as i32))
+        );
         assert!(manager
             .state
             .windows
@@ -830,13 +1016,16 @@ mod tests {
             .map(|w| !w.has_tag(&nsp_tag))
             .unwrap());
         for mock_window_pid in [mock_window2, mock_window3] {
-            let window_pid = scratchpad.pop_front();
-            assert_eq!(window_pid, Some(mock_window_pid));
+            let window_handle = scratchpad.pop_front();
+            assert_eq!(
+                window_handle,
+                Some(WindowHandle::MockHandle(mock_window_pid as i32))
+            );
             assert!(manager
                 .state
                 .windows
                 .iter()
-                .find(|w| w.pid == window_pid)
+                .find(|w| Some(w.handle) == window_handle)
                 .map(|w| w.has_tag(&nsp_tag))
                 .unwrap());
         }
@@ -844,7 +1033,7 @@ mod tests {
     }
 
     #[test]
-    fn next_valid_pid_forward_test() {
+    fn next_valid_window_forward_test() {
         // Setup
         let mock_window1 = 1_u32;
         let mock_window2 = 2_u32;
@@ -855,30 +1044,29 @@ mod tests {
             .iter()
             .map(|pid| Window::new(WindowHandle::MockHandle(*pid as i32), None, Some(*pid)))
             .collect::<Vec<Window>>();
-        let mut scratchpad =
-            VecDeque::from([mock_window1, mock_window2, mock_window3, mock_window4]);
+        let mut scratchpad = handles([mock_window1, mock_window2, mock_window3, mock_window4]);
 
         assert_eq!(
-            next_valid_scratchpad_pid(&mut scratchpad, &managed_windows, Direction::Forward),
-            Some(1)
+            next_valid_scratchpad_window(&mut scratchpad, &managed_windows, Direction::Forward),
+            Some(WindowHandle::MockHandle(1))
         );
 
         managed_windows.remove(1);
         assert_eq!(
-            next_valid_scratchpad_pid(&mut scratchpad, &managed_windows, Direction::Forward),
-            Some(1)
+            next_valid_scratchpad_window(&mut scratchpad, &managed_windows, Direction::Forward),
+            Some(WindowHandle::MockHandle(1))
         );
 
         scratchpad.pop_front();
         assert_eq!(
-            next_valid_scratchpad_pid(&mut scratchpad, &managed_windows, Direction::Forward),
-            Some(3)
+            next_valid_scratchpad_window(&mut scratchpad, &managed_windows, Direction::Forward),
+            Some(WindowHandle::MockHandle(3))
         );
         assert_eq!(scratchpad.len(), 2);
     }
 
     #[test]
-    fn next_valid_pid_backward_test() {
+    fn next_valid_window_backward_test() {
         // setup
         let mock_window1 = 1_u32;
         let mock_window2 = 2_u32;
@@ -889,24 +1077,23 @@ mod tests {
             .iter()
             .map(|pid| Window::new(WindowHandle::MockHandle(*pid as i32), None, Some(*pid)))
             .collect::<Vec<Window>>();
-        let mut scratchpad =
-            VecDeque::from([mock_window1, mock_window2, mock_window3, mock_window4]);
+        let mut scratchpad = handles([mock_window1, mock_window2, mock_window3, mock_window4]);
 
         assert_eq!(
-            next_valid_scratchpad_pid(&mut scratchpad, &managed_windows, Direction::Backward),
-            Some(4)
+            next_valid_scratchpad_window(&mut scratchpad, &managed_windows, Direction::Backward),
+            Some(WindowHandle::MockHandle(4))
         );
 
         managed_windows.remove(2);
         assert_eq!(
-            next_valid_scratchpad_pid(&mut scratchpad, &managed_windows, Direction::Backward),
-            Some(4)
+            next_valid_scratchpad_window(&mut scratchpad, &managed_windows, Direction::Backward),
+            Some(WindowHandle::MockHandle(4))
         );
 
         scratchpad.pop_back();
         assert_eq!(
-            next_valid_scratchpad_pid(&mut scratchpad, &managed_windows, Direction::Backward),
-            Some(2)
+            next_valid_scratchpad_window(&mut scratchpad, &managed_windows, Direction::Backward),
+            Some(WindowHandle::MockHandle(2))
         );
         assert_eq!(scratchpad.len(), 2);
     }
@@ -916,27 +1103,27 @@ mod tests {
     fn cycle_scratchpad_window_test() {
         fn is_visible<C: Config, SERVER: DisplayServer>(
             manager: &Manager<C, SERVER>,
-            pid: u32,
+            handle: WindowHandle,
             nsp_tag: TagId,
         ) -> bool {
             manager
                 .state
                 .windows
                 .iter()
-                .find(|w| w.pid == Some(pid))
+                .find(|w| w.handle == handle)
                 .map(|w| w.visible() && !w.has_tag(&nsp_tag))
                 .unwrap()
         }
         fn is_only_first_visible<C: Config, SERVER: DisplayServer>(
             manager: &Manager<C, SERVER>,
-            mut pids: impl Iterator<Item = u32>,
+            mut handles: impl Iterator<Item = WindowHandle>,
             nsp_tag: TagId,
         ) -> bool {
-            if !is_visible(manager, pids.next().unwrap(), nsp_tag) {
+            if !is_visible(manager, handles.next().unwrap(), nsp_tag) {
                 return false;
             }
-            for pid in pids {
-                if is_visible(manager, pid, nsp_tag) {
+            for handle in handles {
+                if is_visible(manager, handle, nsp_tag) {
                     return false;
                 }
             }
@@ -969,15 +1156,23 @@ mod tests {
         manager.state.scratchpads.push(ScratchPad {
             name: scratchpad_name.clone(),
             value: "scratchpad".to_string(),
+            class: None,
+            title: None,
             x: None,
             y: None,
             height: None,
             width: None,
+            anchor: None,
+            workspace: None,
+            follow_focus: false,
+            group: None,
         });
         manager.state.active_scratchpads.insert(
             scratchpad_name.clone(),
-            VecDeque::from([mock_window1, mock_window2, mock_window3]),
+            handles([mock_window1, mock_window2, mock_window3]),
         );
+        let [handle1, handle2, handle3] = [mock_window1, mock_window2, mock_window3]
+            .map(|pid| WindowHandle::MockHandle(pid as i32));
 
         cycle_scratchpad_window(&mut manager, &scratchpad_name, Direction::Forward);
         let mut scratchpad_iterator = manager
@@ -990,9 +1185,9 @@ mod tests {
             is_only_first_visible(&manager, scratchpad_iterator.clone().copied(), nsp_tag),
             "On the first forward cycle, the first window is not visible or the other windows are visible"
         );
-        assert_eq!(scratchpad_iterator.next(), Some(&mock_window2));
-        assert_eq!(scratchpad_iterator.next(), Some(&mock_window3));
-        assert_eq!(scratchpad_iterator.next(), Some(&mock_window1));
+        assert_eq!(scratchpad_iterator.next(), Some(&handle2));
+        assert_eq!(scratchpad_iterator.next(), Some(&handle3));
+        assert_eq!(scratchpad_iterator.next(), Some(&handle1));
         assert_eq!(scratchpad_iterator.next(), None);
 
         cycle_scratchpad_window(&mut manager, &scratchpad_name, Direction::Forward);
@@ -1009,9 +1204,9 @@ mod tests {
         ),
             "On the second forward cycle, the first window is not visible or the other windows are visible"
         );
-        assert_eq!(scratchpad_iterator.next(), Some(&mock_window3));
-        assert_eq!(scratchpad_iterator.next(), Some(&mock_window1));
-        assert_eq!(scratchpad_iterator.next(), Some(&mock_window2));
+        assert_eq!(scratchpad_iterator.next(), Some(&handle3));
+        assert_eq!(scratchpad_iterator.next(), Some(&handle1));
+        assert_eq!(scratchpad_iterator.next(), Some(&handle2));
         assert_eq!(scratchpad_iterator.next(), None);
 
         cycle_scratchpad_window(&mut manager, &scratchpad_name, Direction::Backward);
@@ -1028,9 +1223,9 @@ mod tests {
         ),
             "After 2 forward and 1 backward cycles, the first window is not visible or the other windows are visible"
         );
-        assert_eq!(scratchpad_iterator.next(), Some(&mock_window2));
-        assert_eq!(scratchpad_iterator.next(), Some(&mock_window3));
-        assert_eq!(scratchpad_iterator.next(), Some(&mock_window1));
+        assert_eq!(scratchpad_iterator.next(), Some(&handle2));
+        assert_eq!(scratchpad_iterator.next(), Some(&handle3));
+        assert_eq!(scratchpad_iterator.next(), Some(&handle1));
         assert_eq!(scratchpad_iterator.next(), None);
 
         cycle_scratchpad_window(&mut manager, &scratchpad_name, Direction::Backward);
@@ -1047,9 +1242,9 @@ mod tests {
         ),
             "After 2 forward and 2 backward cycles, the first window is not visible or the other windows are visible"
         );
-        assert_eq!(scratchpad_iterator.next(), Some(&mock_window1));
-        assert_eq!(scratchpad_iterator.next(), Some(&mock_window2));
-        assert_eq!(scratchpad_iterator.next(), Some(&mock_window3));
+        assert_eq!(scratchpad_iterator.next(), Some(&handle1));
+        assert_eq!(scratchpad_iterator.next(), Some(&handle2));
+        assert_eq!(scratchpad_iterator.next(), Some(&handle3));
         assert_eq!(scratchpad_iterator.next(), None);
     }
 
@@ -1078,15 +1273,21 @@ mod tests {
         manager.state.scratchpads.push(ScratchPad {
             name: scratchpad_name.clone(),
             value: "scratchpad".to_string(),
+            class: None,
+            title: None,
             x: None,
             y: None,
             height: None,
             width: None,
+            anchor: None,
+            workspace: None,
+            follow_focus: false,
+            group: None,
         });
         manager
             .state
             .active_scratchpads
-            .insert(scratchpad_name, VecDeque::from([mock_window3]));
+            .insert(scratchpad_name, handles([mock_window3]));
 
         // Focus first window
         let focus_window_handler = manager.state.windows[0].handle;
@@ -1188,15 +1389,21 @@ mod tests {
         manager.state.scratchpads.push(ScratchPad {
             name: scratchpad_name.clone(),
             value: "scratchpad".to_string(),
+            class: None,
+            title: None,
             x: None,
             y: None,
             height: None,
             width: None,
+            anchor: None,
+            workspace: None,
+            follow_focus: false,
+            group: None,
         });
         manager
             .state
             .active_scratchpads
-            .insert(scratchpad_name, VecDeque::from([mock_window3]));
+            .insert(scratchpad_name, handles([mock_window3]));
 
         // Focus first window
         let focus_window_handler = manager.state.windows[0].handle;
@@ -1252,15 +1459,21 @@ mod tests {
         manager.state.scratchpads.push(ScratchPad {
             name: scratchpad_name.clone(),
             value: String::new(),
+            class: None,
+            title: None,
             x: None,
             y: None,
             height: None,
             width: None,
+            anchor: None,
+            workspace: None,
+            follow_focus: false,
+            group: None,
         });
         manager
             .state
             .active_scratchpads
-            .insert(scratchpad_name.clone(), VecDeque::from([scratchpad_pid]));
+            .insert(scratchpad_name.clone(), handles([scratchpad_pid]));
 
         let window_pid = 2_u32;
         let window_handle = WindowHandle::MockHandle(window_pid as i32);
@@ -1299,4 +1512,370 @@ mod tests {
             assert_eq!(window.border(), 0);
         }
     }
+
+    fn adopting_scratchpad(name: &ScratchPadName) -> ScratchPad {
+        ScratchPad {
+            name: name.clone(),
+            value: String::new(),
+            class: Some("Scratch".to_string()),
+            title: None,
+            x: None,
+            y: None,
+            height: None,
+            width: None,
+            anchor: None,
+            workspace: None,
+            follow_focus: false,
+            group: None,
+        }
+    }
+
+    fn window_of_class(id: u32, class: &str) -> Window {
+        let mut window = Window::new(WindowHandle::MockHandle(id as i32), None, Some(id));
+        window.res_class = Some(class.to_string());
+        window
+    }
+
+    #[test]
+    fn toggle_scratchpad_adopts_an_existing_window() {
+        let mut manager = Manager::new_test(vec!["AO".to_string(), "EU".to_string()]);
+        manager.screen_create_handler(Default::default());
+        let scratchpad_name: ScratchPadName = "Notes".into();
+        manager.window_created_handler(window_of_class(1, "Other"), -1, -1);
+        manager.window_created_handler(window_of_class(2, "Scratch"), -1, -1);
+        manager
+            .state
+            .scratchpads
+            .push(adopting_scratchpad(&scratchpad_name));
+
+        assert!(manager.command_handler(&Command::ToggleScratchPad(scratchpad_name.clone())));
+
+        assert_eq!(
+            manager.state.active_scratchpads.get(&scratchpad_name),
+            Some(&handles([2]))
+        );
+        let window = manager
+            .state
+            .windows
+            .iter()
+            .find(|w| w.pid == Some(2))
+            .unwrap();
+        assert!(window.floating());
+        assert!(window.visible());
+        assert_eq!(window.tag, manager.state.focus_manager.tag(0));
+        // Nothing was launched.
+        assert!(manager.children.is_empty());
+    }
+
+    #[test]
+    fn a_scratchpad_without_window_adopts_a_new_matching_window() {
+        let mut manager = Manager::new_test(vec!["AO".to_string(), "EU".to_string()]);
+        manager.screen_create_handler(Default::default());
+        let scratchpad_name: ScratchPadName = "Notes".into();
+        manager
+            .state
+            .scratchpads
+            .push(adopting_scratchpad(&scratchpad_name));
+
+        manager.window_created_handler(window_of_class(1, "Scratch"), -1, -1);
+        manager.window_created_handler(window_of_class(2, "Scratch"), -1, -1);
+
+        // The second window is not adopted, as the scratchpad has a window already.
+        assert_eq!(
+            manager.state.active_scratchpads.get(&scratchpad_name),
+            Some(&handles([1]))
+        );
+        assert!(manager
+            .state
+            .windows
+            .iter()
+            .any(|w| w.pid == Some(1) && w.floating()));
+    }
+
+    #[test]
+    fn a_scratchpad_is_not_launched_again_while_its_window_is_pending() {
+        let mut manager = Manager::new_test(vec!["AO".to_string(), "EU".to_string()]);
+        manager.screen_create_handler(Default::default());
+        let scratchpad_name: ScratchPadName = "Terminal".into();
+        manager.state.scratchpads.push(ScratchPad {
+            value: "true".to_string(),
+            class: None,
+            ..adopting_scratchpad(&scratchpad_name)
+        });
+        // A launch from before a reload, whose process is not known.
+        manager
+            .state
+            .spawned_scratchpads
+            .insert(u32::MAX, scratchpad_name.clone());
+
+        manager.command_handler(&Command::ToggleScratchPad(scratchpad_name.clone()));
+        assert_eq!(manager.children.len(), 1);
+        assert!(!manager.state.spawned_scratchpads.contains_key(&u32::MAX));
+
+        manager.command_handler(&Command::ToggleScratchPad(scratchpad_name.clone()));
+        assert_eq!(manager.children.len(), 1);
+        assert_eq!(manager.state.spawned_scratchpads.len(), 1);
+    }
+
+    #[test]
+    fn a_launched_scratchpad_takes_only_the_first_window_of_its_process() {
+        let mut manager = Manager::new_test(vec!["AO".to_string(), "EU".to_string()]);
+        manager.screen_create_handler(Default::default());
+        let scratchpad_name: ScratchPadName = "Terminal".into();
+        manager.state.scratchpads.push(ScratchPad {
+            class: None,
+            ..adopting_scratchpad(&scratchpad_name)
+        });
+        manager
+            .state
+            .spawned_scratchpads
+            .insert(7, scratchpad_name.clone());
+
+        for id in [1, 2] {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, Some(7)),
+                -1,
+                -1,
+            );
+        }
+
+        assert_eq!(
+            manager.state.active_scratchpads.get(&scratchpad_name),
+            Some(&handles([1]))
+        );
+        assert!(manager.state.spawned_scratchpads.is_empty());
+        let second = manager
+            .state
+            .windows
+            .iter()
+            .find(|w| w.handle == WindowHandle::MockHandle(2))
+            .unwrap();
+        assert!(manager.state.scratchpad_of(second).is_none());
+    }
+
+    #[test]
+    fn an_adopted_window_without_a_pid_is_toggled() {
+        let mut manager = Manager::new_test(vec!["AO".to_string(), "EU".to_string()]);
+        manager.screen_create_handler(Default::default());
+        let nsp_tag = manager.state.tags.get_hidden_by_label("NSP").unwrap().id;
+        let scratchpad_name: ScratchPadName = "Notes".into();
+        let mut window = Window::new(WindowHandle::MockHandle(1), None, None);
+        window.res_class = Some("Scratch".to_string());
+        manager.window_created_handler(window, -1, -1);
+        manager
+            .state
+            .scratchpads
+            .push(adopting_scratchpad(&scratchpad_name));
+
+        assert!(manager.command_handler(&Command::ToggleScratchPad(scratchpad_name.clone())));
+        assert!(manager.state.windows[0].visible());
+        assert!(manager.command_handler(&Command::ToggleScratchPad(scratchpad_name.clone())));
+
+        assert!(manager.state.windows[0].has_tag(&nsp_tag));
+        assert_eq!(
+            manager.state.active_scratchpads.get(&scratchpad_name),
+            Some(&handles([1]))
+        );
+    }
+
+    fn manager_with_two_screens() -> Manager<TestConfig, MockDisplayServer> {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        for x in [0, 800] {
+            manager.screen_create_handler(Screen::new(
+                BBox {
+                    x,
+                    y: 0,
+                    width: 800,
+                    height: 600,
+                },
+                format!("output-{x}"),
+            ));
+        }
+        manager
+    }
+
+    #[test]
+    fn an_anchored_scratchpad_is_shown_on_its_own_workspace() {
+        let mut manager = manager_with_two_screens();
+        let scratchpad_name: ScratchPadName = "Dropdown".into();
+        let second_workspace = manager.state.workspaces[1].clone();
+        manager.window_created_handler(window_of_class(1, "Scratch"), -1, -1);
+        manager.state.scratchpads.push(ScratchPad {
+            height: Some(Size::Ratio(0.5)),
+            width: Some(Size::Ratio(1.0)),
+            anchor: Some(ScratchPadAnchor::Top),
+            workspace: Some(second_workspace.id),
+            ..adopting_scratchpad(&scratchpad_name)
+        });
+
+        assert!(manager.command_handler(&Command::ToggleScratchPad(scratchpad_name)));
+
+        let window = manager
+            .state
+            .windows
+            .iter()
+            .find(|w| w.pid == Some(1))
+            .unwrap();
+        assert_eq!(window.tag, second_workspace.tag);
+        let xyhw = window.exact_xyhw();
+        assert_eq!((xyhw.x(), xyhw.y(), xyhw.w(), xyhw.h()), (800, 0, 800, 300));
+        assert!(manager.state.actions.iter().any(|act| matches!(
+            act,
+            DisplayAction::SlideWindow(WindowHandle::MockHandle(1), ScratchPadAnchor::Top, true)
+        )));
+    }
+
+    #[test]
+    fn a_visible_scratchpad_follows_the_focus_to_another_workspace() {
+        let mut manager = manager_with_two_screens();
+        let scratchpad_name: ScratchPadName = "Notes".into();
+        manager.window_created_handler(window_of_class(1, "Scratch"), -1, -1);
+        manager.state.scratchpads.push(ScratchPad {
+            follow_focus: true,
+            ..adopting_scratchpad(&scratchpad_name)
+        });
+        assert!(manager.command_handler(&Command::ToggleScratchPad(scratchpad_name)));
+        assert_eq!(
+            manager.state.windows[0].tag,
+            manager.state.workspaces[0].tag
+        );
+
+        let second_workspace = manager.state.workspaces[1].clone();
+        manager.state.focus_workspace(&second_workspace);
+
+        assert_eq!(manager.state.windows[0].tag, second_workspace.tag);
+    }
+
+    #[test]
+    fn scratchpads_are_reattached_after_a_restart() {
+        let mut manager = Manager::new_test(vec!["AO".to_string(), "EU".to_string()]);
+        manager.screen_create_handler(Default::default());
+        manager.window_created_handler(window_of_class(1, "Term"), -1, -1);
+        manager.window_created_handler(window_of_class(2, "Scratch"), -1, -1);
+        manager.window_created_handler(window_of_class(3, "Other"), -1, -1);
+        let by_pid: ScratchPadName = "Terminal".into();
+        let by_class: ScratchPadName = "Notes".into();
+        let stale: ScratchPadName = "Stale".into();
+        for (name, class) in [
+            (&by_pid, None),
+            (&by_class, Some("Scratch")),
+            (&stale, None),
+        ] {
+            manager.state.scratchpads.push(ScratchPad {
+                class: class.map(str::to_string),
+                ..adopting_scratchpad(name)
+            });
+        }
+        let binding = |name: &ScratchPadName, id: u32, class: &str| ScratchPadBinding {
+            name: name.clone(),
+            pid: Some(id),
+            handle: WindowHandle::MockHandle(id as i32),
+            class: Some(class.to_string()),
+        };
+
+        manager.state.reattach_scratchpads(&[
+            binding(&by_pid, 1, "Term"),
+            // The window is gone, the class of the scratchpad finds another one.
+            binding(&by_class, 9, "Scratch"),
+            // The pid was reused by a window of another class.
+            binding(&stale, 3, "Term"),
+        ]);
+
+        assert_eq!(
+            manager.state.active_scratchpads.get(&by_pid),
+            Some(&handles([1]))
+        );
+        assert_eq!(
+            manager.state.active_scratchpads.get(&by_class),
+            Some(&handles([2]))
+        );
+        assert_eq!(manager.state.active_scratchpads.get(&stale), None);
+        assert!(manager
+            .state
+            .windows
+            .iter()
+            .find(|w| w.pid == Some(1))
+            .unwrap()
+            .floating());
+        assert_eq!(
+            manager.state.scratchpad_bindings(),
+            vec![
+                binding(&by_class, 2, "Scratch"),
+                binding(&by_pid, 1, "Term")
+            ]
+        );
+    }
+
+    #[test]
+    fn scratchpads_restored_by_a_soft_reload_are_not_reattached() {
+        let mut manager = Manager::new_test(vec!["AO".to_string(), "EU".to_string()]);
+        manager.screen_create_handler(Default::default());
+        manager.window_created_handler(window_of_class(1, "Term"), -1, -1);
+        manager.window_created_handler(window_of_class(2, "Term"), -1, -1);
+        let scratchpad_name: ScratchPadName = "Terminal".into();
+        manager.state.scratchpads.push(ScratchPad {
+            class: Some("Term".to_string()),
+            ..adopting_scratchpad(&scratchpad_name)
+        });
+        manager
+            .state
+            .active_scratchpads
+            .insert(scratchpad_name.clone(), handles([1]));
+
+        manager.state.reattach_scratchpads(&[ScratchPadBinding {
+            name: scratchpad_name.clone(),
+            pid: Some(2),
+            handle: WindowHandle::MockHandle(2),
+            class: Some("Term".to_string()),
+        }]);
+
+        assert_eq!(
+            manager.state.active_scratchpads.get(&scratchpad_name),
+            Some(&handles([1]))
+        );
+    }
+
+    #[test]
+    fn cycling_a_group_shows_one_scratchpad_at_a_time() {
+        let mut manager = Manager::new_test(vec!["AO".to_string(), "EU".to_string()]);
+        manager.screen_create_handler(Default::default());
+        let names: Vec<ScratchPadName> =
+            vec!["Terminal".into(), "Calculator".into(), "Notes".into()];
+        for (i, name) in names.iter().enumerate() {
+            let class = format!("Tool{i}");
+            manager.window_created_handler(window_of_class(i as u32 + 1, &class), -1, -1);
+            manager.state.scratchpads.push(ScratchPad {
+                class: Some(class),
+                group: Some("tools".to_string()),
+                ..adopting_scratchpad(name)
+            });
+        }
+        let cycle = Command::CycleScratchPadGroup {
+            group: "tools".to_string(),
+        };
+        let shown = |manager: &Manager<TestConfig, MockDisplayServer>| -> Vec<ScratchPadName> {
+            manager
+                .state
+                .scratchpads
+                .iter()
+                .filter(|s| manager.state.shown_scratchpad_windows(s).next().is_some())
+                .map(|s| s.name.clone())
+                .collect()
+        };
+
+        assert!(manager.command_handler(&cycle));
+        assert_eq!(shown(&manager), vec![names[0].clone()]);
+        assert!(manager.command_handler(&cycle));
+        assert_eq!(shown(&manager), vec![names[1].clone()]);
+        // Showing a member directly hides the others as well.
+        assert!(manager.command_handler(&Command::ToggleScratchPad(names[2].clone())));
+        assert_eq!(shown(&manager), vec![names[2].clone()]);
+
+        assert!(manager.command_handler(&Command::ToggleScratchPad(names[2].clone())));
+        let groups = ScratchPadGroupInfo::list(&manager.state);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].members, vec!["Terminal", "Calculator", "Notes"]);
+        assert_eq!(groups[0].current.as_deref(), Some("Notes"));
+        assert!(!groups[0].visible);
+    }
 }
@@ -11,6 +11,8 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         let state = &mut self.state;
         match event {
             DisplayEvent::ScreenCreate(s) => self.screen_create_handler(s),
+            DisplayEvent::ScreenUpdate(s) => self.screen_update_handler(&s),
+            DisplayEvent::ScreenDestroy(s) => self.screen_destroy_handler(&s),
             DisplayEvent::WindowCreate(w, x, y) => self.window_created_handler(w, x, y),
             DisplayEvent::WindowChange(w) => self.window_changed_handler(w),
             DisplayEvent::WindowDestroy(handle) => self.window_destroyed_handler(&handle),
@@ -56,6 +58,7 @@ fn from_verify_focus_at(state: &mut State, handle: WindowHandle) -> bool {
 
 fn from_change_to_normal_mode(state: &mut State) -> bool {
     match state.mode {
+        Mode::ResizingWindow(h) if state.tiled_resize.is_some() => state.focus_window(&h),
         Mode::MovingWindow(h) | Mode::ResizingWindow(h) => {
             // We want to update the windows tag once it is done moving. This means
             // when the window is re-tiled it is on the correct workspace. This also
@@ -85,6 +88,7 @@ fn from_change_to_normal_mode(state: &mut State) -> bool {
         _ => {}
     }
     state.mode = Mode::Normal;
+    state.tiled_resize = None;
     true
 }
 
@@ -117,7 +121,10 @@ fn from_resize_window<C: Config, SERVER: DisplayServer>(
     // Setup for when window first resizes.
     if let Mode::ReadyToResize(h) = manager.state.mode {
         manager.state.mode = Mode::ResizingWindow(h);
-        prepare_window(&mut manager.state, h);
+        // Tiled windows being resized by a boundary stay where they are.
+        if manager.state.tiled_resize.is_none() {
+            prepare_window(&mut manager.state, h);
+        }
     }
     manager.window_resize_handler(&handle, x, y)
 }
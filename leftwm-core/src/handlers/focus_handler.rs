@@ -3,6 +3,7 @@
 use super::*;
 use crate::models::TagId;
 use crate::state::State;
+use crate::StateEvent;
 use crate::{display_action::DisplayAction, models::FocusBehaviour};
 
 impl State {
@@ -183,6 +184,7 @@ impl State {
 
         let act = DisplayAction::SetCurrentTags(Some(tag));
         self.actions.push_back(act);
+        self.events.push_back(StateEvent::TagSwitched { tag });
         true
     }
 
@@ -191,6 +193,8 @@ impl State {
             let act = DisplayAction::Unfocus(None, false);
             self.actions.push_back(act);
             self.focus_manager.window_history.push_front(None);
+            self.events
+                .push_back(StateEvent::FocusChanged { handle: None });
             return None;
         }
         // Find the handle in our managed windows.
@@ -223,6 +227,9 @@ impl State {
             previous_window: previous.cloned(),
         };
         self.actions.push_back(act);
+        self.events.push_back(StateEvent::FocusChanged {
+            handle: Some(*handle),
+        });
 
         Some(found.clone())
     }
@@ -239,11 +246,49 @@ impl State {
         // Add this focus to the history.
         if let Some(index) = self.workspaces.iter().position(|x| x.id == ws_id) {
             self.focus_manager.workspace_history.push_front(index);
+            self.scratchpads_follow_focus();
             return true;
         }
         false
     }
 
+    /// Move the visible scratchpads which follow the focus onto the focused workspace.
+    fn scratchpads_follow_focus(&mut self) {
+        let Some(ws) = self.focus_manager.workspace(&self.workspaces) else {
+            return;
+        };
+        let Some(tag) = ws.tag else {
+            return;
+        };
+        let mut moved = false;
+        for scratchpad in self
+            .scratchpads
+            .iter()
+            .filter(|s| s.follow_focus && s.workspace.is_none())
+        {
+            let Some(handles) = self.active_scratchpads.get(&scratchpad.name) else {
+                continue;
+            };
+            for window in self.windows.iter_mut().filter(|w| {
+                handles.contains(&w.handle)
+                    && !ws.is_displaying(w)
+                    && self.workspaces.iter().any(|other| other.is_displaying(w))
+            }) {
+                window.untag();
+                window.tag(&tag);
+                if scratchpad.anchor.is_some() {
+                    scratchpad.place(window, &ws.xyhw);
+                }
+                let act = DisplayAction::SetWindowTag(window.handle, window.tag);
+                self.actions.push_back(act);
+                moved = true;
+            }
+        }
+        if moved {
+            self.sort_windows();
+        }
+    }
+
     fn unfocus_current_window(&mut self) {
         if let Some(window) = self.focus_manager.window(&self.windows) {
             self.actions.push_back(DisplayAction::Unfocus(
@@ -251,6 +296,8 @@ impl State {
                 window.floating(),
             ));
             self.focus_manager.window_history.push_front(None);
+            self.events
+                .push_back(StateEvent::FocusChanged { handle: None });
             if let Some(tag_id) = &window.tag {
                 self.focus_manager
                     .tags_last_window
@@ -20,8 +20,14 @@ impl State {
         if let Some(ws) = self.workspaces.iter_mut().find(|ws| ws.tag == new_tag) {
             ws.tag = Some(old_tag);
         }
+        // The tag can not stay toggled into view anywhere else.
+        for ws in &mut self.workspaces {
+            ws.extra_tags.retain(|t| *t != tag_id);
+        }
 
-        self.focus_manager.workspace_mut(&mut self.workspaces)?.tag = new_tag;
+        self.focus_manager
+            .workspace_mut(&mut self.workspaces)?
+            .show_tag(&tag_id);
         self.focus_tag(&tag_id);
         self.update_static();
 
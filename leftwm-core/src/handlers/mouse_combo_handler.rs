@@ -1,5 +1,10 @@
 use crate::display_action::DisplayAction;
+use crate::layouts::MANUAL;
 use crate::models::Mode;
+use crate::models::Side;
+use crate::models::StackedWindow;
+use crate::models::TiledResize;
+use crate::models::Window;
 use crate::models::WindowHandle;
 use crate::state::State;
 use crate::utils;
@@ -23,8 +28,10 @@ impl State {
                 // Build the display to say whether we are ready to move/resize.
                 let act = self.build_action(modmask, button, handle, modifier);
                 if let Some(act) = act {
-                    if self.reposition_cursor_on_resize {
-                        if let DisplayAction::ReadyToResizeWindow(_) = act {
+                    if let DisplayAction::ReadyToResizeWindow(_) = act {
+                        // A tiled window is resized by dragging its closest boundary instead.
+                        self.tiled_resize = self.start_tiled_resize(handle, x, y);
+                        if self.reposition_cursor_on_resize && self.tiled_resize.is_none() {
                             let move_act = DisplayAction::MoveMouseOverPoint(bottom_right);
                             self.actions.push_back(move_act);
                         }
@@ -76,4 +83,95 @@ impl State {
             _ => None,
         }
     }
+
+    /// Start dragging the boundary of a tiled window closest to the point the window was
+    /// clicked at, `x` and `y` being relative to the window. `None` if the window is not tiled
+    /// or has no boundary which can be dragged.
+    fn start_tiled_resize(&mut self, handle: WindowHandle, x: i32, y: i32) -> Option<TiledResize> {
+        let window = self.windows.iter().find(|w| w.handle == handle)?;
+        if window.floating() {
+            return None;
+        }
+        let workspace = self.workspaces.iter().find(|ws| ws.is_displaying(window))?;
+        let (ws_id, tag) = (workspace.id, workspace.tag?);
+        let tiled: Vec<&Window> = self
+            .windows
+            .iter()
+            .filter(|w| workspace.is_displaying(w) && w.is_managed() && !w.floating())
+            .collect();
+        let index = tiled.iter().position(|w| w.handle == handle)?;
+        let (point_x, point_y) = (window.x() + x, window.y() + y);
+        let rect = window.normal;
+
+        // The boundary closest to the point, and the window across it.
+        let (side, other) = [Side::Left, Side::Right, Side::Top, Side::Bottom]
+            .into_iter()
+            .filter_map(|side| {
+                let other = tiled.iter().position(|w| {
+                    w.handle != handle
+                        && rect
+                            .distance_towards(&w.normal, &side)
+                            .map_or(false, |(gap, _)| gap == 0)
+                })?;
+                let distance = match side {
+                    Side::Left => point_x - rect.x(),
+                    Side::Right => rect.x() + rect.w() - point_x,
+                    Side::Top => point_y - rect.y(),
+                    Side::Bottom => rect.y() + rect.h() - point_y,
+                };
+                Some((distance.abs(), side, other))
+            })
+            .min_by_key(|(distance, _, _)| *distance)
+            .map(|(_, side, other)| (side, other))?;
+        let horizontal = matches!(side, Side::Left | Side::Right);
+        // Whether the clicked window lies left of or above the boundary.
+        let before = matches!(side, Side::Right | Side::Bottom);
+
+        let def = self.layout_manager.layout(ws_id, tag);
+        if def.name == MANUAL {
+            return None;
+        }
+        let main_count = def.columns.main.as_ref().map_or(0, |main| main.count);
+        if (index < main_count) != (other < main_count) {
+            let main_before = before == (index < main_count);
+            return Some(TiledResize::MainSize {
+                workspace: ws_id,
+                tag,
+                horizontal,
+                sign: if main_before { 1 } else { -1 },
+                applied: 0,
+            });
+        }
+
+        // Only windows lined up in the same stack share their space by their weights.
+        let across = |w: &Window| {
+            if horizontal {
+                (w.normal.y(), w.normal.h())
+            } else {
+                (w.normal.x(), w.normal.w())
+            }
+        };
+        if across(tiled[index]) != across(tiled[other]) {
+            return None;
+        }
+        let stacked = |w: &Window| StackedWindow {
+            handle: w.handle,
+            weight: w.weight(tag),
+            length: if horizontal {
+                w.normal.w()
+            } else {
+                w.normal.h()
+            },
+        };
+        let (first, second) = if before {
+            (tiled[index], tiled[other])
+        } else {
+            (tiled[other], tiled[index])
+        };
+        Some(TiledResize::Weights {
+            tag,
+            horizontal,
+            windows: [stacked(first), stacked(second)],
+        })
+    }
 }
@@ -1,12 +1,15 @@
 use super::{Manager, Screen, Workspace};
-use crate::config::Config;
+use crate::config::{numbered_workspaces, Config};
 use crate::display_servers::DisplayServer;
+use crate::models::{TagId, WorkspaceId};
+use crate::state::State;
+use crate::StateEvent;
 
 impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
     /// Process a collection of events, and apply the changes to a manager.
     ///
     /// Returns `true` if changes need to be rendered.
-    pub fn screen_create_handler(&mut self, screen: Screen) -> bool {
+    pub fn screen_create_handler(&mut self, mut screen: Screen) -> bool {
         tracing::trace!("Screen create: {:?}", screen);
 
         let tag_index = self.state.workspaces.len();
@@ -18,6 +21,18 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             None => self.state.workspaces.last().map_or(0, |ws| ws.id) + 1,
             Some(set_id) => set_id,
         };
+        screen.id = Some(workspace_id);
+
+        if let Some(index) = self
+            .state
+            .detached_workspaces
+            .iter()
+            .position(|detached| !screen.output.is_empty() && detached.output == screen.output)
+        {
+            let workspace = self.state.detached_workspaces.remove(index).workspace;
+            self.restore_workspace(screen, workspace_id, workspace);
+            return true;
+        }
 
         let mut new_workspace = Workspace::new(screen.bbox, workspace_id);
         if self.state.workspaces.len() >= tag_len {
@@ -25,22 +40,72 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         }
         new_workspace.load_config(&self.config);
 
+        let default_tag = numbered_workspaces(&self.config)
+            .into_iter()
+            .find(|(id, _, _)| *id == workspace_id)
+            .and_then(|(_, _, wsc)| wsc.default_tag)
+            .filter(|tag| {
+                (1..=tag_len).contains(tag)
+                    && !self.state.workspaces.iter().any(|ws| ws.shows_tag(tag))
+            });
+
         // Make sure there are enough tags for this new screen.
-        let next_id = if tag_len > tag_index {
-            tag_index + 1
-        } else {
+        let next_id = match default_tag {
+            Some(tag) => tag,
+            None if tag_len > tag_index => tag_index + 1,
             // Add a new tag for the workspace.
-            self.state.tags.add_new_unlabeled()
+            None => self.state.tags.add_new_unlabeled(),
         };
 
         self.state.focus_workspace(&new_workspace);
         self.state.focus_tag(&next_id);
         new_workspace.show_tag(&next_id);
         self.state.workspaces.push(new_workspace.clone());
+        self.state.events.push_back(StateEvent::ScreenAdded {
+            workspace: workspace_id,
+            output: screen.output.clone(),
+        });
         self.state.screens.push(screen);
         self.state.focus_workspace(&new_workspace);
         false
     }
+
+    /// Bring back the workspace of a screen which was connected before, taking its tags back
+    /// from the workspaces they were moved to.
+    fn restore_workspace(&mut self, screen: Screen, id: WorkspaceId, mut workspace: Workspace) {
+        let tags: Vec<TagId> = workspace.tags().collect();
+        for ws in &mut self.state.workspaces {
+            ws.extra_tags.retain(|t| !tags.contains(t));
+        }
+        let is_free = |state: &State, tag: &TagId| -> bool {
+            !state.workspaces.iter().any(|ws| ws.shows_tag(tag))
+        };
+        // Tags which were focused on another workspace in the meantime stay there.
+        workspace.extra_tags.retain(|t| is_free(&self.state, t));
+        if !workspace.tag.map_or(false, |t| is_free(&self.state, &t)) {
+            workspace.tag = if workspace.extra_tags.is_empty() {
+                self.state
+                    .tags
+                    .normal()
+                    .iter()
+                    .map(|t| t.id)
+                    .find(|t| is_free(&self.state, t))
+            } else {
+                Some(workspace.extra_tags.remove(0))
+            };
+        }
+
+        workspace.id = id;
+        workspace.set_bbox(screen.bbox);
+        workspace.load_config(&self.config);
+        self.state.move_floating_windows(&workspace, workspace.xyhw);
+        self.state.workspaces.push(workspace);
+        self.state.events.push_back(StateEvent::ScreenAdded {
+            workspace: id,
+            output: screen.output.clone(),
+        });
+        self.state.screens.push(screen);
+    }
 }
 
 #[cfg(test)]
@@ -94,4 +159,50 @@ mod tests {
         assert!(manager.state.workspaces[2].has_tag(&3));
         assert!(manager.state.workspaces[3].has_tag(&4));
     }
+
+    #[test]
+    fn workspaces_of_a_profile_show_their_default_tag() {
+        let mut manager = Manager::new_test(vec![
+            "web".to_string(),
+            "console".to_string(),
+            "code".to_string(),
+        ]);
+        manager.config.workspaces = Some(vec![crate::config::Workspace::default()]);
+        manager.config.workspace_profiles = vec![crate::config::WorkspaceProfile {
+            name: "desk".to_string(),
+            outputs: vec!["DP-1".to_string()],
+            workspaces: vec![crate::config::Workspace {
+                output: "DP-1".to_string(),
+                default_tag: Some(3),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }];
+
+        let mut screen = Screen::default();
+        screen.id = Some(2);
+        manager.screen_create_handler(screen);
+        assert!(manager.state.workspaces[0].has_tag(&3));
+    }
+
+    #[test]
+    fn profiles_apply_without_plain_workspaces() {
+        let mut manager = Manager::new_test(vec!["web".to_string(), "code".to_string()]);
+        manager.config.workspaces = None;
+        manager.config.workspace_profiles = vec![crate::config::WorkspaceProfile {
+            name: "desk".to_string(),
+            outputs: vec!["DP-1".to_string()],
+            workspaces: vec![crate::config::Workspace {
+                output: "DP-1".to_string(),
+                default_tag: Some(2),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }];
+
+        let mut screen = Screen::default();
+        screen.id = Some(1);
+        manager.screen_create_handler(screen);
+        assert!(manager.state.workspaces[0].has_tag(&2));
+    }
 }
@@ -0,0 +1,180 @@
+use super::{Manager, Screen};
+use crate::config::Config;
+use crate::display_servers::DisplayServer;
+use crate::models::DetachedWorkspace;
+use crate::StateEvent;
+
+impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
+    /// Remove the workspace of a disconnected screen. Its tags are toggled into view on the
+    /// focused workspace, so its windows stay visible until the screen is connected again.
+    ///
+    /// Returns `true` if changes need to be rendered.
+    pub fn screen_destroy_handler(&mut self, screen: &Screen) -> bool {
+        tracing::trace!("Screen destroy: {:?}", screen);
+
+        let Some(screen_index) = self.state.screen_index(screen) else {
+            return false;
+        };
+        let workspace_id = self.state.screens[screen_index].id;
+        let Some(ws_index) = self
+            .state
+            .workspaces
+            .iter()
+            .position(|ws| Some(ws.id) == workspace_id)
+        else {
+            return false;
+        };
+        if self.state.workspaces.len() < 2 {
+            tracing::warn!("Not removing the last workspace.");
+            return false;
+        }
+
+        let was_focused = self.state.focus_manager.workspace_history.front() == Some(&ws_index);
+        let old_screen = self.state.screens.remove(screen_index);
+        let workspace = self.state.workspaces.remove(ws_index);
+
+        // Workspace history refers to workspaces by their index.
+        let history = &mut self.state.focus_manager.workspace_history;
+        *history = history
+            .iter()
+            .filter(|i| **i != ws_index)
+            .map(|i| if *i > ws_index { i - 1 } else { *i })
+            .collect();
+
+        let target_index = if was_focused {
+            0
+        } else {
+            self.state
+                .focus_manager
+                .workspace_history
+                .front()
+                .copied()
+                .unwrap_or_default()
+        };
+        let target = &mut self.state.workspaces[target_index];
+        for tag in workspace.tags() {
+            target.toggle_tag(&tag);
+        }
+        if was_focused {
+            let target = target.clone();
+            self.state
+                .focus_manager
+                .workspace_history
+                .retain(|i| *i != target_index);
+            self.state.focus_workspace(&target);
+        }
+        let target = self.state.workspaces[target_index].xyhw;
+        self.state.move_floating_windows(&workspace, target);
+
+        self.state.events.push_back(StateEvent::ScreenRemoved {
+            workspace: workspace.id,
+            output: old_screen.output.clone(),
+        });
+        self.state.detached_workspaces.push(DetachedWorkspace {
+            output: old_screen.output,
+            workspace,
+        });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BBox, Window, WindowHandle, XyhwBuilder};
+
+    fn screen(output: &str, x: i32) -> Screen {
+        Screen::new(
+            BBox {
+                x,
+                y: 0,
+                width: 800,
+                height: 600,
+            },
+            output.to_string(),
+        )
+    }
+
+    #[test]
+    fn removing_a_screen_moves_its_tags_and_restores_them() {
+        let mut manager =
+            Manager::new_test(vec!["1".to_string(), "2".to_string(), "3".to_string()]);
+        manager.screen_create_handler(screen("eDP-1", 0));
+        manager.screen_create_handler(screen("HDMI-1", 800));
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        assert!(manager.state.windows[0].has_tag(&2));
+
+        assert!(manager.screen_destroy_handler(&screen("HDMI-1", 800)));
+        assert_eq!(manager.state.workspaces.len(), 1);
+        assert_eq!(manager.state.screens.len(), 1);
+        let remaining = &manager.state.workspaces[0];
+        assert!(
+            remaining.shows_tag(&2),
+            "tag of the removed workspace should be shown"
+        );
+        assert!(remaining.is_displaying(&manager.state.windows[0]));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(1));
+
+        assert!(manager.screen_create_handler(screen("HDMI-1", 800)));
+        assert_eq!(manager.state.workspaces.len(), 2);
+        assert!(!manager.state.workspaces[0].shows_tag(&2));
+        assert!(manager.state.workspaces[1].has_tag(&2));
+        assert!(manager.state.detached_workspaces.is_empty());
+    }
+
+    #[test]
+    fn floating_windows_keep_their_place_on_the_workspace_they_move_to() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        let hdmi = Screen::new(
+            BBox {
+                x: 800,
+                y: 0,
+                width: 1920,
+                height: 1080,
+            },
+            "HDMI-1".to_string(),
+        );
+        manager.screen_create_handler(screen("eDP-1", 0));
+        manager.screen_create_handler(hdmi.clone());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        let window = &mut manager.state.windows[0];
+        window.set_floating(true);
+        window.normal = manager.state.workspaces[1].xyhw;
+        window.set_floating_exact(
+            XyhwBuilder {
+                x: 1000,
+                y: 100,
+                w: 300,
+                h: 200,
+                ..XyhwBuilder::default()
+            }
+            .into(),
+        );
+        let placed = |manager: &Manager<_, _>| {
+            let xyhw = manager.state.windows[0].exact_xyhw();
+            (xyhw.x(), xyhw.y(), xyhw.w(), xyhw.h())
+        };
+
+        assert!(manager.screen_destroy_handler(&hdmi));
+        assert_eq!(placed(&manager), (200, 100, 300, 200));
+
+        assert!(manager.screen_create_handler(hdmi));
+        assert_eq!(placed(&manager), (1000, 100, 300, 200));
+    }
+
+    #[test]
+    fn the_last_screen_is_not_removed() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(screen("eDP-1", 0));
+        assert!(!manager.screen_destroy_handler(&screen("eDP-1", 0)));
+        assert_eq!(manager.state.workspaces.len(), 1);
+    }
+}
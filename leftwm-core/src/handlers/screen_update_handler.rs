@@ -0,0 +1,56 @@
+use super::{Manager, Screen};
+use crate::config::Config;
+use crate::display_servers::DisplayServer;
+
+impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
+    /// Apply the new geometry of a screen to its workspace.
+    ///
+    /// Returns `true` if changes need to be rendered.
+    pub fn screen_update_handler(&mut self, screen: &Screen) -> bool {
+        tracing::trace!("Screen update: {:?}", screen);
+
+        let Some(index) = self.state.screen_index(screen) else {
+            return false;
+        };
+        let known = &mut self.state.screens[index];
+        known.bbox = screen.bbox;
+        known.max_window_width = screen.max_window_width;
+        let id = known.id;
+
+        match self.state.workspaces.iter_mut().find(|ws| Some(ws.id) == id) {
+            Some(workspace) => {
+                workspace.set_bbox(screen.bbox);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::BBox;
+
+    #[test]
+    fn updating_a_screen_resizes_its_workspace() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        let mut screen = Screen::new(
+            BBox {
+                x: 0,
+                y: 0,
+                width: 800,
+                height: 600,
+            },
+            "eDP-1".to_string(),
+        );
+        manager.screen_create_handler(screen.clone());
+
+        screen.bbox.width = 1920;
+        screen.bbox.height = 1080;
+        assert!(manager.screen_update_handler(&screen));
+        assert_eq!(manager.state.workspaces[0].xyhw.w(), 1920);
+        assert_eq!(manager.state.workspaces[0].xyhw.h(), 1080);
+        assert_eq!(manager.state.screens[0].bbox.width, 1920);
+    }
+}
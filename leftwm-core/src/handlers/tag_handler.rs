@@ -0,0 +1,333 @@
+use crate::display_action::DisplayAction;
+use crate::models::TagId;
+use crate::state::State;
+
+impl State {
+    /// Append a new normal tag with the given label.
+    pub fn add_tag(&mut self, label: &str) -> TagId {
+        let id = self.tags.add_new(label);
+        self.push_tag_labels();
+        id
+    }
+
+    /// Change the label of a normal tag.
+    ///
+    /// Returns `false` if the tag does not exist.
+    pub fn rename_tag(&mut self, id: TagId, label: &str) -> bool {
+        if id == 0 || id > self.tags.len_normal() {
+            return false;
+        }
+        let Some(tag) = self.tags.get_mut(id) else {
+            return false;
+        };
+        tag.label = label.to_string();
+        self.push_tag_labels();
+        true
+    }
+
+    /// Remove a normal tag, the tags right of it are re-numbered.
+    ///
+    /// Windows only on the removed tag are moved to the tag which replaces it on its workspace,
+    /// or to the tag left of it (right of it for the first tag) if it wasn't displayed.
+    ///
+    /// Returns `false` if the tag does not exist, or if there would not be enough tags
+    /// left to display one on each workspace.
+    pub fn remove_tag(&mut self, id: TagId) -> bool {
+        let len = self.tags.len_normal();
+        if id == 0 || id > len {
+            return false;
+        }
+        if len - 1 < self.workspaces.len() {
+            tracing::warn!(
+                "Can't remove tag {}, every workspace needs a tag to display",
+                id
+            );
+            return false;
+        }
+
+        let replacement = match self.workspaces.iter().position(|ws| ws.has_tag(&id)) {
+            Some(index) => {
+                let replacement = self.take_replacement_tag(index, id);
+                self.workspaces[index].tag = replacement;
+                replacement
+            }
+            None => None,
+        };
+        let fallback = match replacement {
+            Some(replacement) => replacement,
+            None if id > 1 => id - 1,
+            None => id + 1,
+        };
+        // Windows which are on other tags as well simply leave the removed one.
+        for window in self.windows.iter_mut().filter(|w| w.tags().eq([id])) {
+            window.tag(&fallback);
+            let act = DisplayAction::SetWindowTag(window.handle, window.tag);
+            self.actions.push_back(act);
+        }
+
+        self.tags.remove(id);
+        self.remap_tag_ids(|t| match t {
+            t if t == id => None,
+            t if t > id && t <= len => Some(t - 1),
+            t => Some(t),
+        });
+        if let Some(tag) = self
+            .focus_manager
+            .workspace(&self.workspaces)
+            .and_then(|ws| ws.tag)
+        {
+            self.focus_tag(&tag);
+        }
+        self.update_static();
+        true
+    }
+
+    /// Find the tag the workspace at `index` switches to when its tag `removed` is removed.
+    ///
+    /// This is the first tag not in view yet. If every tag is in view, a tag toggled in by the
+    /// workspace itself, or else by another workspace, is taken out of its extra tags.
+    fn take_replacement_tag(&mut self, index: usize, removed: TagId) -> Option<TagId> {
+        let free = (1..=self.tags.len_normal())
+            .find(|t| *t != removed && !self.workspaces.iter().any(|ws| ws.shows_tag(t)));
+        if free.is_some() {
+            return free;
+        }
+        let own = &mut self.workspaces[index].extra_tags;
+        if let Some(position) = own.iter().position(|t| *t != removed) {
+            return Some(own.remove(position));
+        }
+        self.workspaces.iter_mut().find_map(|ws| {
+            let position = ws.extra_tags.iter().position(|t| *t != removed)?;
+            Some(ws.extra_tags.remove(position))
+        })
+    }
+
+    /// Move a normal tag to another position, so that its ID becomes `position`.
+    ///
+    /// Returns `false` if either the tag or the position does not exist.
+    pub fn move_tag(&mut self, id: TagId, position: TagId) -> bool {
+        if !self.tags.move_to(id, position) {
+            return false;
+        }
+        self.remap_tag_ids(|t| match t {
+            t if t == id => Some(position),
+            // Tags between the old and new position shift by one towards the old position.
+            t if id < position && t > id && t <= position => Some(t - 1),
+            t if position < id && t >= position && t < id => Some(t + 1),
+            t => Some(t),
+        });
+        self.update_static();
+        true
+    }
+
+    /// Update every reference to a normal tag after the tags were re-numbered.
+    ///
+    /// `new_id` maps an old tag ID to its new one, or to `None` if the tag was removed.
+    /// Hidden tags have to be mapped onto themselves.
+    fn remap_tag_ids(&mut self, new_id: impl Fn(TagId) -> Option<TagId>) {
+        for window in &mut self.windows {
+            let old = window.tag;
+            window.tag = old.and_then(&new_id);
+            window.extra_tags = window
+                .extra_tags
+                .iter()
+                .filter_map(|t| new_id(*t))
+                .collect();
+            if window.tag.is_none() && !window.extra_tags.is_empty() {
+                window.tag = Some(window.extra_tags.remove(0));
+            }
+            window.weights = window
+                .weights
+                .drain()
+                .filter_map(|(t, weight)| new_id(t).map(|t| (t, weight)))
+                .collect();
+            if window.tag != old {
+                let act = DisplayAction::SetWindowTag(window.handle, window.tag);
+                self.actions.push_back(act);
+            }
+        }
+        for ws in &mut self.workspaces {
+            ws.tag = ws.tag.and_then(&new_id);
+            ws.extra_tags = ws.extra_tags.iter().filter_map(|t| new_id(*t)).collect();
+        }
+
+        let focus_manager = &mut self.focus_manager;
+        focus_manager.tag_history = focus_manager
+            .tag_history
+            .iter()
+            .filter_map(|t| new_id(*t))
+            .collect();
+        focus_manager.tags_last_window = focus_manager
+            .tags_last_window
+            .drain()
+            .filter_map(|(t, handle)| new_id(t).map(|t| (t, handle)))
+            .collect();
+        self.window_history = self
+            .window_history
+            .drain()
+            .filter_map(|(t, handles)| new_id(t).map(|t| (t, handles)))
+            .collect();
+        self.split_trees = self
+            .split_trees
+            .drain()
+            .filter_map(|(t, tree)| new_id(t).map(|t| (t, tree)))
+            .collect();
+        self.layout_manager.remap_tags(&new_id);
+
+        let act = DisplayAction::SetCurrentTags(self.focus_manager.tag(0));
+        self.actions.push_back(act);
+        self.push_tag_labels();
+    }
+
+    fn push_tag_labels(&mut self) {
+        let labels = self
+            .tags
+            .normal()
+            .iter()
+            .map(|tag| tag.label.clone())
+            .collect();
+        self.actions.push_back(DisplayAction::SetTagLabels(labels));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::tests::TestConfig;
+    use crate::display_servers::MockDisplayServer;
+    use crate::models::{Screen, Window, WindowHandle};
+    use crate::Manager;
+
+    fn manager_with_tags() -> Manager<TestConfig, MockDisplayServer> {
+        let mut manager = Manager::new_test(vec![
+            "home".to_string(),
+            "chat".to_string(),
+            "surf".to_string(),
+            "code".to_string(),
+        ]);
+        manager.screen_create_handler(Screen::default());
+        manager
+    }
+
+    #[test]
+    fn removing_a_tag_moves_its_windows_and_renumbers_the_others() {
+        let mut manager = manager_with_tags();
+        for i in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.state.windows[1].untag();
+        manager.state.windows[1].tag(&3);
+        manager.state.windows[2].untag();
+        manager.state.windows[2].tag(&4);
+        let handle_on_3 = manager.state.windows[1].handle;
+        let handle_on_4 = manager.state.windows[2].handle;
+
+        assert!(manager.state.remove_tag(3));
+
+        let labels: Vec<&str> = manager
+            .state
+            .tags
+            .normal()
+            .iter()
+            .map(|t| t.label.as_str())
+            .collect();
+        assert_eq!(labels, vec!["home", "chat", "code"]);
+        let tag_of = |handle| {
+            manager
+                .state
+                .windows
+                .iter()
+                .find(|w| w.handle == handle)
+                .unwrap()
+                .tag
+        };
+        // The windows of the removed tag move to the tag left of it.
+        assert_eq!(tag_of(handle_on_3), Some(2));
+        // The windows of the tags right of it follow their tag.
+        assert_eq!(tag_of(handle_on_4), Some(3));
+    }
+
+    #[test]
+    fn removing_a_displayed_tag_replaces_it_on_the_workspace() {
+        let mut manager = manager_with_tags();
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+
+        assert!(manager.state.remove_tag(1));
+
+        // Tag 2 ("chat") was the first free tag, which is now tag 1.
+        assert_eq!(manager.state.workspaces[0].tag, Some(1));
+        assert_eq!(manager.state.tags.get(1).unwrap().label, "chat");
+        assert_eq!(manager.state.windows[0].tag, Some(1));
+    }
+
+    #[test]
+    fn removing_a_displayed_tag_takes_a_replacement_from_the_extra_tags() {
+        let mut manager = Manager::new_test(vec![
+            "home".to_string(),
+            "chat".to_string(),
+            "surf".to_string(),
+        ]);
+        manager.screen_create_handler(Screen::default());
+        manager.screen_create_handler(Screen::default());
+        manager.state.workspaces[1].show_tag(&3);
+        assert!(manager.state.workspaces[0].toggle_tag(&2));
+
+        assert!(manager.state.remove_tag(3));
+
+        // Every tag was in view, so "chat" leaves the extra tags of the first workspace.
+        assert_eq!(manager.state.workspaces[0].tag, Some(1));
+        assert!(manager.state.workspaces[0].extra_tags.is_empty());
+        assert_eq!(manager.state.workspaces[1].tag, Some(2));
+    }
+
+    #[test]
+    fn the_last_tags_can_not_be_removed() {
+        let mut manager = Manager::new_test(vec!["home".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        assert!(!manager.state.remove_tag(1));
+        assert!(!manager.state.remove_tag(2));
+        assert_eq!(manager.state.tags.len_normal(), 1);
+    }
+
+    #[test]
+    fn moving_a_tag_keeps_windows_on_their_tag() {
+        let mut manager = manager_with_tags();
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.state.windows[0].untag();
+        manager.state.windows[0].tag(&4);
+
+        assert!(manager.state.move_tag(4, 1));
+
+        assert_eq!(manager.state.tags.get(1).unwrap().label, "code");
+        assert_eq!(manager.state.windows[0].tag, Some(1));
+        // "home" was displayed on the workspace and is now tag 2.
+        assert_eq!(manager.state.workspaces[0].tag, Some(2));
+    }
+
+    #[test]
+    fn renaming_and_adding_tags() {
+        let mut manager = manager_with_tags();
+        assert!(manager.state.rename_tag(2, "mail"));
+        assert!(!manager.state.rename_tag(7, "nope"));
+        assert_eq!(manager.state.add_tag("music"), 5);
+        let labels: Vec<&str> = manager
+            .state
+            .tags
+            .normal()
+            .iter()
+            .map(|t| t.label.as_str())
+            .collect();
+        assert_eq!(labels, vec!["home", "mail", "surf", "code", "music"]);
+    }
+}
@@ -4,11 +4,11 @@ use crate::config::{Config, InsertBehavior};
 use crate::display_action::DisplayAction;
 use crate::display_servers::DisplayServer;
 use crate::layouts::{self, MAIN_AND_VERT_STACK};
-use crate::models::{WindowHandle, WindowState, Xyhw};
+use crate::models::{ScratchPadName, TagId, WindowHandle, WindowState, Xyhw};
 use crate::state::State;
 use crate::utils::helpers;
+use crate::StateEvent;
 use std::env;
-use std::str::FromStr;
 
 impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
     /// Process a collection of events, and apply them changes to a manager.
@@ -22,10 +22,23 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         // Setup any predifined hooks.
         self.config
             .setup_predefined_window(&mut self.state, &mut window);
+        adopt_by_scratchpad(&mut self.state, &window);
+        // A window of the restored session goes back to its tag.
+        let restored = self.state.take_session_window(&window);
+        if let Some((_, tag)) = &restored {
+            window.tag(tag);
+        }
         let mut is_first = false;
         let mut on_same_tag = true;
         // Random value
         let mut layout = MAIN_AND_VERT_STACK.to_string();
+        let terminal = find_terminal(
+            &self.state,
+            window.pid,
+            &self.config.terminal_classes(),
+            self.parent_pid,
+        )
+        .map(|terminal| (terminal.handle, terminal.tag));
         setup_window(
             &mut self.state,
             &mut window,
@@ -33,14 +46,39 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             &mut layout,
             &mut is_first,
             &mut on_same_tag,
+            terminal.and_then(|(_, tag)| tag),
         );
         self.config.load_window(&mut window);
-        insert_window(&mut self.state, &mut window, &layout);
+        apply_floating_overrides(&self.state, &mut window);
+        if let Some((restored, tag)) = &restored {
+            let workspace = self.state.session_workspace(*tag);
+            restored.apply(&mut window, *tag, workspace.as_ref());
+        }
+        let swallowed = terminal.map_or(false, |(terminal, _)| {
+            window.r#type == WindowType::Normal
+                && self.config.swallow(&self.state, &window)
+                && swallow_terminal(&mut self.state, &mut window, terminal)
+        });
+        if !swallowed {
+            insert_window(&mut self.state, &mut window, &layout);
+            if let Some((restored, _)) = &restored {
+                self.state.restore_slot(window.handle, restored.slot);
+            }
+        }
 
-        let follow_mouse = self.state.focus_manager.focus_new_windows
+        let focus_new_windows = window
+            .overrides
+            .focus_on_map
+            .unwrap_or(self.state.focus_manager.focus_new_windows);
+        let follow_mouse = focus_new_windows
             && self.state.focus_manager.behaviour.is_sloppy()
             && self.state.focus_manager.sloppy_mouse_follows_focus
             && on_same_tag;
+        if !window.overrides.border_colors.is_empty() {
+            let colors = window.overrides.border_colors.clone();
+            let act = DisplayAction::SetBorderColors(window.handle, colors);
+            self.state.actions.push_back(act);
+        }
         // Let the DS know we are managing this window.
         let act = DisplayAction::AddedWindow(window.handle, window.floating(), follow_mouse);
         self.state.actions.push_back(act);
@@ -51,11 +89,23 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             self.state.actions.push_back(act);
         }
 
+        self.state.events.push_back(StateEvent::WindowCreated {
+            handle: window.handle,
+            title: window.name.clone(),
+            res_class: window.res_class.clone(),
+            tag: window.tag,
+        });
+
         // Tell the WM to reevaluate the stacking order, so the new window is put in the correct layer
         self.state.sort_windows();
         self.state.handle_single_border(self.config.border_width());
 
-        if (self.state.focus_manager.focus_new_windows || is_first) && on_same_tag {
+        // A window which must not be focused isn't, even if it is the first one on its tag.
+        let focus_on_map = window
+            .overrides
+            .focus_on_map
+            .unwrap_or(focus_new_windows || is_first);
+        if focus_on_map && on_same_tag {
             self.state.focus_window(&window.handle);
         }
 
@@ -83,11 +133,21 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
                 Some(window) => (window.transient, window.floating(), window.visible()),
                 None => return false,
             };
+        let restored = self
+            .state
+            .windows
+            .iter()
+            .find(|w| &w.handle == handle)
+            .cloned()
+            .and_then(|window| restore_swallowed(&mut self.state, &window));
         self.state
             .focus_manager
             .tags_last_window
             .retain(|_, h| h != handle);
         self.state.windows.retain(|w| &w.handle != handle);
+        self.state
+            .events
+            .push_back(StateEvent::WindowDestroyed { handle: *handle });
 
         self.state.handle_single_border(self.config.border_width());
 
@@ -97,7 +157,9 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         let focused = self.state.focus_manager.window_history.get(0);
         // Make sure focus is recalculated if we closed the currently focused window
         if focused == Some(&Some(*handle)) {
-            if self.state.focus_manager.behaviour.is_sloppy()
+            if let Some(terminal) = restored {
+                self.state.focus_window(&terminal);
+            } else if self.state.focus_manager.behaviour.is_sloppy()
                 && self.state.focus_manager.sloppy_mouse_follows_focus
             {
                 let act = DisplayAction::FocusWindowUnderCursor;
@@ -112,17 +174,22 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
                 let act = DisplayAction::Unfocus(Some(*handle), floating);
                 self.state.actions.push_back(act);
                 self.state.focus_manager.window_history.push_front(None);
+                self.state
+                    .events
+                    .push_back(StateEvent::FocusChanged { handle: None });
             }
         }
 
         // Only update windows if this window is visible.
-        visible
+        visible || restored.is_some()
     }
 
     pub fn window_changed_handler(&mut self, change: WindowChange) -> bool {
         let mut changed = false;
         let mut fullscreen_changed = false;
         let strut_changed = change.strut.is_some();
+        let mut urgent_raised = None;
+        let mut renamed = None;
         let windows = self.state.windows.clone();
         if let Some(window) = self
             .state
@@ -145,13 +212,34 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
                 _ => None,
             };
 
+            let was_urgent = window.urgent;
+            let old =
+                (change.name.is_some() || change.res_name.is_some() || change.res_class.is_some())
+                    .then(|| window.clone());
             changed = change.update(window, container);
+            renamed = old.filter(|old| {
+                old.name != window.name
+                    || old.res_name != window.res_name
+                    || old.res_class != window.res_class
+            });
+            if !was_urgent && window.urgent {
+                urgent_raised = Some(StateEvent::UrgentRaised {
+                    handle: window.handle,
+                    tag: window.tag,
+                });
+            }
             if window.r#type == WindowType::Dock {
                 update_workspace_avoid_list(&mut self.state);
                 // Don't let changes from docks re-render the worker. This will result in an
                 // infinite loop. Just be patient a rerender will occur.
             }
         }
+        if let Some(event) = urgent_raised {
+            self.state.events.push_back(event);
+        }
+        if let Some(old) = renamed {
+            changed = self.reevaluate_predefined_window(&old) || changed;
+        }
         if fullscreen_changed {
             // Update `dock` windows once, so they can recieve mouse click events again.
             // This is necessary, since we exclude them from the general update loop above.
@@ -173,6 +261,50 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         changed
     }
 
+    /// Apply the window rules which run again after the title or class of a window changed.
+    ///
+    /// Returns `true` if a rule was applied.
+    fn reevaluate_predefined_window(&mut self, old: &Window) -> bool {
+        let Some(mut window) = self
+            .state
+            .windows
+            .iter()
+            .find(|w| w.handle == old.handle)
+            .cloned()
+        else {
+            return false;
+        };
+        if !self
+            .config
+            .reevaluate_predefined_window(&mut self.state, old, &mut window)
+        {
+            return false;
+        }
+        if window.tag != old.tag {
+            let act = DisplayAction::SetWindowTag(window.handle, window.tag);
+            self.state.actions.push_back(act);
+        }
+        if window.overrides != old.overrides {
+            self.config.load_window(&mut window);
+            apply_floating_overrides(&self.state, &mut window);
+            if window.overrides.border_colors != old.overrides.border_colors {
+                let colors = window.overrides.border_colors.clone();
+                let act = DisplayAction::SetBorderColors(window.handle, colors);
+                self.state.actions.push_back(act);
+            }
+        }
+        if let Some(w) = self
+            .state
+            .windows
+            .iter_mut()
+            .find(|w| w.handle == window.handle)
+        {
+            *w = window;
+        }
+        self.state.sort_windows();
+        true
+    }
+
     /// Find the next or previous window on the currently focused workspace.
     /// May return `None` if no other window is present.
     pub fn get_next_or_previous_handle(&mut self, handle: &WindowHandle) -> Option<WindowHandle> {
@@ -191,7 +323,48 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
 
 // Helper functions.
 
-fn find_terminal(state: &State, pid: Option<u32>) -> Option<&Window> {
+/// Find the window of the terminal the process with the given pid was launched from.
+///
+/// The ancestors of the process are looked up among the windows with one of the given terminal
+/// classes. Without any terminal classes, the parent of the process has to be `$SHELL`, which in
+/// turn has to be launched by the terminal. `parent_pid` looks up the parent of a process.
+fn find_terminal<'a>(
+    state: &'a State,
+    pid: Option<u32>,
+    terminal_classes: &[String],
+    parent_pid: fn(u32) -> Option<u32>,
+) -> Option<&'a Window> {
+    let pid = pid?;
+    if terminal_classes.is_empty() {
+        return find_shell_terminal(state, pid, parent_pid);
+    }
+
+    let is_terminal = |w: &&Window| {
+        w.res_class
+            .as_ref()
+            .map_or(false, |class| terminal_classes.contains(class))
+    };
+    let mut ancestor = parent_pid(pid)?;
+    // Stop at init, which adopts orphaned processes.
+    while ancestor > 1 {
+        let terminal = state
+            .windows
+            .iter()
+            .filter(is_terminal)
+            .find(|w| w.pid == Some(ancestor));
+        if terminal.is_some() {
+            return terminal;
+        }
+        ancestor = parent_pid(ancestor)?;
+    }
+    None
+}
+
+fn find_shell_terminal(
+    state: &State,
+    pid: u32,
+    parent_pid: fn(u32) -> Option<u32>,
+) -> Option<&Window> {
     // Get $SHELL, e.g. /bin/zsh
     let shell_path = env::var("SHELL").ok()?;
     // Remove /bin/
@@ -204,24 +377,73 @@ fn find_terminal(state: &State, pid: Option<u32>) -> Option<&Window> {
         Some(parent_str == shell)
     };
 
-    let get_parent = |pid: u32| -> Option<u32> {
-        let stat = std::fs::read(format!("/proc/{pid}/stat")).ok()?;
-        let ppid_bytes = stat.split(|&c| c == b' ').nth(3)?;
-        let ppid_str = std::str::from_utf8(ppid_bytes).ok()?;
-        let ppid_u32 = u32::from_str(ppid_str).ok()?;
-        Some(ppid_u32)
-    };
-
-    let pid = pid?;
-    let shell_id = get_parent(pid)?;
+    let shell_id = parent_pid(pid)?;
     if is_terminal(shell_id)? {
-        let terminal = get_parent(shell_id)?;
+        let terminal = parent_pid(shell_id)?;
         return state.windows.iter().find(|w| w.pid == Some(terminal));
     }
 
     None
 }
 
+/// Put the window in the place of the terminal it was launched from, hiding the terminal.
+///
+/// Returns `false` if the terminal could not be swallowed.
+fn swallow_terminal(state: &mut State, window: &mut Window, terminal: WindowHandle) -> bool {
+    let Some(hidden) = state
+        .tags
+        .get_hidden_by_label("SWALLOWED")
+        .map(|tag| tag.id)
+    else {
+        return false;
+    };
+    let Some(index) = state.windows.iter().position(|w| w.handle == terminal) else {
+        return false;
+    };
+    let mut terminal = state.windows.remove(index);
+
+    window.tag = terminal.tag;
+    window.extra_tags = terminal.extra_tags.clone();
+    if terminal.floating() {
+        window.set_floating(true);
+        window.normal = terminal.normal;
+        window.set_floating_exact(terminal.exact_xyhw());
+    }
+    window.swallowed = Some(terminal.handle);
+
+    terminal.tag(&hidden);
+    terminal.set_visible(false);
+    let act = DisplayAction::SetWindowTag(terminal.handle, terminal.tag);
+    state.actions.push_back(act);
+
+    state.windows.insert(index, window.clone());
+    state.windows.push(terminal);
+    true
+}
+
+/// Bring back the terminal swallowed by the window, in the place of the window.
+///
+/// Returns the handle of the terminal if it was restored.
+fn restore_swallowed(state: &mut State, window: &Window) -> Option<WindowHandle> {
+    let terminal = window.swallowed?;
+    let terminal_index = state.windows.iter().position(|w| w.handle == terminal)?;
+    let mut terminal = state.windows.remove(terminal_index);
+
+    terminal.tag = window.tag;
+    terminal.extra_tags = window.extra_tags.clone();
+    let act = DisplayAction::SetWindowTag(terminal.handle, terminal.tag);
+    state.actions.push_back(act);
+
+    let handle = terminal.handle;
+    let index = state
+        .windows
+        .iter()
+        .position(|w| w.handle == window.handle)
+        .unwrap_or_default();
+    state.windows.insert(index, terminal);
+    Some(handle)
+}
+
 fn find_transient_parent(windows: &[Window], transient: Option<WindowHandle>) -> Option<&Window> {
     let mut transient = transient?;
     loop {
@@ -329,8 +551,46 @@ fn insert_window(state: &mut State, window: &mut Window, layout: &str) {
 fn is_scratchpad(state: &State, window: &Window) -> bool {
     state
         .active_scratchpads
-        .iter()
-        .any(|(_, id)| id.iter().any(|id| window.pid == Some(*id)))
+        .values()
+        .any(|handles| handles.contains(&window.handle))
+}
+
+/// Let a scratchpad take the new window. The first window of a process launched by a scratchpad
+/// belongs to it, otherwise a scratchpad without a window adopts the window if it matches its
+/// class or title.
+fn adopt_by_scratchpad(state: &mut State, window: &Window) {
+    if is_scratchpad(state, window) {
+        return;
+    }
+    let spawned = window
+        .pid
+        .and_then(|pid| state.spawned_scratchpads.remove(&pid));
+    let has_window = |name: &ScratchPadName| {
+        state.active_scratchpads.get(name).map_or(false, |handles| {
+            handles
+                .iter()
+                .any(|handle| state.windows.iter().any(|w| w.handle == *handle))
+        })
+    };
+    let Some(name) = spawned.or_else(|| {
+        state
+            .scratchpads
+            .iter()
+            .find(|s| s.matches(window) && !has_window(&s.name))
+            .map(|s| s.name.clone())
+    }) else {
+        return;
+    };
+    tracing::debug!(
+        "Scratchpad {:?} adopts new window {:?}",
+        name,
+        window.handle
+    );
+    state
+        .active_scratchpads
+        .entry(name)
+        .or_default()
+        .push_front(window.handle);
 }
 
 fn set_relative_floating(window: &mut Window, ws: &Workspace, outer: Xyhw) {
@@ -351,6 +611,32 @@ fn set_relative_floating(window: &mut Window, ws: &Workspace, outer: Xyhw) {
     window.set_floating_exact(xyhw);
 }
 
+/// Place a floating window as requested by its overrides, relative to the workspace it is on.
+fn apply_floating_overrides(state: &State, window: &mut Window) {
+    let geometry = window.overrides.floating_geometry;
+    let center = window.overrides.center;
+    if !window.floating() || (geometry.is_none() && !center) {
+        return;
+    }
+    let Some(ws) = state
+        .workspaces
+        .iter()
+        .find(|ws| ws.is_displaying(window))
+        .or_else(|| state.focus_manager.workspace(&state.workspaces))
+    else {
+        return;
+    };
+    let mut xyhw = window.exact_xyhw();
+    if let Some(geometry) = geometry {
+        xyhw = geometry.xyhw(xyhw, &ws.xyhw);
+    }
+    if center {
+        xyhw.center_relative(ws.xyhw, window.border);
+    }
+    window.normal = ws.xyhw;
+    window.set_floating_exact(xyhw);
+}
+
 fn setup_window(
     state: &mut State,
     window: &mut Window,
@@ -358,6 +644,7 @@ fn setup_window(
     layout: &mut String,
     is_first: &mut bool,
     on_same_tag: &mut bool,
+    terminal_tag: Option<TagId>,
 ) {
     // Identify the workspace in which to create the window.
     let ws = state
@@ -373,6 +660,13 @@ fn setup_window(
         .flatten()
         .or(state.focus_manager.workspace(&state.workspaces)); // Else, use the workspace which has the focus.
 
+    // A scratchpad with a workspace of its own is always created there.
+    let ws = state
+        .scratchpad_of(window)
+        .filter(|s| s.workspace.is_some())
+        .and_then(|s| state.scratchpad_workspace(s))
+        .or(ws);
+
     // If no workspace was found, put the window on tag 1.
     let Some(ws) = ws else {
         tracing::warn!("setup_window failed to identify workspace. Falling back to tag 1");
@@ -391,8 +685,7 @@ fn setup_window(
     *is_first = !state.windows.iter().any(for_active_workspace);
     // May have been set by a predefined tag.
     if window.tag.is_none() {
-        window.tag =
-            find_terminal(state, window.pid).map_or_else(|| ws.tag, |terminal| terminal.tag);
+        window.tag = terminal_tag.or(ws.tag);
     }
     *on_same_tag = ws.tag == window.tag;
     *layout = state
@@ -405,7 +698,7 @@ fn setup_window(
     if let Some((scratchpad_name, _)) = state
         .active_scratchpads
         .iter()
-        .find(|(_, id)| id.iter().any(|id| Some(*id) == window.pid))
+        .find(|(_, handles)| handles.contains(&window.handle))
     {
         window.set_floating(true);
         if let Some(s) = state
@@ -413,9 +706,7 @@ fn setup_window(
             .iter()
             .find(|s| *scratchpad_name == s.name)
         {
-            let new_float_exact = s.xyhw(&ws.xyhw);
-            window.normal = ws.xyhw;
-            window.set_floating_exact(new_float_exact);
+            s.place(window, &ws.xyhw);
             return;
         }
     }
@@ -486,6 +777,222 @@ mod tests {
     use crate::models::Screen;
     use crate::Manager;
 
+    #[test]
+    fn creating_and_destroying_a_window_emits_events() {
+        let mut manager = Manager::new_test(vec![]);
+        manager.screen_create_handler(Screen::default());
+        manager.state.events.clear();
+
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        assert!(manager.state.events.iter().any(|e| matches!(
+            e,
+            StateEvent::WindowCreated {
+                handle: WindowHandle::MockHandle(1),
+                ..
+            }
+        )));
+        assert!(manager.state.events.contains(&StateEvent::FocusChanged {
+            handle: Some(WindowHandle::MockHandle(1))
+        }));
+
+        manager.state.events.clear();
+        manager.window_destroyed_handler(&WindowHandle::MockHandle(1));
+        assert!(manager.state.events.contains(&StateEvent::WindowDestroyed {
+            handle: WindowHandle::MockHandle(1)
+        }));
+    }
+
+    #[test]
+    fn windows_launched_from_a_terminal_swallow_it() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.config.terminal_classes = vec!["Alacritty".to_string()];
+        manager.config.swallow = true;
+        manager.screen_create_handler(Screen::default());
+
+        // The child 30 is launched from the shell 20, which runs in the terminal 10.
+        manager.parent_pid = |pid| match pid {
+            30 => Some(20),
+            20 => Some(10),
+            _ => Some(1),
+        };
+        let mut terminal = Window::new(WindowHandle::MockHandle(1), None, Some(10));
+        terminal.res_class = Some("Alacritty".to_string());
+        manager.window_created_handler(terminal, -1, -1);
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(2), None, Some(40)),
+            -1,
+            -1,
+        );
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(3), None, Some(30)),
+            -1,
+            -1,
+        );
+
+        let hidden = manager
+            .state
+            .tags
+            .get_hidden_by_label("SWALLOWED")
+            .unwrap()
+            .id;
+        assert_eq!(manager.state.windows[0].handle, WindowHandle::MockHandle(3));
+        assert_eq!(manager.state.windows[0].tag, Some(1));
+        assert_eq!(manager.state.windows[2].handle, WindowHandle::MockHandle(1));
+        assert_eq!(manager.state.windows[2].tag, Some(hidden));
+
+        manager.state.focus_window(&WindowHandle::MockHandle(3));
+        manager.window_destroyed_handler(&WindowHandle::MockHandle(3));
+        assert_eq!(manager.state.windows[0].handle, WindowHandle::MockHandle(1));
+        assert_eq!(manager.state.windows[0].tag, Some(1));
+        assert_eq!(
+            manager
+                .state
+                .focus_manager
+                .window(&manager.state.windows)
+                .map(|w| w.handle),
+            Some(WindowHandle::MockHandle(1))
+        );
+    }
+
+    #[test]
+    fn window_overrides_place_the_window_and_prevent_focus() {
+        use crate::models::{FloatingGeometry, Size};
+
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let mut window = Window::new(WindowHandle::MockHandle(1), None, None);
+        window.set_floating(true);
+        window.overrides.floating_geometry = Some(FloatingGeometry {
+            x: Some(Size::Ratio(0.5)),
+            y: Some(Size::Pixel(0)),
+            width: Some(Size::Pixel(200)),
+            height: Some(Size::Ratio(0.5)),
+        });
+        window.overrides.focus_on_map = Some(false);
+        manager.window_created_handler(window, -1, -1);
+
+        let xyhw = manager.state.windows[0].exact_xyhw();
+        assert_eq!((xyhw.x(), xyhw.y(), xyhw.w(), xyhw.h()), (400, 0, 200, 300));
+        // Even though it is the first window on the tag.
+        assert!(manager
+            .state
+            .focus_manager
+            .window(&manager.state.windows)
+            .is_none());
+    }
+
+    #[test]
+    fn windows_of_a_restored_session_are_placed_as_they_appear() {
+        use crate::models::{FloatingGeometry, Session, SessionWindow, Size};
+
+        let mut manager =
+            Manager::new_test(vec!["1".to_string(), "2".to_string(), "3".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let entry = |class: &str, title: &str, tag: &str, slot| SessionWindow {
+            class: class.to_string(),
+            title: Some(title.to_string()),
+            tag: tag.to_string(),
+            floating: None,
+            slot,
+        };
+        manager.state.restore_session(Session {
+            windows: vec![
+                entry("Alacritty", "htop", "2", 1),
+                entry("Alacritty", "vim", "2", 0),
+                SessionWindow {
+                    floating: Some(FloatingGeometry {
+                        x: Some(Size::Pixel(10)),
+                        y: Some(Size::Pixel(20)),
+                        width: Some(Size::Pixel(300)),
+                        height: Some(Size::Pixel(200)),
+                    }),
+                    ..entry("mpv", "video", "3", 0)
+                },
+            ],
+        });
+
+        let window = |id, class: &str, title: &str| {
+            let mut window =
+                Window::new(WindowHandle::MockHandle(id), Some(title.to_string()), None);
+            window.res_class = Some(class.to_string());
+            window
+        };
+        manager.window_created_handler(window(1, "Alacritty", "htop"), -1, -1);
+        manager.window_created_handler(window(2, "mpv", "video"), -1, -1);
+        manager.window_created_handler(window(3, "Alacritty", "vim"), -1, -1);
+        manager.window_created_handler(window(4, "Alacritty", "zsh"), -1, -1);
+        assert!(manager.state.restored_session.pending.windows.is_empty());
+
+        let find = |id| {
+            manager
+                .state
+                .windows
+                .iter()
+                .position(|w| w.handle == WindowHandle::MockHandle(id))
+                .unwrap()
+        };
+        assert_eq!(manager.state.windows[find(1)].tag, Some(2));
+        assert_eq!(manager.state.windows[find(3)].tag, Some(2));
+        assert!(find(3) < find(1), "vim should take the first slot");
+        assert_eq!(manager.state.windows[find(4)].tag, Some(1));
+        let mpv = &manager.state.windows[find(2)];
+        assert_eq!(mpv.tag, Some(3));
+        assert!(mpv.floating());
+        let xyhw = mpv.exact_xyhw();
+        assert_eq!((xyhw.x(), xyhw.y(), xyhw.w(), xyhw.h()), (10, 20, 300, 200));
+
+        // The session saved again is the one restored, along with the new window.
+        let session = manager.state.session();
+        assert_eq!(session.windows.len(), 4);
+        let vim = session
+            .windows
+            .iter()
+            .find(|w| w.title.as_deref() == Some("vim"));
+        assert_eq!(vim.map(|w| (w.tag.as_str(), w.slot)), Some(("2", 0)));
+    }
+
+    #[test]
+    fn windows_of_a_restored_session_follow_the_label_of_their_tag() {
+        use crate::models::{Session, SessionWindow};
+
+        let mut manager = Manager::new_test(vec![
+            "web".to_string(),
+            "term".to_string(),
+            "chat".to_string(),
+        ]);
+        manager.screen_create_handler(Screen::default());
+        let mut window = Window::new(WindowHandle::MockHandle(1), Some("vim".to_string()), None);
+        window.res_class = Some("Alacritty".to_string());
+        manager.window_created_handler(window.clone(), -1, -1);
+        manager.state.windows[0].tag(&2);
+        let session = manager.state.session();
+        assert_eq!(session.windows[0].tag, "term");
+
+        // The tags were reordered since the session was saved.
+        let mut manager = Manager::new_test(vec![
+            "term".to_string(),
+            "chat".to_string(),
+            "web".to_string(),
+        ]);
+        manager.screen_create_handler(Screen::default());
+        manager.state.restore_session(Session {
+            windows: vec![
+                session.windows[0].clone(),
+                SessionWindow {
+                    tag: "gone".to_string(),
+                    ..session.windows[0].clone()
+                },
+            ],
+        });
+        assert_eq!(manager.state.restored_session.pending.windows.len(), 1);
+        manager.window_created_handler(window, -1, -1);
+        assert_eq!(manager.state.windows[0].tag, Some(1));
+    }
+
     #[test]
     fn insert_behavior_bottom_add_window_at_the_end_of_the_stack() {
         let mut manager = Manager::new_test(vec![]);
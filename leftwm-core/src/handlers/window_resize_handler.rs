@@ -1,6 +1,8 @@
 use super::{Manager, Window, WindowHandle};
 use crate::config::Config;
 use crate::display_servers::DisplayServer;
+use crate::models::{dragged_weights, TiledResize};
+use crate::state::State;
 
 impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
     pub fn window_resize_handler(
@@ -9,6 +11,9 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         offset_w: i32,
         offset_h: i32,
     ) -> bool {
+        if self.state.tiled_resize.is_some() {
+            return process_tiled(&mut self.state, offset_w, offset_h);
+        }
         if let Some(w) = self.state.windows.iter_mut().find(|w| &w.handle == handle) {
             process_window(w, offset_w, offset_h);
             return true;
@@ -26,3 +31,102 @@ fn process_window(window: &mut Window, offset_w: i32, offset_h: i32) {
     offset.set_h(start.h() + offset_h);
     window.set_floating_offsets(Some(offset));
 }
+
+/// Move the boundary between tiled windows being dragged, the windows stay tiled.
+fn process_tiled(state: &mut State, offset_w: i32, offset_h: i32) -> bool {
+    let Some(resize) = state.tiled_resize.as_mut() else {
+        return false;
+    };
+    let drag = resize.drag(offset_w, offset_h);
+    match resize {
+        TiledResize::MainSize {
+            workspace,
+            tag,
+            horizontal,
+            sign,
+            applied,
+        } => {
+            let Some(ws) = state.workspaces.iter().find(|ws| ws.id == *workspace) else {
+                return false;
+            };
+            let upper_bound = if *horizontal { ws.width() } else { ws.height() };
+            // The main size changes by steps, so only the rest of the drag is applied.
+            let delta = *sign * drag;
+            let def = state.layout_manager.layout_mut(*workspace, *tag);
+            def.change_main_size(delta - *applied, upper_bound);
+            *applied = delta;
+        }
+        TiledResize::Weights { tag, windows, .. } => {
+            let weights = dragged_weights(windows, drag);
+            for (stacked, weight) in windows.iter().zip(weights) {
+                if let Some(w) = state
+                    .windows
+                    .iter_mut()
+                    .find(|w| w.handle == stacked.handle)
+                {
+                    w.weights.insert(*tag, weight);
+                }
+            }
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::layouts::MAIN_AND_VERT_STACK;
+    use crate::models::{Screen, Window, WindowHandle};
+    use crate::utils::modmask_lookup::into_modmask;
+    use crate::{DisplayEvent, Manager};
+    use x11_dl::xlib;
+
+    #[test]
+    fn dragging_a_boundary_resizes_the_tiled_windows() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for i in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+        }
+        let ws_id = manager.state.workspaces[0].id;
+        manager
+            .state
+            .layout_manager
+            .set_layout(ws_id, 1, MAIN_AND_VERT_STACK);
+        manager.update_windows();
+        let modmask = into_modmask(&manager.state.mousekey);
+        let drag = |manager: &mut Manager<_, _>, index: usize, at_bottom: bool, offset| {
+            let window = &manager.state.windows[index];
+            let handle = window.handle;
+            // Right next to the right or the bottom edge of the window.
+            let (x, y) = if at_bottom {
+                (window.width() / 2, window.height() - 5)
+            } else {
+                (window.width() - 5, window.height() / 2)
+            };
+            manager
+                .state
+                .mouse_combo_handler(modmask, xlib::Button3, handle, x, y);
+            assert!(manager.state.tiled_resize.is_some());
+            let (offset_x, offset_y) = if at_bottom { (0, offset) } else { (offset, 0) };
+            manager.display_event_handler(DisplayEvent::ResizeWindow(handle, offset_x, offset_y));
+            manager.display_event_handler(DisplayEvent::ChangeToNormalMode);
+            manager.update_windows();
+        };
+
+        // The boundary between the main and the stack area.
+        let main_width = manager.state.windows[0].normal.w();
+        drag(&mut manager, 0, false, 100);
+        assert!(manager.state.windows[0].normal.w() > main_width);
+
+        // The boundary between the two windows of the stack.
+        drag(&mut manager, 1, true, 60);
+        let stack = &manager.state.windows[1..];
+        assert!(stack[0].normal.h() > stack[1].normal.h());
+        assert!(manager.state.windows.iter().all(|w| !w.floating()));
+        assert!(manager.state.tiled_resize.is_none());
+    }
+}
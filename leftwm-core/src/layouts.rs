@@ -1,10 +1,16 @@
 mod layout_manager;
 mod layout_mode;
+mod split_tree;
+mod validation;
+mod weights;
 
 use thiserror::Error;
 
 pub use layout_manager::LayoutManager;
 pub use layout_mode::LayoutMode;
+pub use split_tree::{SplitDirection, SplitTree};
+pub use validation::{validate, LayoutDefinitionError, CHECKED_WINDOWS};
+pub(crate) use weights::apply_weights;
 
 pub const DEFAULT: &str = "Default";
 pub const MONOCLE: &str = "Monocle";
@@ -21,6 +27,8 @@ pub const LEFT_MAIN: &str = "LeftMain";
 pub const CENTER_MAIN: &str = "CenterMain";
 pub const CENTER_MAIN_BALANCED: &str = "CenterMainBalanced";
 pub const CENTER_MAIN_FLUID: &str = "CenterMainFluid";
+/// Arranges the windows of a tag by hand, in a [`SplitTree`].
+pub const MANUAL: &str = "Manual";
 
 #[derive(Debug, Error)]
 #[error("Could not parse layout: {0}")]
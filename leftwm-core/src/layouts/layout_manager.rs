@@ -1,9 +1,10 @@
-use crate::{config::Config, utils::helpers::cycle_vec};
+use crate::config::{numbered_workspaces, Config};
+use crate::utils::helpers::cycle_vec;
 use leftwm_layouts::Layout;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use super::LayoutMode;
+use super::{LayoutMode, MANUAL};
 
 /// The [`LayoutManager`] holds the actual set of [`Layout`].
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -22,6 +23,16 @@ pub struct LayoutManager {
     /// global available layouts from [`available_layouts`] will be used instead.
     available_layouts_per_ws: HashMap<usize, Vec<Layout>>,
 
+    /// All the available layouts per tag, used in [`LayoutMode::Tag`]. A tag
+    /// without its own set of available layouts falls back to the ones of its
+    /// workspace, and then to the global [`available_layouts`].
+    #[serde(default)]
+    available_layouts_per_tag: HashMap<usize, Vec<Layout>>,
+
+    /// The name of the layout each tag starts with, used in [`LayoutMode::Tag`].
+    #[serde(default)]
+    default_layout_per_tag: HashMap<usize, String>,
+
     /// The actual, modifiable layouts grouped by either
     /// Workspace or Tag, depending on the configured [`LayoutMode`].
     layouts: HashMap<usize, Vec<Layout>>,
@@ -32,13 +43,18 @@ impl LayoutManager {
     pub fn new(config: &impl Config) -> Self {
         let mut available_layouts: Vec<Layout> = Vec::new();
 
+        let mut definitions = config.layout_definitions();
+        if !definitions.iter().any(|def| def.name == MANUAL) {
+            // Only its name is used, the windows are arranged by a `SplitTree` instead.
+            definitions.push(Layout {
+                name: MANUAL.to_string(),
+                ..Layout::default()
+            });
+        }
+
         tracing::trace!("Looking for layouts named: {:?}", config.layouts());
         for name in config.layouts() {
-            if let Some(def) = config
-                .layout_definitions()
-                .iter()
-                .find(|def| def.name == name)
-            {
+            if let Some(def) = definitions.iter().find(|def| def.name == name) {
                 available_layouts.push(def.clone());
             } else {
                 tracing::warn!("There is no Layout with the name {:?}", name);
@@ -47,12 +63,10 @@ impl LayoutManager {
 
         let mut available_layouts_per_ws: HashMap<usize, Vec<Layout>> = HashMap::new();
 
-        for (i, ws) in config.workspaces().unwrap_or_default().iter().enumerate() {
+        for (wsid, _, ws) in numbered_workspaces(config) {
             if let Some(ws_layout_names) = &ws.layouts {
-                let wsid = i + 1;
                 for ws_layout_name in ws_layout_names {
-                    if let Some(layout) = config
-                        .layout_definitions()
+                    if let Some(layout) = definitions
                         .iter()
                         .find(|layout| layout.name == *ws_layout_name)
                     {
@@ -67,6 +81,46 @@ impl LayoutManager {
             }
         }
 
+        let labels = config.create_list_of_tag_labels();
+        let mut available_layouts_per_tag: HashMap<usize, Vec<Layout>> = HashMap::new();
+        let mut default_layout_per_tag: HashMap<usize, String> = HashMap::new();
+        let tag_layouts = config.tag_layouts();
+        if !tag_layouts.is_empty() && config.layout_mode() != LayoutMode::Tag {
+            tracing::warn!("The layouts of tags are only used with the layout mode Tag");
+        }
+        for tag in tag_layouts {
+            let Some(index) = labels.iter().position(|label| *label == tag.tag) else {
+                tracing::warn!(
+                    "There is no tag with the label {:?}, but it has layouts configured",
+                    tag.tag
+                );
+                continue;
+            };
+            let tagid = index + 1;
+            let layouts: Vec<Layout> = tag
+                .layouts
+                .iter()
+                .flatten()
+                .filter_map(|name| {
+                    let layout = definitions.iter().find(|def| def.name == *name);
+                    if layout.is_none() {
+                        tracing::warn!(
+                            "There is no Layout with the name {:?}, but was configured on tag {:?}",
+                            name,
+                            tag.tag
+                        );
+                    }
+                    layout.cloned()
+                })
+                .collect();
+            if !layouts.is_empty() {
+                available_layouts_per_tag.insert(tagid, layouts);
+            }
+            if let Some(name) = tag.default_layout {
+                default_layout_per_tag.insert(tagid, name);
+            }
+        }
+
         if available_layouts.is_empty() {
             tracing::warn!(
                 "No Layouts were loaded from config - defaulting to a single default Layout"
@@ -84,28 +138,100 @@ impl LayoutManager {
             mode: config.layout_mode(),
             available_layouts,
             available_layouts_per_ws,
+            available_layouts_per_tag,
+            default_layout_per_tag,
             layouts: HashMap::new(),
         }
     }
 
-    pub fn restore(&mut self, old: &LayoutManager) {
+    /// Restore the layouts of an older [`LayoutManager`], as far as they are still available.
+    ///
+    /// Layouts are mapped by their name, one whose definition changed starts over from the new
+    /// definition. `workspace_of` gives the workspace a tag is shown on, if any. Returns a
+    /// description of each current layout which could not be restored.
+    pub fn restore(
+        &mut self,
+        old: &LayoutManager,
+        workspace_of: impl Fn(usize) -> Option<usize>,
+    ) -> Vec<String> {
         if self.mode != old.mode {
-            tracing::debug!("The LayoutMode has changed, layouts will not be restored");
-            return;
+            return vec!["the layouts, as the layout mode changed".to_string()];
+        }
+        let mut lost = vec![];
+        for (id, old_layouts) in &old.layouts {
+            let Some(current) = old_layouts.first() else {
+                continue;
+            };
+            let old_definitions = old.available_for(*id, &workspace_of);
+            let mut layouts = self.available_for(*id, &workspace_of).to_vec();
+            for layout in &mut layouts {
+                if !old_definitions.contains(layout) {
+                    continue;
+                }
+                if let Some(old_layout) = old_layouts.iter().find(|l| l.name == layout.name) {
+                    *layout = old_layout.clone();
+                }
+            }
+            match layouts.iter().position(|l| l.name == current.name) {
+                Some(index) => {
+                    cycle_vec(&mut layouts, -(index as i32));
+                }
+                None => {
+                    let context = match self.mode {
+                        LayoutMode::Tag => "tag",
+                        LayoutMode::Workspace => "workspace",
+                    };
+                    lost.push(format!("the layout {} of {context} {id}", current.name));
+                }
+            }
+            self.layouts.insert(*id, layouts);
         }
-        // TODO we could eventually try to map available layouts as best as we can
-        //      and only fallback to default for layouts not avialable anymore
-        if self.available_layouts != old.available_layouts {
-            tracing::debug!("The available Layouts have changed, layouts will not be restored");
-            return;
+        lost.sort();
+        lost
+    }
+
+    /// Get the [`Layout`]s available for the given ID, the one of a tag or a workspace based
+    /// on the current [`LayoutMode`]. `workspace_of` gives the workspace a tag is shown on.
+    fn available_for(
+        &self,
+        id: usize,
+        workspace_of: &impl Fn(usize) -> Option<usize>,
+    ) -> &[Layout] {
+        match self.mode {
+            LayoutMode::Tag => self.lookup(workspace_of(id), Some(id)),
+            LayoutMode::Workspace => self.lookup(Some(id), None),
         }
-        if self.available_layouts_per_ws != old.available_layouts_per_ws {
-            tracing::debug!(
-                "The available Layouts per Workspace have changed, layouts will not be restored"
-            );
-            return;
+    }
+
+    /// Get the [`Layout`]s that can be chosen from on the provided workspace / tag context
+    pub fn available_layouts(&self, wsid: usize, tagid: usize) -> &[Layout] {
+        self.lookup(Some(wsid), Some(tagid))
+    }
+
+    /// Look up the [`Layout`]s of a workspace / tag context, either of which may be unknown.
+    ///
+    /// These are the ones of the tag in [`LayoutMode::Tag`], else the ones of the workspace,
+    /// else the global ones.
+    fn lookup(&self, wsid: Option<usize>, tagid: Option<usize>) -> &[Layout] {
+        let per_tag = match self.mode {
+            LayoutMode::Tag => tagid.and_then(|id| self.available_layouts_per_tag.get(&id)),
+            LayoutMode::Workspace => None,
+        };
+        per_tag
+            .or_else(|| wsid.and_then(|id| self.available_layouts_per_ws.get(&id)))
+            .unwrap_or(&self.available_layouts)
+    }
+
+    /// Re-key the layouts after the IDs of the tags changed.
+    ///
+    /// `new_id` maps an old tag ID to its new one, or to `None` if the tag was removed.
+    /// The current layouts are only re-keyed if they are grouped by tag.
+    pub fn remap_tags(&mut self, new_id: impl Fn(usize) -> Option<usize>) {
+        remap(&mut self.available_layouts_per_tag, &new_id);
+        remap(&mut self.default_layout_per_tag, &new_id);
+        if self.mode == LayoutMode::Tag {
+            remap(&mut self.layouts, &new_id);
         }
-        self.layouts = old.layouts.clone();
     }
 
     /// Get back either the workspace ID or the tag ID, based on the current [`LayoutMode`]
@@ -120,7 +246,7 @@ impl LayoutManager {
     ///
     /// If the layouts for the specific workspace / tag have not
     /// yet been set up, they will be initialized by copying
-    /// from the [`Self::available_layouts`] or [`Self::available_layouts_per_ws`].
+    /// from [`Self::available_layouts`], starting with the default layout of the tag.
     fn layouts(&mut self, wsid: usize, tagid: usize) -> &Vec<Layout> {
         self.layouts_mut(wsid, tagid)
     }
@@ -129,17 +255,23 @@ impl LayoutManager {
     ///
     /// If the layouts for the specific workspace / tag have not
     /// yet been set up, they will be initialized by copying
-    /// from the [`Self::available_layouts`] or [`Self::available_layouts_per_ws`].
+    /// from [`Self::available_layouts`], starting with the default layout of the tag.
     fn layouts_mut(&mut self, wsid: usize, tagid: usize) -> &mut Vec<Layout> {
         let id = self.id(wsid, tagid);
-        self.layouts.entry(id).or_insert_with(|| match &self.mode {
-            LayoutMode::Tag => self.available_layouts.clone(),
-            LayoutMode::Workspace => self
-                .available_layouts_per_ws
-                .get(&wsid)
-                .unwrap_or(&self.available_layouts)
-                .clone(),
-        })
+        if !self.layouts.contains_key(&id) {
+            let mut layouts = self.lookup(Some(wsid), Some(tagid)).to_vec();
+            let default = match self.mode {
+                LayoutMode::Tag => self.default_layout_per_tag.get(&tagid),
+                LayoutMode::Workspace => None,
+            };
+            if let Some(index) =
+                default.and_then(|name| layouts.iter().position(|l| l.name == *name))
+            {
+                cycle_vec(&mut layouts, -(index as i32));
+            }
+            self.layouts.insert(id, layouts);
+        }
+        self.layouts.entry(id).or_default()
     }
 
     /// Get the current [`Layout`] for the provided workspace / tag context
@@ -206,13 +338,23 @@ impl LayoutManager {
     // todo - low priority: reset fn, that resets all the layouts to their unchanged properties
 }
 
+/// Re-key a map by tag ID, dropping the entries of removed tags.
+fn remap<T>(map: &mut HashMap<usize, T>, new_id: &impl Fn(usize) -> Option<usize>) {
+    *map = map
+        .drain()
+        .filter_map(|(tagid, value)| new_id(tagid).map(|id| (id, value)))
+        .collect();
+}
+
 #[cfg(test)]
 mod tests {
     use leftwm_layouts::layouts::Layouts;
 
     use crate::{
-        config::tests::TestConfig,
-        layouts::{self, EVEN_VERTICAL, MONOCLE},
+        config::{tests::TestConfig, TagLayouts},
+        layouts::{
+            self, LayoutMode, CENTER_MAIN, EVEN_VERTICAL, MAIN_AND_HORIZONTAL_STACK, MONOCLE,
+        },
     };
 
     use super::LayoutManager;
@@ -254,6 +396,89 @@ mod tests {
         assert_eq!(1, layout_manager.id(1, 2));
     }
 
+    #[test]
+    fn layouts_are_restored_by_name() {
+        let mut old = layout_manager();
+        old.set_layout(2, 1, EVEN_VERTICAL);
+        old.layout_mut(2, 1).rotate(true);
+        let rotated = old.layout(2, 1).clone();
+        old.set_layout(3, 1, MONOCLE);
+
+        let mut new = LayoutManager::new(&TestConfig {
+            layouts: vec![CENTER_MAIN.to_string(), EVEN_VERTICAL.to_string()],
+            layout_definitions: Layouts::default().layouts,
+            ..Default::default()
+        });
+        let lost = new.restore(&old, |_| None);
+
+        assert_eq!(&rotated, new.layout(2, 1));
+        assert_eq!(CENTER_MAIN, &new.layout(3, 1).name);
+        assert_eq!(lost, vec![format!("the layout {MONOCLE} of workspace 3")]);
+    }
+
+    #[test]
+    fn tags_cycle_through_their_own_layouts() {
+        let mut layout_manager = LayoutManager::new(&TestConfig {
+            tags: vec!["web".to_string(), "code".to_string(), "misc".to_string()],
+            layouts: vec![
+                EVEN_VERTICAL.to_string(),
+                MAIN_AND_HORIZONTAL_STACK.to_string(),
+            ],
+            layout_definitions: Layouts::default().layouts,
+            layout_mode: Some(LayoutMode::Tag),
+            tag_layouts: vec![
+                TagLayouts {
+                    tag: "web".to_string(),
+                    layouts: Some(vec![MONOCLE.to_string()]),
+                    ..Default::default()
+                },
+                TagLayouts {
+                    tag: "code".to_string(),
+                    default_layout: Some(MAIN_AND_HORIZONTAL_STACK.to_string()),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        });
+
+        layout_manager.cycle_next_layout(1, 1);
+        assert_eq!(MONOCLE, &layout_manager.layout(1, 1).name);
+        assert_eq!(MAIN_AND_HORIZONTAL_STACK, &layout_manager.layout(1, 2).name);
+        layout_manager.cycle_next_layout(1, 2);
+        assert_eq!(EVEN_VERTICAL, &layout_manager.layout(1, 2).name);
+        assert_eq!(EVEN_VERTICAL, &layout_manager.layout(1, 3).name);
+    }
+
+    #[test]
+    fn tags_without_own_layouts_are_restored_from_their_workspace() {
+        let config = |layouts: Vec<&str>| TestConfig {
+            tags: vec!["web".to_string(), "code".to_string()],
+            layouts: vec![MONOCLE.to_string()],
+            layout_definitions: Layouts::default().layouts,
+            layout_mode: Some(LayoutMode::Tag),
+            workspaces: Some(vec![crate::config::Workspace {
+                layouts: Some(layouts.into_iter().map(str::to_string).collect()),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+        let mut old = LayoutManager::new(&config(vec![CENTER_MAIN, EVEN_VERTICAL]));
+        old.set_layout(1, 2, EVEN_VERTICAL);
+        old.layout_mut(1, 2).rotate(true);
+        let rotated = old.layout(1, 2).clone();
+
+        let mut new = LayoutManager::new(&config(vec![EVEN_VERTICAL, CENTER_MAIN]));
+        let lost = new.restore(&old, |tag| (tag == 2).then_some(1));
+
+        assert!(lost.is_empty());
+        assert_eq!(&rotated, new.layout(1, 2));
+        assert_eq!(
+            new.available_layouts(1, 2),
+            new.lookup(Some(1), None),
+            "the tag keeps the layouts of its workspace"
+        );
+    }
+
     #[test]
     fn monocle_layout_only_has_single_windows() {
         let mut layout_manager = layout_manager();
@@ -0,0 +1,434 @@
+//! The windows of a tag arranged by hand, as a binary tree of splits.
+
+use crate::models::{Side, WindowHandle, Xyhw};
+use leftwm_layouts::geometry::Rect;
+use serde::{Deserialize, Serialize};
+
+/// The smallest share of a split either of its halves can be resized to.
+const MIN_RATIO: f32 = 0.1;
+
+/// How a split divides its area between its two halves.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SplitDirection {
+    /// The halves are side by side.
+    Horizontal,
+    /// The halves are above each other.
+    Vertical,
+}
+
+impl SplitDirection {
+    fn of(side: &Side) -> Self {
+        match side {
+            Side::Left | Side::Right => Self::Horizontal,
+            Side::Top | Side::Bottom => Self::Vertical,
+        }
+    }
+
+    /// Split along the longer side of the area.
+    fn longer(area: &Rect) -> Self {
+        if area.w >= area.h {
+            Self::Horizontal
+        } else {
+            Self::Vertical
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+enum Node {
+    Window(WindowHandle),
+    Split {
+        direction: SplitDirection,
+        /// The share of the area taken by `first`.
+        ratio: f32,
+        first: Box<Node>,
+        second: Box<Node>,
+    },
+}
+
+impl Node {
+    fn contains(&self, handle: WindowHandle) -> bool {
+        match self {
+            Node::Window(window) => *window == handle,
+            Node::Split { first, second, .. } => first.contains(handle) || second.contains(handle),
+        }
+    }
+
+    fn windows(&self, windows: &mut Vec<WindowHandle>) {
+        match self {
+            Node::Window(handle) => windows.push(*handle),
+            Node::Split { first, second, .. } => {
+                first.windows(windows);
+                second.windows(windows);
+            }
+        }
+    }
+
+    fn rects(&self, area: Rect, rects: &mut Vec<(WindowHandle, Rect)>) {
+        match self {
+            Node::Window(handle) => rects.push((*handle, area)),
+            Node::Split {
+                direction,
+                ratio,
+                first,
+                second,
+            } => {
+                let (first_area, second_area) = divide(area, *direction, *ratio);
+                first.rects(first_area, rects);
+                second.rects(second_area, rects);
+            }
+        }
+    }
+
+    /// Drop the windows not to be kept, a split left with a single half is replaced by it.
+    fn retain(self, keep: &impl Fn(WindowHandle) -> bool) -> Option<Node> {
+        match self {
+            Node::Window(handle) => keep(handle).then_some(self),
+            Node::Split {
+                direction,
+                ratio,
+                first,
+                second,
+            } => match (first.retain(keep), second.retain(keep)) {
+                (Some(first), Some(second)) => Some(Node::Split {
+                    direction,
+                    ratio,
+                    first: Box::new(first),
+                    second: Box::new(second),
+                }),
+                (first, second) => first.or(second),
+            },
+        }
+    }
+
+    /// Replace the `target` window by a split of it and the `new` one, `new` being the first
+    /// half if `before`.
+    fn split(
+        &mut self,
+        target: WindowHandle,
+        new: WindowHandle,
+        direction: SplitDirection,
+        before: bool,
+    ) -> bool {
+        match self {
+            Node::Window(handle) if *handle == target => {
+                let (first, second) = if before { (new, target) } else { (target, new) };
+                *self = Node::Split {
+                    direction,
+                    ratio: 0.5,
+                    first: Box::new(Node::Window(first)),
+                    second: Box::new(Node::Window(second)),
+                };
+                true
+            }
+            Node::Window(_) => false,
+            Node::Split { first, second, .. } => {
+                first.split(target, new, direction, before)
+                    || second.split(target, new, direction, before)
+            }
+        }
+    }
+
+    /// Grow the window by `delta` within the split it is a direct half of.
+    fn resize(&mut self, handle: WindowHandle, delta: f32) -> bool {
+        let Node::Split {
+            ratio,
+            first,
+            second,
+            ..
+        } = self
+        else {
+            return false;
+        };
+        if **first == Node::Window(handle) {
+            *ratio += delta;
+        } else if **second == Node::Window(handle) {
+            *ratio -= delta;
+        } else {
+            return first.resize(handle, delta) || second.resize(handle, delta);
+        }
+        *ratio = ratio.clamp(MIN_RATIO, 1.0 - MIN_RATIO);
+        true
+    }
+
+    fn are_siblings(&self, a: WindowHandle, b: WindowHandle) -> bool {
+        let Node::Split { first, second, .. } = self else {
+            return false;
+        };
+        let pair = (first.as_ref(), second.as_ref());
+        pair == (&Node::Window(a), &Node::Window(b))
+            || pair == (&Node::Window(b), &Node::Window(a))
+            || first.are_siblings(a, b)
+            || second.are_siblings(a, b)
+    }
+
+    /// Exchange the places of two windows.
+    fn swap(&mut self, a: WindowHandle, b: WindowHandle) {
+        match self {
+            Node::Window(handle) if *handle == a => *handle = b,
+            Node::Window(handle) if *handle == b => *handle = a,
+            Node::Window(_) => {}
+            Node::Split { first, second, .. } => {
+                first.swap(a, b);
+                second.swap(a, b);
+            }
+        }
+    }
+}
+
+/// The windows of a tag, arranged in a binary tree of splits by the [`super::MANUAL`] layout.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct SplitTree {
+    root: Option<Node>,
+    /// New windows are split off this one, the window focused when the tree was last arranged.
+    focused: Option<WindowHandle>,
+    /// The window the next window is split off instead, and the direction of that split.
+    next_split: Option<(WindowHandle, SplitDirection)>,
+}
+
+impl SplitTree {
+    /// The windows in the tree, from the top left to the bottom right.
+    #[must_use]
+    pub fn windows(&self) -> Vec<WindowHandle> {
+        let mut windows = vec![];
+        if let Some(root) = &self.root {
+            root.windows(&mut windows);
+        }
+        windows
+    }
+
+    /// Split the next window off the given one, in the given direction.
+    pub fn split(&mut self, handle: WindowHandle, direction: SplitDirection) {
+        self.next_split = Some((handle, direction));
+    }
+
+    /// Bring the tree in line with the windows to arrange, and get the area of each of them in
+    /// their order. Windows not in the tree yet are split off the focused one.
+    pub fn arrange(
+        &mut self,
+        windows: &[WindowHandle],
+        focused: Option<WindowHandle>,
+        area: Rect,
+    ) -> Vec<Rect> {
+        self.root = self
+            .root
+            .take()
+            .and_then(|root| root.retain(&|handle| windows.contains(&handle)));
+        for handle in windows {
+            self.insert(*handle, area);
+        }
+        if let Some(focused) = focused.filter(|handle| self.contains(*handle)) {
+            self.focused = Some(focused);
+        }
+        let rects = self.rects(area);
+        windows
+            .iter()
+            .filter_map(|handle| rects.iter().find(|(h, _)| h == handle))
+            .map(|(_, rect)| *rect)
+            .collect()
+    }
+
+    /// Grow the window by `delta`, a share of the split it is a half of.
+    pub fn resize(&mut self, handle: WindowHandle, delta: f32) -> bool {
+        self.root
+            .as_mut()
+            .map_or(false, |root| root.resize(handle, delta))
+    }
+
+    /// Move the window towards the given side, next to the closest window on that side. Without
+    /// a window on that side, it is moved to that side of the whole tree.
+    pub fn move_window(&mut self, handle: WindowHandle, side: &Side, area: Rect) -> bool {
+        let rects = self.rects(area);
+        let Some((_, from)) = rects.iter().find(|(h, _)| *h == handle) else {
+            return false;
+        };
+        if rects.len() < 2 {
+            return false;
+        }
+        let Some(mut root) = self.root.take() else {
+            return false;
+        };
+        let direction = SplitDirection::of(side);
+        let towards_start = matches!(side, Side::Left | Side::Top);
+        let neighbour = rects
+            .iter()
+            .filter(|(h, _)| *h != handle)
+            .filter_map(|(h, rect)| {
+                let distance = Xyhw::from(*from).distance_towards(&Xyhw::from(*rect), side);
+                distance.map(|d| (d, *h))
+            })
+            .min_by_key(|(d, _)| *d)
+            .map(|(_, h)| h);
+
+        match neighbour {
+            Some(neighbour) if root.are_siblings(handle, neighbour) => root.swap(handle, neighbour),
+            Some(neighbour) => {
+                if let Some(mut rest) = root.retain(&|h| h != handle) {
+                    rest.split(neighbour, handle, direction, !towards_start);
+                    root = rest;
+                }
+            }
+            None => {
+                if let Some(rest) = root.retain(&|h| h != handle) {
+                    let (first, second) = if towards_start {
+                        (Node::Window(handle), rest)
+                    } else {
+                        (rest, Node::Window(handle))
+                    };
+                    root = Node::Split {
+                        direction,
+                        ratio: 0.5,
+                        first: Box::new(first),
+                        second: Box::new(second),
+                    };
+                }
+            }
+        }
+        self.root = Some(root);
+        true
+    }
+
+    /// Exchange the places of two windows.
+    pub fn swap(&mut self, a: WindowHandle, b: WindowHandle) {
+        if let Some(root) = &mut self.root {
+            root.swap(a, b);
+        }
+    }
+
+    fn contains(&self, handle: WindowHandle) -> bool {
+        self.root
+            .as_ref()
+            .map_or(false, |root| root.contains(handle))
+    }
+
+    fn rects(&self, area: Rect) -> Vec<(WindowHandle, Rect)> {
+        let mut rects = vec![];
+        if let Some(root) = &self.root {
+            root.rects(area, &mut rects);
+        }
+        rects
+    }
+
+    fn insert(&mut self, handle: WindowHandle, area: Rect) {
+        if self.contains(handle) {
+            return;
+        }
+        let rects = self.rects(area);
+        let target = self
+            .next_split
+            .take()
+            .filter(|(target, _)| rects.iter().any(|(h, _)| h == target))
+            .or_else(|| {
+                let (target, rect) = rects
+                    .iter()
+                    .find(|(h, _)| Some(*h) == self.focused)
+                    .or_else(|| rects.last())?;
+                Some((*target, SplitDirection::longer(rect)))
+            });
+        let Some((target, direction)) = target else {
+            self.root = Some(Node::Window(handle));
+            return;
+        };
+        if let Some(root) = &mut self.root {
+            root.split(target, handle, direction, false);
+        }
+    }
+}
+
+fn divide(area: Rect, direction: SplitDirection, ratio: f32) -> (Rect, Rect) {
+    match direction {
+        SplitDirection::Horizontal => {
+            let w = (area.w as f32 * ratio).round() as u32;
+            let second = Rect {
+                x: area.x + w as i32,
+                w: area.w - w,
+                ..area
+            };
+            (Rect { w, ..area }, second)
+        }
+        SplitDirection::Vertical => {
+            let h = (area.h as f32 * ratio).round() as u32;
+            let second = Rect {
+                y: area.y + h as i32,
+                h: area.h - h,
+                ..area
+            };
+            (Rect { h, ..area }, second)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AREA: Rect = Rect {
+        x: 0,
+        y: 0,
+        w: 800,
+        h: 600,
+    };
+
+    fn handles(ids: &[i32]) -> Vec<WindowHandle> {
+        ids.iter().map(|id| WindowHandle::MockHandle(*id)).collect()
+    }
+
+    #[test]
+    fn windows_are_split_off_the_focused_one() {
+        let mut tree = SplitTree::default();
+        let windows = handles(&[1, 2]);
+        tree.arrange(&windows, Some(windows[0]), AREA);
+        tree.split(windows[0], SplitDirection::Vertical);
+        let windows = handles(&[1, 2, 3]);
+        let rects = tree.arrange(&windows, Some(windows[0]), AREA);
+
+        assert_eq!(
+            rects[0],
+            Rect {
+                h: 300,
+                w: 400,
+                ..AREA
+            }
+        );
+        assert_eq!(
+            rects[1],
+            Rect {
+                x: 400,
+                w: 400,
+                ..AREA
+            }
+        );
+        assert_eq!(
+            rects[2],
+            Rect {
+                y: 300,
+                h: 300,
+                w: 400,
+                ..AREA
+            }
+        );
+        assert_eq!(tree.windows(), handles(&[1, 3, 2]));
+    }
+
+    #[test]
+    fn windows_are_moved_next_to_their_neighbour() {
+        let mut tree = SplitTree::default();
+        let windows = handles(&[1, 2]);
+        tree.arrange(&windows, Some(windows[1]), AREA);
+        tree.split(windows[1], SplitDirection::Vertical);
+        tree.arrange(&handles(&[1, 2, 3]), None, AREA);
+
+        assert!(tree.move_window(WindowHandle::MockHandle(3), &Side::Left, AREA));
+        assert_eq!(tree.windows(), handles(&[1, 3, 2]));
+        assert!(tree.resize(WindowHandle::MockHandle(2), 0.2));
+        let rects = tree.arrange(&handles(&[1, 2, 3]), None, AREA);
+        assert_eq!(
+            rects[1],
+            Rect {
+                x: 240,
+                w: 560,
+                ..AREA
+            }
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Checking layout definitions before they are used to arrange windows.
+
+use leftwm_layouts::geometry::{Rect, Size};
+use leftwm_layouts::Layout;
+use thiserror::Error;
+
+/// How many windows a definition is arranged with when it is checked without a given number.
+pub const CHECKED_WINDOWS: usize = 10;
+
+/// A problem with a layout definition.
+#[derive(Debug, Error, PartialEq)]
+pub enum LayoutDefinitionError {
+    #[error("the layout has no name")]
+    MissingName,
+    #[error("the main size {0} is not a ratio between 0 and 1")]
+    MainRatio(f32),
+    #[error("the main size {0}px is negative")]
+    MainPixels(i32),
+    #[error("with {windows} windows, window {index} is placed outside of the area")]
+    OutsideArea { windows: usize, index: usize },
+    #[error("with {windows} windows, window {index} has no size")]
+    Empty { windows: usize, index: usize },
+}
+
+/// Check the settings of a definition, and that it arranges any number of windows up to
+/// `windows` inside `area`. Returns the rects of `windows` windows.
+///
+/// # Errors
+///
+/// Will error with the first problem found with the definition.
+pub fn validate(
+    def: &Layout,
+    windows: usize,
+    area: &Rect,
+) -> Result<Vec<Rect>, LayoutDefinitionError> {
+    if def.name.trim().is_empty() {
+        return Err(LayoutDefinitionError::MissingName);
+    }
+    match def.columns.main.as_ref().map(|main| &main.size) {
+        Some(&Size::Ratio(ratio)) if !(0.0..=1.0).contains(&ratio) => {
+            return Err(LayoutDefinitionError::MainRatio(ratio));
+        }
+        Some(&Size::Pixel(pixels)) if pixels < 0 => {
+            return Err(LayoutDefinitionError::MainPixels(pixels));
+        }
+        _ => {}
+    }
+
+    let mut rects = vec![];
+    for count in 1..=windows {
+        rects = leftwm_layouts::apply(def, count, area);
+        for (index, rect) in rects.iter().enumerate() {
+            let inside = rect.x >= area.x
+                && rect.y >= area.y
+                && i64::from(rect.x) + i64::from(rect.w) <= i64::from(area.x) + i64::from(area.w)
+                && i64::from(rect.y) + i64::from(rect.h) <= i64::from(area.y) + i64::from(area.h);
+            if !inside {
+                return Err(LayoutDefinitionError::OutsideArea {
+                    windows: count,
+                    index: index + 1,
+                });
+            }
+            if rect.w == 0 || rect.h == 0 {
+                return Err(LayoutDefinitionError::Empty {
+                    windows: count,
+                    index: index + 1,
+                });
+            }
+        }
+    }
+    Ok(rects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layouts::MAIN_AND_VERT_STACK;
+    use leftwm_layouts::layouts::Layouts;
+
+    #[test]
+    fn the_default_layouts_are_valid_and_a_bad_main_size_is_not() {
+        let area = Rect {
+            x: 0,
+            y: 0,
+            w: 1920,
+            h: 1080,
+        };
+        for def in Layouts::default().layouts {
+            assert!(
+                validate(&def, CHECKED_WINDOWS, &area).is_ok(),
+                "{}",
+                def.name
+            );
+        }
+
+        let mut def = Layouts::default()
+            .layouts
+            .into_iter()
+            .find(|def| def.name == MAIN_AND_VERT_STACK)
+            .unwrap();
+        if let Some(main) = &mut def.columns.main {
+            main.size = Size::Ratio(1.5);
+        }
+        assert_eq!(
+            validate(&def, CHECKED_WINDOWS, &area),
+            Err(LayoutDefinitionError::MainRatio(1.5))
+        );
+        def.name = String::new();
+        assert_eq!(
+            validate(&def, CHECKED_WINDOWS, &area),
+            Err(LayoutDefinitionError::MissingName)
+        );
+    }
+}
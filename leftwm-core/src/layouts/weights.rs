@@ -0,0 +1,93 @@
+//! Sharing the space of a stack between its windows by their weights.
+
+use leftwm_layouts::geometry::Rect;
+
+#[derive(Clone, Copy)]
+enum Axis {
+    X,
+    Y,
+}
+
+/// The start and the length of the rect along the axis.
+fn span(rect: &Rect, axis: Axis) -> (i32, u32) {
+    match axis {
+        Axis::X => (rect.x, rect.w),
+        Axis::Y => (rect.y, rect.h),
+    }
+}
+
+fn set_span(rect: &mut Rect, axis: Axis, start: i32, len: u32) {
+    match axis {
+        Axis::X => (rect.x, rect.w) = (start, len),
+        Axis::Y => (rect.y, rect.h) = (start, len),
+    }
+}
+
+/// Share the space of the windows stacked in a column, or lined up in a row, by their weights.
+///
+/// `weights` are in the order of `rects`, a missing weight counts as 1.
+pub(crate) fn apply_weights(rects: &mut [Rect], weights: &[f32]) {
+    let weight = |i: usize| weights.get(i).copied().unwrap_or(1.0);
+    // Columns first, the windows of a column no longer line up in rows once resized.
+    for (along, across) in [(Axis::Y, Axis::X), (Axis::X, Axis::Y)] {
+        let mut seen = vec![false; rects.len()];
+        for i in 0..rects.len() {
+            if seen[i] {
+                continue;
+            }
+            let mut stack: Vec<usize> = (i..rects.len())
+                .filter(|&j| span(&rects[j], across) == span(&rects[i], across))
+                .collect();
+            stack.iter().for_each(|&j| seen[j] = true);
+            if stack
+                .iter()
+                .all(|&j| (weight(j) - weight(i)).abs() < f32::EPSILON)
+            {
+                continue;
+            }
+            stack.sort_by_key(|&j| span(&rects[j], along).0);
+            let adjacent = stack.windows(2).all(|pair| {
+                let (start, len) = span(&rects[pair[0]], along);
+                start + len as i32 == span(&rects[pair[1]], along).0
+            });
+            if !adjacent {
+                continue;
+            }
+
+            let start = span(&rects[stack[0]], along).0;
+            let total: u32 = stack.iter().map(|&j| span(&rects[j], along).1).sum();
+            let total_weight: f32 = stack.iter().map(|&j| weight(j)).sum();
+            let mut offset = 0;
+            for (n, &j) in stack.iter().enumerate() {
+                let len = if n + 1 == stack.len() {
+                    total - offset
+                } else {
+                    ((total as f32 * weight(j) / total_weight).round() as u32).min(total - offset)
+                };
+                set_span(&mut rects[j], along, start + offset as i32, len);
+                offset += len;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_heavier_window_takes_more_of_its_stack() {
+        let rect = |x, y, w, h| Rect { x, y, w, h };
+        // A main window and a stack of two on the right.
+        let mut rects = vec![
+            rect(0, 0, 400, 600),
+            rect(400, 0, 400, 300),
+            rect(400, 300, 400, 300),
+        ];
+        apply_weights(&mut rects, &[1.0, 2.0]);
+
+        assert_eq!(rects[0], rect(0, 0, 400, 600));
+        assert_eq!(rects[1], rect(400, 0, 400, 400));
+        assert_eq!(rects[2], rect(400, 400, 400, 200));
+    }
+}
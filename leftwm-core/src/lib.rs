@@ -24,13 +24,15 @@ mod event_loop;
 mod handlers;
 pub mod layouts;
 pub mod models;
+mod query;
 pub mod state;
+mod state_event;
 pub mod utils;
 
 use utils::modmask_lookup::Button;
 use utils::modmask_lookup::ModMask;
 
-pub use command::{Command, ReleaseScratchPadOption};
+pub use command::{Command, CommandError, ReleaseScratchPadOption};
 pub use config::Config;
 pub use display_action::DisplayAction;
 pub use display_event::DisplayEvent;
@@ -39,8 +41,12 @@ pub use models::Manager;
 pub use models::Mode;
 pub use models::Window;
 pub use models::Workspace;
+pub use query::Query;
 pub use state::State;
+pub use state_event::{StateEvent, StateEventKind};
 pub use utils::child_process;
 pub use utils::command_pipe::CommandPipe;
+pub use utils::command_socket::CommandSocket;
+pub use utils::event_socket::EventSocket;
 pub use utils::return_pipe::ReturnPipe;
 pub use utils::state_socket::StateSocket;
@@ -7,10 +7,13 @@ mod margins;
 mod mode;
 mod scratchpad;
 mod screen;
+mod session;
 mod size;
 mod tag;
+mod tiled_resize;
 mod window;
 mod window_change;
+mod window_overrides;
 mod window_state;
 mod window_type;
 mod workspace;
@@ -27,15 +30,19 @@ pub use gutter::Side;
 pub use manager::Manager;
 pub use margins::Margins;
 pub use mode::Mode;
-pub use scratchpad::{ScratchPad, ScratchPadName};
+pub use scratchpad::{ScratchPad, ScratchPadAnchor, ScratchPadBinding, ScratchPadName};
 pub use screen::{BBox, Screen};
+pub use session::{RestoredSession, Session, SessionWindow, LAST_SESSION};
 pub use size::Size;
+pub(crate) use tiled_resize::dragged_weights;
+pub use tiled_resize::{StackedWindow, TiledResize};
 pub use window::Window;
 pub use window::WindowHandle;
 pub use window_change::WindowChange;
+pub use window_overrides::{BorderColors, FloatingGeometry, WindowOverrides};
 pub use window_state::WindowState;
 pub use window_type::WindowType;
-pub use workspace::Workspace;
+pub use workspace::{DetachedWorkspace, Workspace};
 pub use xyhw::Xyhw;
 pub use xyhw::XyhwBuilder;
 pub use xyhw_change::XyhwChange;
@@ -1,3 +1,4 @@
+use crate::models::{TagId, Window, WindowHandle, Workspace};
 use crate::state::State;
 use serde::{Deserialize, Serialize};
 
@@ -6,6 +7,9 @@ pub struct Viewport {
     pub id: usize,
     pub output: String,
     pub tag: String,
+    /// Labels of the tags toggled into view besides `tag`.
+    #[serde(default)]
+    pub extra_tags: Vec<String>,
     pub h: u32,
     pub w: u32,
     pub x: i32,
@@ -13,6 +17,169 @@ pub struct Viewport {
     pub layout: String,
 }
 
+impl Viewport {
+    pub(crate) fn new(state: &State, ws: &Workspace) -> Self {
+        // A workspace without a tag is reported with an empty label rather than crashing a query.
+        let tag_label = ws
+            .tag
+            .and_then(|tag_id| state.tags.get(tag_id))
+            .map(|tag| tag.label.clone())
+            .unwrap_or_default();
+
+        let extra_tags = ws
+            .extra_tags
+            .iter()
+            .filter_map(|tag_id| state.tags.get(*tag_id).map(|tag| tag.label.clone()))
+            .collect();
+
+        let layout_name: String = ws
+            .tag
+            .and_then(|tagid| state.layout_manager.layout_maybe(ws.id, tagid))
+            .map_or_else(|| String::from("N/A"), |layout| layout.name.clone());
+
+        let output = state
+            .screens
+            .iter()
+            .find(|s| s.id == Some(ws.id))
+            .map_or_else(
+                || String::from("Not found (unreachable)"),
+                |s| s.output.clone(),
+            );
+
+        Self {
+            id: ws.id,
+            output,
+            tag: tag_label,
+            extra_tags,
+            x: ws.xyhw.x(),
+            y: ws.xyhw.y(),
+            h: ws.xyhw.h() as u32,
+            w: ws.xyhw.w() as u32,
+            layout: layout_name,
+        }
+    }
+}
+
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WindowInfo {
+    pub handle: WindowHandle,
+    pub title: Option<String>,
+    pub res_class: Option<String>,
+    pub res_name: Option<String>,
+    pub pid: Option<u32>,
+    pub tag: Option<TagId>,
+    #[serde(default)]
+    pub extra_tags: Vec<TagId>,
+    /// Label of `tag`, which stays the same when the tags are reordered.
+    #[serde(default)]
+    pub tag_label: Option<String>,
+    /// Labels of `extra_tags`.
+    #[serde(default)]
+    pub extra_tag_labels: Vec<String>,
+    pub floating: bool,
+    pub fullscreen: bool,
+    pub sticky: bool,
+    pub urgent: bool,
+    pub focused: bool,
+    pub h: u32,
+    pub w: u32,
+    pub x: i32,
+    pub y: i32,
+}
+
+impl WindowInfo {
+    pub(crate) fn new(state: &State, window: &Window, focused: bool) -> Self {
+        let label = |tag: TagId| state.tags.get(tag).map(|tag| tag.label.clone());
+        Self {
+            handle: window.handle,
+            title: window.name.clone(),
+            res_class: window.res_class.clone(),
+            res_name: window.res_name.clone(),
+            pid: window.pid,
+            tag: window.tag,
+            extra_tags: window.extra_tags.clone(),
+            tag_label: window.tag.and_then(label),
+            extra_tag_labels: window
+                .extra_tags
+                .iter()
+                .copied()
+                .filter_map(label)
+                .collect(),
+            floating: window.floating(),
+            fullscreen: window.is_fullscreen(),
+            sticky: window.is_sticky(),
+            urgent: window.urgent,
+            focused,
+            h: window.height() as u32,
+            w: window.width() as u32,
+            x: window.x(),
+            y: window.y(),
+        }
+    }
+}
+
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TagInfo {
+    pub id: TagId,
+    pub label: String,
+    pub visible: bool,
+    pub focused: bool,
+    pub urgent: bool,
+    pub busy: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScratchPadInfo {
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub group: Option<String>,
+    pub visible: bool,
+    pub windows: Vec<WindowHandle>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScratchPadGroupInfo {
+    pub name: String,
+    pub members: Vec<String>,
+    /// The member shown last, which is shown again when the group is cycled.
+    pub current: Option<String>,
+    pub visible: bool,
+}
+
+impl ScratchPadGroupInfo {
+    /// The groups of the scratchpads, in the order they are first used in the config.
+    pub(crate) fn list(state: &State) -> Vec<Self> {
+        let mut groups: Vec<Self> = vec![];
+        for scratchpad in &state.scratchpads {
+            let Some(group) = &scratchpad.group else {
+                continue;
+            };
+            let index = match groups.iter().position(|g| &g.name == group) {
+                Some(index) => index,
+                None => {
+                    groups.push(Self {
+                        name: group.clone(),
+                        members: vec![],
+                        current: state
+                            .scratchpad_groups
+                            .get(group)
+                            .map(|name| name.clone().into()),
+                        visible: false,
+                    });
+                    groups.len() - 1
+                }
+            };
+            let info = &mut groups[index];
+            info.members.push(scratchpad.name.clone().into());
+            info.visible |= state.shown_scratchpad_windows(scratchpad).next().is_some();
+        }
+        groups
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ManagerState {
     pub window_title: Option<String>,
@@ -21,6 +188,9 @@ pub struct ManagerState {
     pub active_desktop: Vec<String>,
     pub working_tags: Vec<String>,
     pub urgent_tags: Vec<String>,
+    pub windows: Vec<WindowInfo>,
+    #[serde(default)]
+    pub scratchpad_groups: Vec<ScratchPadGroupInfo>,
 }
 
 #[allow(clippy::struct_excessive_bools)]
@@ -45,22 +215,29 @@ pub struct DisplayWorkspace {
     pub layout: String,
     pub index: usize,
     pub tags: Vec<TagsForWorkspace>,
+    pub windows: Vec<WindowInfo>,
 }
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DisplayState {
     pub window_title: String,
     pub workspaces: Vec<DisplayWorkspace>,
+    pub windows: Vec<WindowInfo>,
+    pub scratchpad_groups: Vec<ScratchPadGroupInfo>,
 }
 
 impl From<ManagerState> for DisplayState {
     fn from(m: ManagerState) -> Self {
-        let visible: Vec<String> = m.viewports.iter().map(|vp| vp.tag.clone()).collect();
+        let visible: Vec<String> = m
+            .viewports
+            .iter()
+            .flat_map(|vp| std::iter::once(&vp.tag).chain(&vp.extra_tags).cloned())
+            .collect();
         let workspaces = m
             .viewports
             .iter()
             .enumerate()
             .map(|(i, vp)| {
-                viewport_into_display_workspace(
+                let mut workspace = viewport_into_display_workspace(
                     &m.desktop_names,
                     &m.active_desktop,
                     &visible,
@@ -68,12 +245,26 @@ impl From<ManagerState> for DisplayState {
                     &m.urgent_tags,
                     vp,
                     i,
-                )
+                );
+                workspace.windows = m
+                    .windows
+                    .iter()
+                    .filter(|w| {
+                        w.tag_label
+                            .iter()
+                            .chain(&w.extra_tag_labels)
+                            .any(|tag| *tag == vp.tag || vp.extra_tags.contains(tag))
+                    })
+                    .cloned()
+                    .collect();
+                workspace
             })
             .collect();
         Self {
             workspaces,
             window_title: m.window_title.unwrap_or_default(),
+            windows: m.windows,
+            scratchpad_groups: m.scratchpad_groups,
         }
     }
 }
@@ -93,7 +284,7 @@ fn viewport_into_display_workspace(
         .map(|(index, t)| TagsForWorkspace {
             name: t.clone(),
             index,
-            mine: viewport.tag == *t,
+            mine: viewport.tag == *t || viewport.extra_tags.contains(t),
             visible: visible.contains(t),
             focused: focused.contains(t),
             urgent: urgent_tags.contains(t),
@@ -110,12 +301,12 @@ fn viewport_into_display_workspace(
         y: viewport.y,
         index: ws_index,
         layout: viewport.layout.clone(),
+        windows: vec![],
     }
 }
 
 impl From<&State> for ManagerState {
     fn from(state: &State) -> Self {
-        let mut viewports: Vec<Viewport> = vec![];
         // tags_len = if tags_len == 0 { 0 } else { tags_len - 1 };
         let working_tags = state
             .tags
@@ -131,50 +322,29 @@ impl From<&State> for ManagerState {
             .filter(|tag| state.windows.iter().any(|w| w.has_tag(&tag.id) && w.urgent))
             .map(|t| t.label.clone())
             .collect();
-        for ws in &state.workspaces {
-            let tag_label = ws
-                .tag
-                .map(|tag_id| state.tags.get(tag_id).map(|tag| tag.label.clone()))
-                .unwrap()
-                .unwrap();
-
-            let layout_name: String = ws
-                .tag
-                .and_then(|tagid| state.layout_manager.layout_maybe(ws.id, tagid))
-                .map_or_else(|| String::from("N/A"), |layout| layout.name.clone());
-
-            let output = state
-                .screens
-                .iter()
-                .find(|s| s.id == Some(ws.id))
-                .map_or_else(
-                    || String::from("Not found (unreachable)"),
-                    |s| s.output.clone(),
-                );
-
-            viewports.push(Viewport {
-                id: ws.id,
-                output,
-                tag: tag_label,
-                x: ws.xyhw.x(),
-                y: ws.xyhw.y(),
-                h: ws.xyhw.h() as u32,
-                w: ws.xyhw.w() as u32,
-                layout: layout_name,
-            });
-        }
+        let viewports: Vec<Viewport> = state
+            .workspaces
+            .iter()
+            .map(|ws| Viewport::new(state, ws))
+            .collect();
         let active_desktop = match state.focus_manager.workspace(&state.workspaces) {
             Some(ws) => ws
-                .tag
-                .iter()
-                .map(|&tag_id| state.tags.get(tag_id).unwrap().label.clone())
+                .tags()
+                .filter_map(|tag_id| state.tags.get(tag_id).map(|tag| tag.label.clone()))
                 .collect(),
             None => vec![], // todo ??
         };
-        let window_title = match state.focus_manager.window(&state.windows) {
+        let focused_window = state.focus_manager.window(&state.windows);
+        let window_title = match focused_window {
             Some(win) => win.name.clone(),
             None => None,
         };
+        let windows = state
+            .windows
+            .iter()
+            .filter(|w| w.is_managed())
+            .map(|w| WindowInfo::new(state, w, focused_window.map(|f| f.handle) == Some(w.handle)))
+            .collect();
         Self {
             window_title,
             desktop_names: state
@@ -187,6 +357,85 @@ impl From<&State> for ManagerState {
             active_desktop,
             urgent_tags,
             working_tags,
+            windows,
+            scratchpad_groups: ScratchPadGroupInfo::list(state),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Screen;
+    use crate::Manager;
+
+    #[test]
+    fn workspaces_without_a_tag_are_reported_with_an_empty_label() {
+        let mut manager = Manager::new_test(vec!["web".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.state.workspaces[0].tag = None;
+
+        let state = ManagerState::from(&manager.state);
+        assert_eq!(state.viewports[0].tag, "");
+        assert!(state.active_desktop.is_empty());
+    }
+
+    #[test]
+    fn windows_are_listed_per_workspace() {
+        let mut manager = Manager::new_test(vec!["web".to_string(), "code".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), Some("one".to_string()), None),
+            -1,
+            -1,
+        );
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(2), Some("two".to_string()), None),
+            -1,
+            -1,
+        );
+        manager.state.windows[0].tag(&2);
+
+        let state: ManagerState = (&manager.state).into();
+        assert_eq!(state.windows.len(), 2);
+        assert_eq!(
+            state.windows.iter().filter(|w| w.focused).count(),
+            1,
+            "exactly one window should be focused"
+        );
+
+        let display: DisplayState = state.into();
+        assert_eq!(display.windows.len(), 2);
+        for workspace in &display.workspaces {
+            assert_eq!(workspace.windows.len(), 1);
+            assert_eq!(
+                workspace.windows[0].tag_label.as_ref(),
+                workspace.tags.iter().find(|t| t.mine).map(|t| &t.name),
+                "workspace should only list windows on its own tag"
+            );
+        }
+    }
+
+    #[test]
+    fn tags_toggled_into_view_are_listed() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.state.windows[0].tag(&2);
+        manager.state.workspaces[0].toggle_tag(&2);
+
+        let state: ManagerState = (&manager.state).into();
+        assert_eq!(state.viewports[0].extra_tags, vec!["2".to_string()]);
+        assert_eq!(state.active_desktop, vec!["1".to_string(), "2".to_string()]);
+
+        let display: DisplayState = state.into();
+        let workspace = &display.workspaces[0];
+        assert!(workspace.tags.iter().all(|t| t.mine && t.visible));
+        assert_eq!(workspace.windows.len(), 1);
+    }
+}
@@ -3,9 +3,12 @@ use leftwm_layouts::layouts::Layouts;
 
 use crate::config::Config;
 use crate::display_servers::DisplayServer;
+use crate::layouts::LayoutManager;
+use crate::models::{ScratchPadBinding, Session};
 use crate::state::State;
 use crate::utils::child_process::Children;
 use std::sync::{atomic::AtomicBool, Arc};
+use std::time::Instant;
 
 /// Maintains current program state.
 #[derive(Debug)]
@@ -16,6 +19,14 @@ pub struct Manager<C, SERVER> {
     pub(crate) children: Children,
     pub(crate) reap_requested: Arc<AtomicBool>,
     pub(crate) reload_requested: bool,
+    /// The windows of the scratchpads as they were last saved.
+    pub(crate) scratchpad_bindings: Vec<ScratchPadBinding>,
+    /// The session as it was last saved.
+    pub(crate) last_session: Session,
+    /// When the session was last compared to the saved one.
+    pub(crate) last_session_saved: Option<Instant>,
+    /// Looks up the parent of a process, to find the terminal a window was launched from.
+    pub(crate) parent_pid: fn(u32) -> Option<u32>,
     pub display_server: SERVER,
 }
 
@@ -32,6 +43,10 @@ where
             children: Default::default(),
             reap_requested: Default::default(),
             reload_requested: false,
+            scratchpad_bindings: Vec::new(),
+            last_session: Session::default(),
+            last_session_saved: None,
+            parent_pid: crate::child_process::parent_pid,
         }
     }
 }
@@ -56,6 +71,24 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         self.state.load_config(&self.config);
         true
     }
+
+    /// Load the layouts from the configuration again, keeping the layout each workspace or tag
+    /// is using as far as it is still available. Returns a description of each layout in use
+    /// which could not be kept.
+    pub fn reload_layouts(&mut self) -> Vec<String> {
+        let old = std::mem::replace(
+            &mut self.state.layout_manager,
+            LayoutManager::new(&self.config),
+        );
+        self.state.layout_definitions = self.config.layout_definitions();
+        let workspaces = &self.state.workspaces;
+        self.state.layout_manager.restore(&old, |tag| {
+            workspaces
+                .iter()
+                .find(|ws| ws.tag == Some(tag))
+                .map(|ws| ws.id)
+        })
+    }
 }
 
 #[cfg(test)]
@@ -1,12 +1,22 @@
 use crate::models::Size;
+use crate::Window;
 use serde::{Deserialize, Serialize};
 
-use super::{Xyhw, XyhwBuilder};
+use super::{WindowHandle, WorkspaceId, Xyhw, XyhwBuilder};
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct ScratchPad {
     pub name: ScratchPadName,
+    /// Command launching the scratchpad. With a `class` or `title` given it is only run when
+    /// no matching window exists, and may be left empty.
+    #[serde(default)]
     pub value: String,
+    /// `WM_CLASS` of a window to adopt as the scratchpad.
+    #[serde(default)]
+    pub class: Option<String>,
+    /// Title of a window to adopt as the scratchpad.
+    #[serde(default)]
+    pub title: Option<String>,
     // relative x of scratchpad, 25 means 25% of workspace x
     pub x: Option<Size>,
     // relative y of scratchpad, 25 means 25% of workspace y
@@ -15,15 +25,50 @@ pub struct ScratchPad {
     pub height: Option<Size>,
     // relative width of scratchpad, 50 means 50% of workspace width
     pub width: Option<Size>,
+    /// Side of the workspace the scratchpad is placed against, `x` and `y` are ignored then.
+    #[serde(default)]
+    pub anchor: Option<ScratchPadAnchor>,
+    /// Workspace the scratchpad is always shown on, instead of the focused one.
+    #[serde(default)]
+    pub workspace: Option<WorkspaceId>,
+    /// Move the visible scratchpad along when another workspace is focused.
+    #[serde(default)]
+    pub follow_focus: bool,
+    /// Group of scratchpads of which only one is shown at a time.
+    #[serde(default)]
+    pub group: Option<String>,
+}
+
+/// Side of the workspace a scratchpad is placed against.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScratchPadAnchor {
+    /// Centered at the top, like a dropdown terminal.
+    Top,
+    Bottom,
+    Left,
+    /// Centered at the right, like a drawer.
+    Right,
+    Center,
 }
 
 impl ScratchPad {
     // Get size and position of scratchpad from config and workspace size.
     pub fn xyhw(&self, xyhw: &Xyhw) -> Xyhw {
-        let x_sane = sane_dimension(self.x, 0.25, xyhw.w());
-        let y_sane = sane_dimension(self.y, 0.25, xyhw.h());
         let height_sane = sane_dimension(self.height, 0.50, xyhw.h());
         let width_sane = sane_dimension(self.width, 0.50, xyhw.w());
+        let centered_x = (xyhw.w() - width_sane) / 2;
+        let centered_y = (xyhw.h() - height_sane) / 2;
+        let (x_sane, y_sane) = match self.anchor {
+            None => (
+                sane_dimension(self.x, 0.25, xyhw.w()),
+                sane_dimension(self.y, 0.25, xyhw.h()),
+            ),
+            Some(ScratchPadAnchor::Top) => (centered_x, 0),
+            Some(ScratchPadAnchor::Bottom) => (centered_x, xyhw.h() - height_sane),
+            Some(ScratchPadAnchor::Left) => (0, centered_y),
+            Some(ScratchPadAnchor::Right) => (xyhw.w() - width_sane, centered_y),
+            Some(ScratchPadAnchor::Center) => (centered_x, centered_y),
+        };
 
         XyhwBuilder {
             x: xyhw.x() + x_sane,
@@ -34,11 +79,44 @@ impl ScratchPad {
         }
         .into()
     }
+
+    /// Float the window at the position of the scratchpad on the workspace.
+    pub fn place(&self, window: &mut Window, workspace: &Xyhw) {
+        window.set_floating(true);
+        window.normal = *workspace;
+        window.set_floating_exact(self.xyhw(workspace));
+    }
+
+    /// Whether the scratchpad adopts windows by their class or title.
+    pub fn adopts_windows(&self) -> bool {
+        self.class.is_some() || self.title.is_some()
+    }
+
+    /// Whether the window is matched by the `class` and `title` of the scratchpad.
+    pub fn matches(&self, window: &Window) -> bool {
+        let matches = |expected: &Option<String>, values: [&Option<String>; 2]| {
+            expected.as_ref().map_or(true, |expected| {
+                values.iter().any(|v| v.as_ref() == Some(expected))
+            })
+        };
+        self.adopts_windows()
+            && matches(&self.class, [&window.res_class, &window.res_name])
+            && matches(&self.title, [&window.name, &window.legacy_name])
+    }
+}
+
+/// A window of a scratchpad, saved to reattach it once the worker is restarted.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ScratchPadBinding {
+    pub name: ScratchPadName,
+    pub pid: Option<u32>,
+    pub handle: WindowHandle,
+    pub class: Option<String>,
 }
 
 /// Newtype used as the name for a scratchpad, can be seen as some sort of symbol in languages like
 /// Lisp/Scheme/...
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(from = "String")]
 #[serde(into = "String")]
 pub struct ScratchPadName(String);
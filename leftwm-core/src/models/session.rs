@@ -0,0 +1,170 @@
+use crate::errors::Result;
+use crate::models::{FloatingGeometry, Size, TagId, Window, WindowHandle, Xyhw};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use xdg::BaseDirectories;
+
+/// Name of the session saved automatically, and restored on the next login.
+pub const LAST_SESSION: &str = "last";
+
+/// The placement of the windows, kept across logins. Windows appearing again are matched back
+/// to it by their `WM_CLASS` and title.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Session {
+    pub windows: Vec<SessionWindow>,
+}
+
+/// The placement of a single window of a [`Session`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SessionWindow {
+    pub class: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Label of the tag, which stays the same when the tags are reordered.
+    pub tag: String,
+    /// Geometry relative to the workspace, if the window is floating.
+    #[serde(default)]
+    pub floating: Option<FloatingGeometry>,
+    /// Position of the window among the tiled windows of its tag.
+    #[serde(default)]
+    pub slot: usize,
+}
+
+impl Session {
+    /// Load the session with the given name from the data directory.
+    ///
+    /// # Errors
+    ///
+    /// Will error if the session doesn't exist or cannot be parsed.
+    pub fn load(name: &str) -> Result<Self> {
+        let path = session_file(name)?;
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    /// Save the session with the given name to the data directory.
+    ///
+    /// # Errors
+    ///
+    /// Will error if the session file cannot be written.
+    pub fn save(&self, name: &str) -> Result<()> {
+        let path = session_file(name)?;
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Take the entry with the same class and title as the window.
+    pub fn take_exact(&mut self, window: &Window) -> Option<SessionWindow> {
+        let class = window.res_class.as_deref()?;
+        let index = self
+            .windows
+            .iter()
+            .position(|w| w.class == class && w.title == window.name)?;
+        Some(self.windows.remove(index))
+    }
+
+    /// Take the entry best matching the window: the one with the same class and title, or else
+    /// the first one with the same class.
+    pub fn take(&mut self, window: &Window) -> Option<SessionWindow> {
+        if let Some(entry) = self.take_exact(window) {
+            return Some(entry);
+        }
+        let class = window.res_class.as_deref()?;
+        let index = self.windows.iter().position(|w| w.class == class)?;
+        Some(self.windows.remove(index))
+    }
+}
+
+impl SessionWindow {
+    /// Record the placement of a window on the tag with the given label. Windows without a class
+    /// are not recorded.
+    #[must_use]
+    pub fn new(window: &Window, tag: &str, slot: usize) -> Option<Self> {
+        // A floating window is placed relative to its `normal` geometry, the one of its workspace.
+        let floating = window.floating().then(|| {
+            let xyhw = window.exact_xyhw();
+            FloatingGeometry {
+                x: Some(Size::Pixel(xyhw.x() - window.normal.x())),
+                y: Some(Size::Pixel(xyhw.y() - window.normal.y())),
+                width: Some(Size::Pixel(xyhw.w())),
+                height: Some(Size::Pixel(xyhw.h())),
+            }
+        });
+        Some(Self {
+            class: window.res_class.clone()?,
+            title: window.name.clone(),
+            tag: tag.to_string(),
+            floating,
+            slot,
+        })
+    }
+
+    /// Move a window to `tag`, the one with the label of this entry, and to the floating geometry
+    /// of this entry, `workspace` being the one the tag is shown on. Its slot is restored once
+    /// the window is managed.
+    pub fn apply(&self, window: &mut Window, tag: TagId, workspace: Option<&Xyhw>) {
+        window.tag(&tag);
+        match (self.floating, workspace) {
+            (Some(geometry), Some(workspace)) => {
+                window.set_floating(true);
+                window.normal = *workspace;
+                window.set_floating_exact(geometry.xyhw(window.exact_xyhw(), workspace));
+            }
+            (Some(_), None) => window.set_floating(true),
+            (None, _) => window.set_floating(false),
+        }
+    }
+}
+
+/// The windows of a restored session, while they are being placed.
+#[derive(Debug, Clone, Default)]
+pub struct RestoredSession {
+    /// Entries not matched to a window yet.
+    pub pending: Session,
+    /// The slots of the windows placed so far, to order the windows appearing later.
+    pub slots: Vec<(WindowHandle, usize)>,
+}
+
+fn session_file(name: &str) -> Result<PathBuf> {
+    let path = BaseDirectories::with_prefix("leftwm")?
+        .place_data_file(PathBuf::from("sessions").join(format!("{name}.json")))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(class: &str, title: &str) -> Window {
+        let mut window = Window::new(WindowHandle::MockHandle(1), Some(title.to_string()), None);
+        window.res_class = Some(class.to_string());
+        window
+    }
+
+    fn entry(class: &str, title: &str, tag: &str) -> SessionWindow {
+        SessionWindow {
+            class: class.to_string(),
+            title: Some(title.to_string()),
+            tag: tag.to_string(),
+            floating: None,
+            slot: 0,
+        }
+    }
+
+    #[test]
+    fn the_same_title_is_preferred_over_the_first_entry() {
+        let mut session = Session {
+            windows: vec![
+                entry("Alacritty", "htop", "2"),
+                entry("Alacritty", "vim", "3"),
+                entry("firefox", "Mozilla Firefox", "1"),
+            ],
+        };
+
+        let taken = session.take(&window("Alacritty", "vim")).unwrap();
+        assert_eq!(taken.tag, "3");
+        let taken = session.take(&window("Alacritty", "zsh")).unwrap();
+        assert_eq!(taken.tag, "2");
+        assert!(session.take(&window("Alacritty", "zsh")).is_none());
+        assert_eq!(session.windows.len(), 1);
+    }
+}
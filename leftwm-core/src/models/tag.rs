@@ -1,6 +1,8 @@
-use super::{TagId, Xyhw};
-use crate::{layouts::LayoutManager, Window, Workspace};
+use super::{TagId, WindowHandle, Xyhw};
+use crate::layouts::{apply_weights, LayoutManager, SplitTree, MANUAL};
+use crate::{Window, Workspace};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Wrapper struct holding all the tags.
 /// This wrapper provides convenience methods to change the tag-list
@@ -75,8 +77,41 @@ impl Tags {
     // todo: add_new_at(position, label, layout)
     // -> shifting all one to the right and re-number them (vec.insert)
 
-    // todo: remove(id)
-    // -> shifting all right of the removed tag one to the left and re-number them
+    /// Remove the normal tag with the provided ID.
+    /// All tags right of the removed tag are shifted one to the left
+    /// and re-numbered accordingly.
+    ///
+    /// Returns `None` if there is no normal tag with that ID.
+    pub fn remove(&mut self, id: TagId) -> Option<Tag> {
+        if id == 0 || id > self.normal.len() {
+            return None;
+        }
+        let tag = self.normal.remove(id - 1);
+        self.renumber();
+        Some(tag)
+    }
+
+    /// Move the normal tag with the provided ID to the provided position,
+    /// so that it will afterwards have the ID `position`. The tags in between
+    /// are shifted and re-numbered accordingly.
+    ///
+    /// Returns `false` if either the ID or the position is out of range.
+    pub fn move_to(&mut self, id: TagId, position: TagId) -> bool {
+        let len = self.normal.len();
+        if id == 0 || id > len || position == 0 || position > len {
+            return false;
+        }
+        let tag = self.normal.remove(id - 1);
+        self.normal.insert(position - 1, tag);
+        self.renumber();
+        true
+    }
+
+    fn renumber(&mut self) {
+        for (i, tag) in self.normal.iter_mut().enumerate() {
+            tag.id = i + 1; // tag id starts at 1
+        }
+    }
 
     /// Create a new hidden tag with the provided label,
     /// and append it to the list of hidden tags.
@@ -152,6 +187,11 @@ impl Tags {
             .find(|hidden_tag| hidden_tag.id == id);
     }
 
+    /// Get a normal tag by its label
+    pub fn get_normal_by_label(&self, label: &str) -> Option<&Tag> {
+        self.normal.iter().find(|tag| tag.label.eq(label))
+    }
+
     /// Get a hidden tag by its label
     pub fn get_hidden_by_label(&self, label: &str) -> Option<&Tag> {
         self.hidden.iter().find(|tag| tag.label.eq(label))
@@ -219,15 +259,22 @@ impl Tag {
         }
     }
 
+    /// Arrange the windows displayed by a workspace focused on this tag. Windows of tags toggled
+    /// into view on the workspace are tiled together with the ones of this tag.
+    ///
+    /// With the [`MANUAL`] layout they are arranged by the split tree of this tag, new windows
+    /// being split off the focused one.
     pub fn update_windows(
         &self,
         windows: &mut [Window],
         workspace: &Workspace,
         layout_manager: &mut LayoutManager,
+        split_trees: &mut HashMap<TagId, SplitTree>,
+        focused: Option<WindowHandle>,
     ) {
         if let Some(window) = windows
             .iter_mut()
-            .find(|w| w.has_tag(&self.id) && w.is_fullscreen())
+            .find(|w| workspace.is_displaying(w) && w.is_fullscreen())
         {
             window.set_visible(true);
             window.normal = workspace.xyhw;
@@ -235,7 +282,7 @@ impl Tag {
             windows
                 .iter_mut()
                 .filter(|w| {
-                    w.has_tag(&self.id)
+                    workspace.is_displaying(w)
                         && w.transient.unwrap_or_else(|| 0.into()) == handle
                         && w.is_managed()
                 })
@@ -244,7 +291,7 @@ impl Tag {
                 });
         } else if let Some(window) = windows
             .iter_mut()
-            .find(|w| w.has_tag(&self.id) && w.is_maximized())
+            .find(|w| workspace.is_displaying(w) && w.is_maximized())
         {
             // Update maximized window
             window.set_visible(true);
@@ -253,7 +300,7 @@ impl Tag {
             windows
                 .iter_mut()
                 .filter(|w| {
-                    w.has_tag(&self.id)
+                    workspace.is_displaying(w)
                         && w.transient.unwrap_or_else(|| 0.into()) == handle
                         && w.is_managed()
                 })
@@ -265,7 +312,9 @@ impl Tag {
             windows
                 .iter_mut()
                 .filter(|w| {
-                    w.has_tag(&self.id) && (!w.is_normal() || w.floating()) && !w.is_maximized()
+                    workspace.is_displaying(w)
+                        && (!w.is_normal() || w.floating())
+                        && !w.is_maximized()
                 })
                 .for_each(|w| {
                     w.set_visible(true);
@@ -277,17 +326,33 @@ impl Tag {
         } else {
             // Don't bother updating the other windows when a window is fullscreen.
             // Mark all windows for this workspace as visible.
-            let mut all_mine: Vec<&mut Window> =
-                windows.iter_mut().filter(|w| w.has_tag(&self.id)).collect();
+            let mut all_mine: Vec<&mut Window> = windows
+                .iter_mut()
+                .filter(|w| workspace.is_displaying(w))
+                .collect();
             all_mine.iter_mut().for_each(|w| w.set_visible(true));
 
             // Update the location / visibility of all non-floating windows.
             let mut managed_nonfloat: Vec<&mut Window> = windows
                 .iter_mut()
-                .filter(|w| w.has_tag(&self.id) && w.is_managed() && !w.floating())
+                .filter(|w| workspace.is_displaying(w) && w.is_managed() && !w.floating())
                 .collect();
-            let def = layout_manager.layout(workspace.id, workspace.tag.unwrap_or(1));
-            let rects = leftwm_layouts::apply(def, managed_nonfloat.len(), &workspace.rect());
+            let def = layout_manager.layout(workspace.id, self.id);
+            let rects = if def.name == MANUAL {
+                let handles: Vec<WindowHandle> =
+                    managed_nonfloat.iter().map(|w| w.handle).collect();
+                split_trees
+                    .entry(self.id)
+                    .or_default()
+                    .arrange(&handles, focused, workspace.rect())
+            } else {
+                let mut rects =
+                    leftwm_layouts::apply(def, managed_nonfloat.len(), &workspace.rect());
+                let weights: Vec<f32> =
+                    managed_nonfloat.iter().map(|w| w.weight(self.id)).collect();
+                apply_weights(&mut rects, &weights);
+                rects
+            };
             for (i, window) in managed_nonfloat.iter_mut().enumerate() {
                 match rects.get(i) {
                     Some(rect) => {
@@ -303,7 +368,7 @@ impl Tag {
             // Update the location of all floating windows.
             windows
                 .iter_mut()
-                .filter(|w| w.has_tag(&self.id) && w.is_managed() && w.floating())
+                .filter(|w| workspace.is_displaying(w) && w.is_managed() && w.floating())
                 .for_each(|w| w.normal = workspace.xyhw);
         }
     }
@@ -430,4 +495,46 @@ mod tests {
         let second_retrieve = tags.get_mut(2).unwrap();
         assert_eq!(second_retrieve.label, String::from("code"));
     }
+
+    #[test]
+    fn removing_a_tag_renumbers_the_following_tags() {
+        let mut tags = Tags::new();
+        tags.add_new("home");
+        tags.add_new("chat");
+        tags.add_new("surf");
+        let nsp_id = tags.add_new_hidden("NSP");
+
+        let removed = tags.remove(2).unwrap();
+        assert_eq!(removed.label, String::from("chat"));
+        assert_eq!(tags.len_normal(), 2);
+        assert_eq!(tags.get(2).unwrap().label, String::from("surf"));
+        assert_eq!(tags.get(2).unwrap().id, 2);
+        assert_eq!(tags.get_hidden_by_label("NSP").unwrap().id, nsp_id.unwrap());
+
+        assert!(tags.remove(0).is_none());
+        assert!(tags.remove(3).is_none());
+    }
+
+    #[test]
+    fn moving_a_tag_renumbers_the_tags_in_between() {
+        let mut tags = Tags::new();
+        tags.add_new("home");
+        tags.add_new("chat");
+        tags.add_new("surf");
+        tags.add_new("code");
+
+        assert!(tags.move_to(4, 2));
+        let labels: Vec<(usize, &str)> = tags
+            .normal()
+            .iter()
+            .map(|t| (t.id, t.label.as_str()))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![(1, "home"), (2, "code"), (3, "chat"), (4, "surf")]
+        );
+
+        assert!(!tags.move_to(1, 5));
+        assert!(!tags.move_to(0, 1));
+    }
 }
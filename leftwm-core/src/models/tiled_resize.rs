@@ -0,0 +1,62 @@
+//! Resizing tiled windows by dragging the boundary between them with the mouse.
+use super::{TagId, WindowHandle, WorkspaceId};
+
+/// The smallest share of the two windows of a dragged boundary either of them keeps.
+const MIN_SHARE: f32 = 0.05;
+
+/// A boundary between tiled windows, being dragged with the mouse.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TiledResize {
+    /// The boundary between the main and the stack area, changing the main size.
+    MainSize {
+        workspace: WorkspaceId,
+        tag: TagId,
+        /// Whether the boundary is dragged to the left and right, rather than up and down.
+        horizontal: bool,
+        /// 1 if the main area lies left of or above the boundary, -1 otherwise.
+        sign: i32,
+        /// The part of the drag applied to the main size so far.
+        applied: i32,
+    },
+    /// The boundary between two windows of the same stack, changing their weights.
+    Weights {
+        tag: TagId,
+        /// Whether the boundary is dragged to the left and right, rather than up and down.
+        horizontal: bool,
+        /// The window left of or above the boundary, then the one right of or below it.
+        windows: [StackedWindow; 2],
+    },
+}
+
+/// A window of a stack, as it was when dragging its boundary started.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StackedWindow {
+    pub handle: WindowHandle,
+    pub weight: f32,
+    /// Its length along the direction the boundary is dragged in.
+    pub length: i32,
+}
+
+impl TiledResize {
+    /// How far the boundary was dragged, by the offset of the mouse since dragging started.
+    #[must_use]
+    pub fn drag(&self, offset_x: i32, offset_y: i32) -> i32 {
+        let (Self::MainSize { horizontal, .. } | Self::Weights { horizontal, .. }) = self;
+        if *horizontal {
+            offset_x
+        } else {
+            offset_y
+        }
+    }
+}
+
+/// The weights of the two windows of a boundary dragged by `drag`. The two of them keep the
+/// space they take together, so the rest of their stack stays the same.
+#[must_use]
+pub(crate) fn dragged_weights(windows: &[StackedWindow; 2], drag: i32) -> [f32; 2] {
+    let [first, second] = windows;
+    let length = (first.length + second.length).max(1) as f32;
+    let share = ((first.length + drag) as f32 / length).clamp(MIN_SHARE, 1.0 - MIN_SHARE);
+    let weight = first.weight + second.weight;
+    [weight * share, weight * (1.0 - share)]
+}
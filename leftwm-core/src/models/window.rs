@@ -4,15 +4,20 @@ use super::WindowState;
 use super::WindowType;
 use crate::models::Margins;
 use crate::models::TagId;
+use crate::models::WindowOverrides;
 use crate::models::Xyhw;
 use crate::models::XyhwBuilder;
 use crate::Workspace;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use x11_dl::xlib;
 
 type MockHandle = i32;
 
+/// The smallest weight a window can be shrunk to.
+const MIN_WEIGHT: f32 = 0.1;
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WindowHandle {
     MockHandle(MockHandle),
@@ -65,6 +70,9 @@ pub struct Window {
     pub pid: Option<u32>,
     pub r#type: WindowType,
     pub tag: Option<TagId>,
+    /// Further tags the window is a member of, besides `tag`.
+    #[serde(default)]
+    pub extra_tags: Vec<TagId>,
     pub border: i32,
     pub margin: Margins,
     pub margin_multiplier: f32,
@@ -77,6 +85,18 @@ pub struct Window {
     // Two strings that are within a XClassHint, kept separate for simpler comparing.
     pub res_name: Option<String>,
     pub res_class: Option<String>,
+    /// `WM_WINDOW_ROLE` in X11.
+    #[serde(default)]
+    pub role: Option<String>,
+    /// The terminal this window was launched from, hidden while this window takes its place.
+    #[serde(default)]
+    pub swallowed: Option<WindowHandle>,
+    /// Settings taking precedence over the config, e.g. set by a window rule.
+    #[serde(default)]
+    pub overrides: WindowOverrides,
+    /// The share of its stack the window takes on each tag, relative to the other windows.
+    #[serde(default)]
+    pub weights: HashMap<TagId, f32>,
 }
 
 impl Window {
@@ -97,6 +117,7 @@ impl Window {
             legacy_name: None,
             r#type: WindowType::Normal,
             tag: None,
+            extra_tags: vec![],
             border: 1,
             margin: Margins::new(10),
             margin_multiplier: 1.0,
@@ -109,6 +130,10 @@ impl Window {
             strut: None,
             res_name: None,
             res_class: None,
+            role: None,
+            swallowed: None,
+            overrides: WindowOverrides::default(),
+            weights: HashMap::new(),
         }
     }
 
@@ -239,6 +264,18 @@ impl Window {
         self.margin_multiplier
     }
 
+    /// The weight of the window on the given tag, 1 unless it was changed.
+    #[must_use]
+    pub fn weight(&self, tag: TagId) -> f32 {
+        self.weights.get(&tag).copied().unwrap_or(1.0)
+    }
+
+    /// Grow, or shrink with a negative `delta`, the weight of the window on the given tag.
+    pub fn change_weight(&mut self, tag: TagId, delta: f32) {
+        let weight = (self.weight(tag) + delta).max(MIN_WEIGHT);
+        self.weights.insert(tag, weight);
+    }
+
     #[must_use]
     pub fn width(&self) -> i32 {
         let mut value;
@@ -351,17 +388,41 @@ impl Window {
         self.calculated_xyhw().contains_point(x, y)
     }
 
+    /// Make the given tag the only tag of the window.
     pub fn tag(&mut self, tag: &TagId) {
         self.tag = Some(*tag);
+        self.extra_tags.clear();
     }
 
     #[must_use]
     pub fn has_tag(&self, tag: &TagId) -> bool {
-        self.tag == Some(*tag)
+        self.tag == Some(*tag) || self.extra_tags.contains(tag)
     }
 
     pub fn untag(&mut self) {
         self.tag = None;
+        self.extra_tags.clear();
+    }
+
+    /// Add the window to the given tag, or remove it if it already is a member.
+    /// A window is never removed from its last tag.
+    pub fn toggle_tag(&mut self, tag: &TagId) {
+        if self.tag == Some(*tag) {
+            if !self.extra_tags.is_empty() {
+                self.tag = Some(self.extra_tags.remove(0));
+            }
+        } else if self.extra_tags.contains(tag) {
+            self.extra_tags.retain(|t| t != tag);
+        } else if self.tag.is_none() {
+            self.tag = Some(*tag);
+        } else {
+            self.extra_tags.push(*tag);
+        }
+    }
+
+    /// All tags the window is a member of.
+    pub fn tags(&self) -> impl Iterator<Item = TagId> + '_ {
+        self.tag.iter().chain(&self.extra_tags).copied()
     }
 
     #[must_use]
@@ -418,4 +479,28 @@ mod tests {
         subject.untag();
         assert!(!subject.has_tag(&1), "was unable to untag the window");
     }
+
+    #[test]
+    fn should_be_able_to_toggle_tags_of_a_window() {
+        let mut subject = Window::new(WindowHandle::MockHandle(1), None, None);
+        subject.tag(&1);
+        subject.toggle_tag(&2);
+        subject.toggle_tag(&3);
+        assert!(subject.has_tag(&1) && subject.has_tag(&2) && subject.has_tag(&3));
+
+        subject.toggle_tag(&1);
+        assert!(
+            !subject.has_tag(&1),
+            "was unable to remove the window from its tag"
+        );
+        assert_eq!(subject.tag, Some(2));
+
+        subject.toggle_tag(&2);
+        subject.toggle_tag(&3);
+        assert_eq!(
+            subject.tags().collect::<Vec<_>>(),
+            vec![3],
+            "last tag was removed"
+        );
+    }
 }
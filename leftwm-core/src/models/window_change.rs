@@ -15,6 +15,8 @@ pub struct WindowChange {
     pub never_focus: Option<bool>,
     pub urgent: Option<bool>,
     pub name: Option<MaybeName>,
+    pub res_name: Option<MaybeName>,
+    pub res_class: Option<MaybeName>,
     pub r#type: Option<WindowType>,
     pub floating: Option<XyhwChange>,
     pub strut: Option<XyhwChange>,
@@ -30,6 +32,8 @@ impl WindowChange {
             transient: None,
             never_focus: None,
             name: None,
+            res_name: None,
+            res_class: None,
             r#type: None,
             urgent: None,
             floating: None,
@@ -51,6 +55,14 @@ impl WindowChange {
             changed = changed || changed_name;
             window.name = name.clone();
         }
+        if let Some(res_name) = &self.res_name {
+            changed = changed || &window.res_name != res_name;
+            window.res_name = res_name.clone();
+        }
+        if let Some(res_class) = &self.res_class {
+            changed = changed || &window.res_class != res_class;
+            window.res_class = res_class.clone();
+        }
         if let Some(nf) = self.never_focus {
             let changed_nf = window.never_focus != nf;
             changed = changed || changed_nf;
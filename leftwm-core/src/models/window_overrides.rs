@@ -0,0 +1,99 @@
+use crate::models::{Margins, Size, Xyhw, XyhwBuilder};
+use serde::{Deserialize, Serialize};
+
+/// Settings of a single window which take precedence over the config, e.g. set by a window rule.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct WindowOverrides {
+    pub border_width: Option<i32>,
+    pub border_colors: BorderColors,
+    pub margin: Option<Margins>,
+    /// Applied once the window appears, it may be moved and resized afterwards.
+    pub floating_geometry: Option<FloatingGeometry>,
+    /// Center the floating window on its workspace once it appears.
+    pub center: bool,
+    /// Whether the window is focused once it appears, instead of following `focus_new_windows`.
+    pub focus_on_map: Option<bool>,
+}
+
+/// Border colors of a single window, a missing color falls back to the one of the theme.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct BorderColors {
+    pub default: Option<String>,
+    pub floating: Option<String>,
+    pub focused: Option<String>,
+}
+
+impl BorderColors {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.floating.is_none() && self.focused.is_none()
+    }
+}
+
+/// Geometry of a floating window relative to its workspace, a missing value keeps the
+/// window's own.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(default)]
+pub struct FloatingGeometry {
+    pub x: Option<Size>,
+    pub y: Option<Size>,
+    pub width: Option<Size>,
+    pub height: Option<Size>,
+}
+
+impl FloatingGeometry {
+    /// Get the absolute geometry from the current one of the window and its workspace.
+    #[must_use]
+    pub fn xyhw(&self, current: Xyhw, workspace: &Xyhw) -> Xyhw {
+        let relative = |size: Option<Size>, offset: i32, whole: i32, fallback: i32| {
+            size.map_or(fallback, |size| offset + size.into_absolute(whole))
+        };
+        XyhwBuilder {
+            x: relative(self.x, workspace.x(), workspace.w(), current.x()),
+            y: relative(self.y, workspace.y(), workspace.h(), current.y()),
+            w: relative(self.width, 0, workspace.w(), current.w()),
+            h: relative(self.height, 0, workspace.h(), current.h()),
+            ..XyhwBuilder::default()
+        }
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn geometry_is_relative_to_the_workspace() {
+        let workspace: Xyhw = XyhwBuilder {
+            x: 1920,
+            y: 0,
+            w: 1000,
+            h: 800,
+            ..XyhwBuilder::default()
+        }
+        .into();
+        let current: Xyhw = XyhwBuilder {
+            x: 2000,
+            y: 100,
+            w: 300,
+            h: 200,
+            ..XyhwBuilder::default()
+        }
+        .into();
+        let geometry = FloatingGeometry {
+            x: Some(Size::Ratio(0.5)),
+            width: Some(Size::Pixel(400)),
+            height: Some(Size::Ratio(0.25)),
+            ..FloatingGeometry::default()
+        };
+
+        let xyhw = geometry.xyhw(current, &workspace);
+        assert_eq!(
+            (xyhw.x(), xyhw.y(), xyhw.w(), xyhw.h()),
+            (2420, 100, 400, 200)
+        );
+    }
+}
@@ -9,7 +9,11 @@ use super::WorkspaceId;
 /// Information for workspaces (screen divisions).
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Workspace {
-    pub tag: Option<TagId>, // TODO: Make this a list.
+    /// The tag the workspace is focused on, its layout is used for the whole workspace.
+    pub tag: Option<TagId>,
+    /// Further tags toggled into view, their windows are tiled together with the ones of `tag`.
+    #[serde(default)]
+    pub extra_tags: Vec<TagId>,
     pub margin: Margins,
     pub margin_multiplier: f32,
     pub gutters: Vec<Gutter>,
@@ -21,13 +25,21 @@ pub struct Workspace {
     pub id: WorkspaceId,
 }
 
+/// A workspace whose screen was disconnected, kept to restore it once the screen is back.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DetachedWorkspace {
+    pub output: String,
+    pub workspace: Workspace,
+}
+
 impl fmt::Debug for Workspace {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "Workspace {{ id: {}, tags: {:?}, x: {}, y: {} }}",
+            "Workspace {{ id: {}, tags: {:?} {:?}, x: {}, y: {} }}",
             self.id,
             self.tag,
+            self.extra_tags,
             self.xyhw.x(),
             self.xyhw.y()
         )
@@ -45,6 +57,7 @@ impl Workspace {
     pub fn new(bbox: BBox, id: usize) -> Self {
         Self {
             tag: None,
+            extra_tags: vec![],
             margin: Margins::new(10),
             margin_multiplier: 1.0,
             gutters: vec![],
@@ -92,8 +105,25 @@ impl Workspace {
             })
     }
 
+    /// Show only the given tag on the workspace.
     pub fn show_tag(&mut self, tag: &TagId) {
         self.tag = Some(*tag);
+        self.extra_tags.clear();
+    }
+
+    /// Toggle an extra tag into or out of view.
+    ///
+    /// Returns `false` if the tag is the one the workspace is focused on.
+    pub fn toggle_tag(&mut self, tag: &TagId) -> bool {
+        if self.has_tag(tag) {
+            return false;
+        }
+        if self.extra_tags.contains(tag) {
+            self.extra_tags.retain(|t| t != tag);
+        } else {
+            self.extra_tags.push(*tag);
+        }
+        true
     }
 
     #[must_use]
@@ -101,18 +131,27 @@ impl Workspace {
         self.xyhw.contains_point(x, y)
     }
 
+    /// Returns true if the workspace is focused on the given tag.
     #[must_use]
     pub fn has_tag(&self, tag: &TagId) -> bool {
         self.tag == Some(*tag)
     }
 
+    /// Returns true if the given tag is in view, either focused or toggled in.
+    #[must_use]
+    pub fn shows_tag(&self, tag: &TagId) -> bool {
+        self.has_tag(tag) || self.extra_tags.contains(tag)
+    }
+
+    /// All tags in view.
+    pub fn tags(&self) -> impl Iterator<Item = TagId> + '_ {
+        self.tag.iter().chain(&self.extra_tags).copied()
+    }
+
     /// Returns true if the workspace is displays a given window.
     #[must_use]
     pub fn is_displaying(&self, window: &Window) -> bool {
-        if let Some(tag) = &window.tag {
-            return self.has_tag(tag);
-        }
-        false
+        window.tags().any(|tag| self.shows_tag(&tag))
     }
 
     /// Returns true if the workspace is to update the locations info of this window.
@@ -169,6 +208,19 @@ impl Workspace {
         self.xyhw_avoided.center_halfed()
     }
 
+    /// Move and resize the workspace to a new screen geometry.
+    pub fn set_bbox(&mut self, bbox: BBox) {
+        self.xyhw = XyhwBuilder {
+            h: bbox.height,
+            w: bbox.width,
+            x: bbox.x,
+            y: bbox.y,
+            ..XyhwBuilder::default()
+        }
+        .into();
+        self.update_avoided_areas();
+    }
+
     pub fn update_avoided_areas(&mut self) {
         let mut xyhw = self.xyhw;
         for a in &self.avoid {
@@ -239,4 +291,31 @@ mod tests {
         w.tag(&TAG_ID);
         assert!(subject.is_displaying(&w), "workspace should include window");
     }
+
+    #[test]
+    fn toggling_a_tag_into_view_should_display_its_windows() {
+        let mut subject = Workspace::new(
+            BBox {
+                width: 600,
+                height: 800,
+                x: 0,
+                y: 0,
+            },
+            0,
+        );
+        subject.show_tag(&1);
+        let mut w = Window::new(WindowHandle::MockHandle(1), None, None);
+        w.tag(&2);
+        assert!(!subject.is_displaying(&w));
+
+        assert!(subject.toggle_tag(&2));
+        assert!(subject.is_displaying(&w), "workspace should include window");
+        assert!(!subject.toggle_tag(&1), "focused tag can not be toggled");
+
+        subject.show_tag(&1);
+        assert!(
+            !subject.is_displaying(&w),
+            "showing a tag should reset the view"
+        );
+    }
 }
@@ -1,5 +1,6 @@
 //! Various window and workspace sizing structs.
 #![allow(clippy::module_name_repetitions)]
+use super::Side;
 use leftwm_layouts::geometry::Rect;
 use serde::{Deserialize, Serialize};
 use std::cmp;
@@ -310,6 +311,34 @@ impl Xyhw {
         let y = self.y + (self.h / 2);
         (x, y)
     }
+
+    /// How far `other` lies towards the given side of this one, and how far their centers are
+    /// apart across that side. `None` if `other` does not lie on that side, alongside this one.
+    #[must_use]
+    pub fn distance_towards(&self, other: &Self, side: &Side) -> Option<(i32, i32)> {
+        let horizontal = matches!(side, Side::Left | Side::Right);
+        let span = |xyhw: &Self, along_x: bool| {
+            if along_x {
+                (xyhw.x, xyhw.x + xyhw.w)
+            } else {
+                (xyhw.y, xyhw.y + xyhw.h)
+            }
+        };
+        let (start, end) = span(self, horizontal);
+        let (other_start, other_end) = span(other, horizontal);
+        let (across_start, across_end) = span(self, !horizontal);
+        let (other_across_start, other_across_end) = span(other, !horizontal);
+        if other_across_start >= across_end || across_start >= other_across_end {
+            return None;
+        }
+        let gap = match side {
+            Side::Left | Side::Top => start - other_end,
+            Side::Right | Side::Bottom => other_start - end,
+        };
+        let off_center =
+            ((across_start + across_end) - (other_across_start + other_across_end)).abs() / 2;
+        (gap >= 0).then_some((gap, off_center))
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,296 @@
+use crate::config::Config;
+use crate::models::dto::{ScratchPadInfo, TagInfo, Viewport, WindowInfo};
+use crate::models::{TagId, WindowHandle, WorkspaceId};
+use crate::state::State;
+use serde::{Deserialize, Serialize};
+use std::os::raw::c_ulong;
+use std::str::FromStr;
+
+/// Read-only requests answered with a fragment of the current [`State`].
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum Query {
+    /// All managed windows, or only the ones on the given tag.
+    Windows {
+        tag: Option<TagId>,
+    },
+    FocusedWindow,
+    Tags,
+    /// All workspaces, or only the one with the given id.
+    Workspaces {
+        workspace: Option<WorkspaceId>,
+    },
+    /// The layouts available on the given workspace, or on the focused one, with the tag it shows.
+    Layouts {
+        workspace: Option<WorkspaceId>,
+    },
+    Scratchpads,
+    /// How the window rules of the config apply to the given window, as text.
+    ExplainWindowRules {
+        handle: WindowHandle,
+    },
+}
+
+impl Query {
+    /// Answer the query from the given config and state.
+    /// # Errors
+    ///
+    /// Will error if the answer cannot be serialized.
+    pub fn answer(
+        &self,
+        config: &impl Config,
+        state: &State,
+    ) -> serde_json::Result<serde_json::Value> {
+        let focused_window = state.focus_manager.window(&state.windows).map(|w| w.handle);
+        match self {
+            Query::Windows { tag } => {
+                let windows: Vec<WindowInfo> = state
+                    .windows
+                    .iter()
+                    .filter(|w| tag.map_or(true, |tag| w.has_tag(&tag)))
+                    .map(|w| WindowInfo::new(state, w, Some(w.handle) == focused_window))
+                    .collect();
+                serde_json::to_value(windows)
+            }
+            Query::FocusedWindow => {
+                let window = state
+                    .focus_manager
+                    .window(&state.windows)
+                    .map(|w| WindowInfo::new(state, w, true));
+                serde_json::to_value(window)
+            }
+            Query::Tags => {
+                let focused_tag = state.focus_manager.tag(0);
+                let tags: Vec<TagInfo> = state
+                    .tags
+                    .normal()
+                    .iter()
+                    .map(|tag| TagInfo {
+                        id: tag.id,
+                        label: tag.label.clone(),
+                        visible: state.workspaces.iter().any(|ws| ws.shows_tag(&tag.id)),
+                        focused: focused_tag == Some(tag.id),
+                        urgent: state.windows.iter().any(|w| w.has_tag(&tag.id) && w.urgent),
+                        busy: state.windows.iter().any(|w| w.has_tag(&tag.id)),
+                    })
+                    .collect();
+                serde_json::to_value(tags)
+            }
+            Query::Workspaces { workspace } => {
+                let workspaces: Vec<Viewport> = state
+                    .workspaces
+                    .iter()
+                    .filter(|ws| workspace.map_or(true, |id| ws.id == id))
+                    .map(|ws| Viewport::new(state, ws))
+                    .collect();
+                serde_json::to_value(workspaces)
+            }
+            Query::Layouts { workspace } => {
+                let ws = match workspace {
+                    Some(id) => state.workspaces.iter().find(|ws| ws.id == *id),
+                    None => state.focus_manager.workspace(&state.workspaces),
+                };
+                let layouts: Vec<&str> = ws
+                    .map(|ws| {
+                        let tagid = ws.tag.unwrap_or_default();
+                        state.layout_manager.available_layouts(ws.id, tagid)
+                    })
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|layout| layout.name.as_str())
+                    .collect();
+                serde_json::to_value(layouts)
+            }
+            Query::Scratchpads => {
+                let scratchpads: Vec<ScratchPadInfo> = state
+                    .scratchpads
+                    .iter()
+                    .map(|scratchpad| {
+                        let windows: Vec<_> = state
+                            .active_scratchpads
+                            .get(&scratchpad.name)
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|handle| state.windows.iter().find(|w| w.handle == *handle))
+                            .collect();
+                        ScratchPadInfo {
+                            name: scratchpad.name.clone().into(),
+                            command: scratchpad.value.clone(),
+                            group: scratchpad.group.clone(),
+                            visible: state.shown_scratchpad_windows(scratchpad).next().is_some(),
+                            windows: windows.iter().map(|w| w.handle).collect(),
+                        }
+                    })
+                    .collect();
+                serde_json::to_value(scratchpads)
+            }
+            Query::ExplainWindowRules { handle } => {
+                serde_json::to_value(config.explain_window_rules(state, *handle))
+            }
+        }
+    }
+}
+
+impl FromStr for Query {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (head, rest) = s.split_once(' ').unwrap_or((s, ""));
+        let rest = rest.trim();
+        let optional_id = |name: &str| -> Result<Option<usize>, Self::Err> {
+            if rest.is_empty() {
+                Ok(None)
+            } else {
+                Ok(Some(rest.parse().map_err(|_| {
+                    format!("argument {name} was not a valid number")
+                })?))
+            }
+        };
+        match head {
+            "Windows" => Ok(Query::Windows {
+                tag: optional_id("tag_index")?,
+            }),
+            "FocusedWindow" => Ok(Query::FocusedWindow),
+            "Tags" => Ok(Query::Tags),
+            "Workspaces" => Ok(Query::Workspaces {
+                workspace: optional_id("workspace_id")?,
+            }),
+            "Layouts" => Ok(Query::Layouts {
+                workspace: optional_id("workspace_id")?,
+            }),
+            "Scratchpads" => Ok(Query::Scratchpads),
+            "ExplainWindowRules" => {
+                let id = match rest.strip_prefix("0x") {
+                    Some(hex) => c_ulong::from_str_radix(hex, 16),
+                    None => rest.parse(),
+                }
+                .map_err(|_| "argument window_id was not a valid number")?;
+                Ok(Query::ExplainWindowRules {
+                    handle: WindowHandle::XlibHandle(id),
+                })
+            }
+            _ => Err(format!("unknown query: {head}").into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Screen, Window, WindowHandle};
+    use crate::Manager;
+
+    #[test]
+    fn windows_can_be_filtered_by_tag() {
+        let mut manager = Manager::new_test(vec!["web".to_string(), "code".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(2), None, None),
+            -1,
+            -1,
+        );
+        manager.state.windows[1].tag(&2);
+
+        let all = Query::Windows { tag: None }
+            .answer(&manager.config, &manager.state)
+            .unwrap();
+        assert_eq!(all.as_array().unwrap().len(), 2);
+
+        let on_two = Query::Windows { tag: Some(2) }
+            .answer(&manager.config, &manager.state)
+            .unwrap();
+        let on_two = on_two.as_array().unwrap();
+        assert_eq!(on_two.len(), 1);
+        assert_eq!(on_two[0]["tag"], 2);
+        assert_eq!(on_two[0]["tag_label"], "code");
+    }
+
+    #[test]
+    fn focused_window_is_returned() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let answer = Query::FocusedWindow
+            .answer(&manager.config, &manager.state)
+            .unwrap();
+        assert!(answer.is_null());
+
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        let answer = Query::FocusedWindow
+            .answer(&manager.config, &manager.state)
+            .unwrap();
+        assert_eq!(answer["focused"], true);
+        assert_eq!(answer["handle"]["MockHandle"], 1);
+    }
+
+    #[test]
+    fn workspace_layout_is_returned() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.screen_create_handler(Screen::default());
+        let ws_id = manager.state.workspaces[1].id;
+        let tag_id = manager.state.workspaces[1].tag.unwrap();
+        manager
+            .state
+            .layout_manager
+            .set_layout(ws_id, tag_id, crate::layouts::MONOCLE);
+
+        let answer = Query::Workspaces {
+            workspace: Some(ws_id),
+        }
+        .answer(&manager.config, &manager.state)
+        .unwrap();
+        let answer = answer.as_array().unwrap();
+        assert_eq!(answer.len(), 1);
+        assert_eq!(answer[0]["layout"], crate::layouts::MONOCLE);
+    }
+
+    #[test]
+    fn window_rules_are_explained_by_the_config() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+
+        let answer = Query::ExplainWindowRules {
+            handle: WindowHandle::MockHandle(1),
+        }
+        .answer(&manager.config, &manager.state)
+        .unwrap();
+        assert_eq!(answer, "MockHandle(1) managed: true");
+    }
+
+    #[test]
+    fn parse_queries() {
+        assert_eq!(
+            "Windows 4".parse::<Query>().unwrap(),
+            Query::Windows { tag: Some(4) }
+        );
+        assert_eq!(
+            "Layouts".parse::<Query>().unwrap(),
+            Query::Layouts { workspace: None }
+        );
+        assert_eq!(
+            "FocusedWindow".parse::<Query>().unwrap(),
+            Query::FocusedWindow
+        );
+        assert_eq!(
+            "ExplainWindowRules 0x1a00004".parse::<Query>().unwrap(),
+            Query::ExplainWindowRules {
+                handle: WindowHandle::XlibHandle(0x1a0_0004)
+            }
+        );
+        assert!("Windows four".parse::<Query>().is_err());
+        assert!("NotAQuery".parse::<Query>().is_err());
+    }
+}
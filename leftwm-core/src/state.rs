@@ -2,29 +2,57 @@
 
 use crate::child_process::ChildID;
 use crate::config::{Config, InsertBehavior, ScratchPad};
-use crate::layouts::LayoutManager;
+use crate::layouts::{LayoutManager, SplitTree};
 use crate::models::{
-    FocusManager, Mode, ScratchPadName, Screen, TagId, Tags, Window, WindowHandle, WindowType,
-    Workspace,
+    DetachedWorkspace, FocusManager, Mode, RestoredSession, ScratchPadBinding, ScratchPadName,
+    Screen, Session, SessionWindow, TagId, Tags, TiledResize, Window, WindowHandle, WindowType,
+    Workspace, Xyhw,
 };
-use crate::DisplayAction;
+use crate::{DisplayAction, StateEvent};
 use leftwm_layouts::Layout;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
 
+mod saved_state;
+
+pub use saved_state::{SavedState, STATE_VERSION};
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct State {
     pub screens: Vec<Screen>,
     pub windows: Vec<Window>,
     pub window_history: HashMap<TagId, Vec<WindowHandle>>,
     pub workspaces: Vec<Workspace>,
+    /// Workspaces of disconnected screens, restored when the screen is connected again.
+    #[serde(default)]
+    pub detached_workspaces: Vec<DetachedWorkspace>,
     pub focus_manager: FocusManager,
     pub layout_manager: LayoutManager,
+    /// The windows of each tag arranged by hand, by the manual layout.
+    #[serde(default)]
+    pub split_trees: HashMap<TagId, SplitTree>,
     pub mode: Mode,
     pub layout_definitions: Vec<Layout>,
     pub scratchpads: Vec<ScratchPad>,
-    pub active_scratchpads: HashMap<ScratchPadName, VecDeque<ChildID>>,
+    /// The windows of each scratchpad, in the order they are cycled through.
+    pub active_scratchpads: HashMap<ScratchPadName, VecDeque<WindowHandle>>,
+    /// The scratchpads launched whose window did not appear yet, by the pid of the process
+    /// launched. The first new window with that pid becomes the window of the scratchpad.
+    /// An entry is dropped once its process exits, and is not kept across reloads.
+    #[serde(default)]
+    pub spawned_scratchpads: HashMap<ChildID, ScratchPadName>,
+    /// The scratchpad of each group shown last.
+    #[serde(default)]
+    pub scratchpad_groups: HashMap<String, ScratchPadName>,
     pub actions: VecDeque<DisplayAction>,
+    #[serde(skip)]
+    pub events: VecDeque<StateEvent>,
+    /// The session being restored, its windows are placed as they appear.
+    #[serde(skip)]
+    pub restored_session: RestoredSession,
+    /// The boundary between tiled windows being dragged with the mouse.
+    #[serde(skip)]
+    pub tiled_resize: Option<TiledResize>,
     pub tags: Tags, // List of all known tags.
     pub mousekey: Vec<String>,
     pub default_width: i32,
@@ -42,19 +70,27 @@ impl State {
             tags.add_new(label.as_str());
         });
         tags.add_new_hidden("NSP");
+        tags.add_new_hidden("SWALLOWED");
 
         Self {
             window_history: HashMap::new(),
             focus_manager: FocusManager::new(config),
             layout_manager: LayoutManager::new(config),
+            split_trees: Default::default(),
             scratchpads: config.create_list_of_scratchpads(),
             layout_definitions: config.layout_definitions(),
             screens: Default::default(),
             windows: Default::default(),
             workspaces: Default::default(),
+            detached_workspaces: Default::default(),
             mode: Default::default(),
             active_scratchpads: Default::default(),
+            spawned_scratchpads: Default::default(),
+            scratchpad_groups: Default::default(),
             actions: Default::default(),
+            events: Default::default(),
+            restored_session: Default::default(),
+            tiled_resize: Default::default(),
             tags,
             mousekey: config.mousekey(),
             default_width: config.default_width(),
@@ -136,17 +172,25 @@ impl State {
         }
 
         for tag in self.tags.normal() {
+            // Tags toggled into view are handled together with the tag of their workspace.
+            if self
+                .workspaces
+                .iter()
+                .any(|ws| !ws.has_tag(&tag.id) && ws.shows_tag(&tag.id))
+            {
+                continue;
+            }
+            let workspace = self.workspaces.iter().find(|ws| ws.has_tag(&tag.id));
             let mut windows_on_tag: Vec<&mut Window> = self
                 .windows
                 .iter_mut()
-                .filter(|w| w.tag.unwrap_or(0) == tag.id && w.r#type == WindowType::Normal)
+                .filter(|w| {
+                    workspace.map_or_else(|| w.has_tag(&tag.id), |ws| ws.is_displaying(w))
+                        && w.r#type == WindowType::Normal
+                })
                 .collect();
 
-            let wsid = self
-                .workspaces
-                .iter()
-                .find(|ws| ws.has_tag(&tag.id))
-                .map(|w| w.id);
+            let wsid = workspace.map(|w| w.id);
             let layout = self.layout_manager.layout(wsid.unwrap_or(1), tag.id);
             if layout.is_monocle() {
                 windows_on_tag.iter_mut().for_each(|w| w.border = 0);
@@ -162,7 +206,7 @@ impl State {
 
             windows_on_tag
                 .iter_mut()
-                .for_each(|w| w.border = border_width);
+                .for_each(|w| w.border = w.overrides.border_width.unwrap_or(border_width));
         }
     }
 
@@ -174,6 +218,336 @@ impl State {
         Some(())
     }
 
+    /// The workspace a scratchpad is shown on, its own workspace if it is connected or the focused
+    /// one.
+    pub fn scratchpad_workspace(&self, scratchpad: &ScratchPad) -> Option<&Workspace> {
+        scratchpad
+            .workspace
+            .and_then(|id| self.workspaces.iter().find(|ws| ws.id == id))
+            .or_else(|| self.focus_manager.workspace(&self.workspaces))
+    }
+
+    /// The windows of the scratchpad shown on the tag of its workspace.
+    pub fn shown_scratchpad_windows<'a>(
+        &'a self,
+        scratchpad: &ScratchPad,
+    ) -> impl Iterator<Item = &'a Window> + 'a {
+        let tag = self.scratchpad_workspace(scratchpad).and_then(|ws| ws.tag);
+        self.active_scratchpads
+            .get(&scratchpad.name)
+            .into_iter()
+            .flatten()
+            .filter_map(|handle| self.windows.iter().find(|w| w.handle == *handle))
+            .filter(move |w| tag.map_or(false, |tag| w.has_tag(&tag)))
+    }
+
+    /// The scratchpad a window belongs to.
+    pub fn scratchpad_of(&self, window: &Window) -> Option<&ScratchPad> {
+        let (name, _) = self
+            .active_scratchpads
+            .iter()
+            .find(|(_, handles)| handles.contains(&window.handle))?;
+        self.scratchpads.iter().find(|s| &s.name == name)
+    }
+
+    /// Put a window into the scratchpad with the given name, as if the scratchpad had launched
+    /// it. It floats at the position of the scratchpad and is hidden until the scratchpad is
+    /// toggled. Returns `false` if there is no such scratchpad.
+    pub fn add_to_scratchpad(&mut self, window: &mut Window, name: &ScratchPadName) -> bool {
+        let Some(scratchpad) = self.scratchpads.iter().find(|s| &s.name == name).cloned() else {
+            return false;
+        };
+        if self.scratchpad_of(window).is_none() {
+            self.active_scratchpads
+                .entry(name.clone())
+                .or_default()
+                .push_front(window.handle);
+        }
+        match self.scratchpad_workspace(&scratchpad).map(|ws| ws.xyhw) {
+            Some(workspace) => scratchpad.place(window, &workspace),
+            None => window.set_floating(true),
+        }
+        self.hide_in_scratchpad(window);
+        true
+    }
+
+    /// Move a scratchpad window out of sight, onto the hidden NSP tag.
+    pub(crate) fn hide_in_scratchpad(&self, window: &mut Window) -> bool {
+        let Some(nsp_tag) = self.tags.get_hidden_by_label("NSP") else {
+            return false;
+        };
+        window.untag();
+        window.tag(&nsp_tag.id);
+        window.set_visible(false);
+        true
+    }
+
+    /// The windows of the active scratchpads, ordered by scratchpad name and then in the order
+    /// they are cycled through.
+    pub fn scratchpad_bindings(&self) -> Vec<ScratchPadBinding> {
+        let mut bindings: Vec<ScratchPadBinding> = self
+            .active_scratchpads
+            .iter()
+            .flat_map(|(name, handles)| {
+                handles.iter().filter_map(|handle| {
+                    let window = self.windows.iter().find(|w| w.handle == *handle)?;
+                    Some(ScratchPadBinding {
+                        name: name.clone(),
+                        pid: window.pid,
+                        handle: window.handle,
+                        class: window.res_class.clone(),
+                    })
+                })
+            })
+            .collect();
+        bindings.sort_by(|a, b| a.name.cmp(&b.name));
+        bindings
+    }
+
+    /// Reattach windows to the scratchpads they belonged to before the worker was restarted.
+    ///
+    /// A window is found by its pid or its handle as long as its class is unchanged, or else by
+    /// the `class` and `title` of the scratchpad. Windows already in a scratchpad are skipped, as
+    /// are scratchpads which still have a window, e.g. as they were restored by a soft reload.
+    pub fn reattach_scratchpads(&mut self, bindings: &[ScratchPadBinding]) {
+        let live: Vec<ScratchPadName> = self
+            .active_scratchpads
+            .iter()
+            .filter(|(_, handles)| {
+                handles
+                    .iter()
+                    .any(|handle| self.windows.iter().any(|w| w.handle == *handle))
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        for binding in bindings {
+            if live.contains(&binding.name) {
+                continue;
+            }
+            let Some(scratchpad) = self
+                .scratchpads
+                .iter()
+                .find(|s| s.name == binding.name)
+                .cloned()
+            else {
+                continue;
+            };
+            let is_free = |w: &&Window| {
+                !self
+                    .active_scratchpads
+                    .values()
+                    .any(|handles| handles.contains(&w.handle))
+            };
+            let same_class = |w: &Window| binding.class.is_none() || w.res_class == binding.class;
+            let mut candidates = self.windows.iter().filter(is_free);
+            let Some(window) = candidates
+                .clone()
+                .find(|w| same_class(w) && binding.pid.is_some() && w.pid == binding.pid)
+                .or_else(|| {
+                    candidates
+                        .clone()
+                        .find(|w| same_class(w) && w.handle == binding.handle)
+                })
+                .or_else(|| candidates.find(|w| scratchpad.matches(w)))
+            else {
+                continue;
+            };
+            let handle = window.handle;
+            let workspace = self
+                .workspaces
+                .iter()
+                .find(|ws| ws.is_displaying(window))
+                .map(|ws| ws.xyhw);
+
+            tracing::info!(
+                "Reattaching window {:?} to scratchpad {:?}",
+                handle,
+                binding.name
+            );
+            self.active_scratchpads
+                .entry(binding.name.clone())
+                .or_default()
+                .push_back(handle);
+            if let Some(window) = self.windows.iter_mut().find(|w| w.handle == handle) {
+                match workspace {
+                    Some(workspace) => scratchpad.place(window, &workspace),
+                    None => window.set_floating(true),
+                }
+            }
+        }
+        self.sort_windows();
+    }
+
+    /// The placement of the managed windows, kept across logins.
+    pub fn session(&self) -> Session {
+        let mut slots: HashMap<TagId, usize> = HashMap::new();
+        let mut windows = vec![];
+        for window in &self.windows {
+            let Some(tag) = window.tag else {
+                continue;
+            };
+            // Every tiled window takes a slot, as it does in the layout.
+            let mut slot = 0;
+            if window.is_normal() && !window.floating() {
+                let next = slots.entry(tag).or_default();
+                slot = *next;
+                *next += 1;
+            }
+            let label = self.tags.get(tag).map(|tag| tag.label.as_str());
+            if let Some(label) = label.filter(|_| self.is_session_window(window)) {
+                windows.extend(SessionWindow::new(window, label, slot));
+            }
+        }
+        Session { windows }
+    }
+
+    /// Restore the placement of a session. The managed windows are placed right away, the
+    /// others as they appear.
+    pub fn restore_session(&mut self, mut session: Session) {
+        session
+            .windows
+            .retain(|w| self.session_tag(&w.tag).is_some());
+        self.restored_session = RestoredSession::default();
+
+        let candidates: Vec<Window> = self
+            .windows
+            .iter()
+            .filter(|w| self.is_session_window(w))
+            .cloned()
+            .collect();
+        // Windows with an unchanged title are matched first, so they get their own entry.
+        let mut matched: Vec<(Window, SessionWindow)> = vec![];
+        let mut unmatched = vec![];
+        for window in candidates {
+            match session.take_exact(&window) {
+                Some(entry) => matched.push((window, entry)),
+                None => unmatched.push(window),
+            }
+        }
+        for window in unmatched {
+            if let Some(entry) = session.take(&window) {
+                matched.push((window, entry));
+            }
+        }
+
+        matched.sort_by_key(|(_, entry)| entry.slot);
+        for (mut window, entry) in matched {
+            let Some(tag) = self.session_tag(&entry.tag) else {
+                continue;
+            };
+            let workspace = self.session_workspace(tag);
+            entry.apply(&mut window, tag, workspace.as_ref());
+            let handle = window.handle;
+            let act = DisplayAction::SetWindowTag(handle, window.tag);
+            self.actions.push_back(act);
+            if let Some(w) = self.windows.iter_mut().find(|w| w.handle == handle) {
+                *w = window;
+            }
+            self.restore_slot(handle, entry.slot);
+        }
+        self.restored_session.pending = session;
+        self.sort_windows();
+    }
+
+    /// Take the entry of the restored session matching a window which just appeared, together
+    /// with the tag it is placed on.
+    pub(crate) fn take_session_window(
+        &mut self,
+        window: &Window,
+    ) -> Option<(SessionWindow, TagId)> {
+        if !self.is_session_window(window) {
+            return None;
+        }
+        let entry = self.restored_session.pending.take(window)?;
+        let tag = self.session_tag(&entry.tag)?;
+        Some((entry, tag))
+    }
+
+    /// The tag a window of a session is placed on, the normal tag with the saved label.
+    pub(crate) fn session_tag(&self, label: &str) -> Option<TagId> {
+        self.tags.get_normal_by_label(label).map(|tag| tag.id)
+    }
+
+    /// The geometry of the workspace a window of a session is placed on, the one showing its
+    /// tag or else the focused one.
+    pub(crate) fn session_workspace(&self, tag: TagId) -> Option<Xyhw> {
+        self.workspaces
+            .iter()
+            .find(|ws| ws.shows_tag(&tag))
+            .or_else(|| self.focus_manager.workspace(&self.workspaces))
+            .map(|ws| ws.xyhw)
+    }
+
+    /// Move a restored window among the tiled windows of its tag, after the restored windows
+    /// with a lower slot and before the ones with a higher slot.
+    pub(crate) fn restore_slot(&mut self, handle: WindowHandle, slot: usize) {
+        let windows = &self.windows;
+        let slots = &mut self.restored_session.slots;
+        slots.retain(|(h, _)| *h != handle && windows.iter().any(|w| w.handle == *h));
+        slots.push((handle, slot));
+
+        let Some(index) = self.windows.iter().position(|w| w.handle == handle) else {
+            return;
+        };
+        let window = self.windows.remove(index);
+        let slot_of = |w: &Window| {
+            let is_tiled = w.tag == window.tag && w.is_normal() && !w.floating();
+            slots
+                .iter()
+                .find(|(h, _)| is_tiled && *h == w.handle)
+                .map(|(_, slot)| *slot)
+        };
+        let position = self
+            .windows
+            .iter()
+            .position(|w| slot_of(w).map_or(false, |s| s > slot))
+            .or_else(|| {
+                self.windows
+                    .iter()
+                    .rposition(|w| slot_of(w).map_or(false, |s| s < slot))
+                    .map(|i| i + 1)
+            })
+            .unwrap_or(index);
+        self.windows.insert(position, window);
+    }
+
+    /// Whether the placement of the window is kept in a session. Dialogs, scratchpads and
+    /// windows on hidden tags are left out.
+    fn is_session_window(&self, window: &Window) -> bool {
+        window.is_normal()
+            && window.transient.is_none()
+            && window
+                .tag
+                .and_then(|tag| self.tags.get(tag))
+                .map_or(true, |tag| !tag.hidden)
+            && self.scratchpad_of(window).is_none()
+    }
+
+    /// Move the floating windows shown on a workspace to the workspace with the geometry `to`.
+    /// They keep their size and their place relative to the workspace, as far as they fit.
+    pub(crate) fn move_floating_windows(&mut self, workspace: &Workspace, to: Xyhw) {
+        let floating =
+            |w: &&mut Window| w.floating() && w.is_managed() && workspace.is_displaying(w);
+        for window in self.windows.iter_mut().filter(floating) {
+            let from = window.normal;
+            let mut xyhw = window.exact_xyhw();
+            let x = (xyhw.x() - from.x()).clamp(0, (to.w() - xyhw.w()).max(0));
+            let y = (xyhw.y() - from.y()).clamp(0, (to.h() - xyhw.h()).max(0));
+            xyhw.set_x(to.x() + x);
+            xyhw.set_y(to.y() + y);
+            window.normal = to;
+            window.set_floating_exact(xyhw);
+        }
+    }
+
+    /// Find a known screen by its ID, or by its output name if it has no ID.
+    pub(crate) fn screen_index(&self, screen: &Screen) -> Option<usize> {
+        self.screens.iter().position(|s| match screen.id {
+            Some(id) => s.id == Some(id),
+            None => s.output == screen.output,
+        })
+    }
+
     pub fn update_static(&mut self) {
         let workspaces = self.workspaces.clone();
         self.windows
@@ -201,8 +575,11 @@ impl State {
     }
 
     /// Apply saved state to a running manager.
-    pub fn restore_state(&mut self, old_state: &Self) {
+    ///
+    /// Returns a description of each part of the old state which could not be restored.
+    pub fn restore_state(&mut self, old_state: &Self) -> Vec<String> {
         tracing::debug!("Restoring old state");
+        let mut lost = vec![];
 
         // Restore tags.
         for old_tag in old_state.tags.all() {
@@ -216,6 +593,7 @@ impl State {
         // Restore windows.
         let mut ordered = vec![];
         let mut had_strut = false;
+        let mut untagged = 0;
         old_state.windows.iter().for_each(|old_window| {
             if let Some((index, new_window)) = self
                 .windows
@@ -233,17 +611,28 @@ impl State {
                 new_window.normal = old_window.normal;
                 if are_tags_equal {
                     new_window.tag = old_window.tag;
+                    new_window.extra_tags = old_window.extra_tags.clone();
                 } else {
                     let mut new_tag = old_window.tag;
                     // Only retain the tag if it still exists, otherwise default to tag 1
                     match new_tag {
                         Some(tag) if self.tags.get(tag).is_some() => {}
-                        _ => new_tag = Some(1),
+                        _ => {
+                            new_tag = Some(1);
+                            untagged += 1;
+                        }
                     }
                     new_window.untag();
                     new_tag.iter().for_each(|&tag_id| new_window.tag(&tag_id));
                 }
                 new_window.strut = old_window.strut;
+                new_window.swallowed = old_window.swallowed;
+                new_window.weights = old_window.weights.clone();
+                if !are_tags_equal {
+                    new_window
+                        .weights
+                        .retain(|&id, _| self.tags.get(id).is_some());
+                }
                 new_window.set_states(old_window.states());
                 ordered.push(new_window.clone());
                 self.windows.remove(index);
@@ -257,6 +646,11 @@ impl State {
             self.update_static();
         }
         self.windows.append(&mut ordered);
+        if untagged > 0 {
+            lost.push(format!(
+                "the tags of {untagged} windows, as their tags were removed"
+            ));
+        }
 
         // This is needed due to mutable/immutable borrows.
         let all_tags = &self.tags;
@@ -268,6 +662,7 @@ impl State {
                 workspace.margin_multiplier = old_workspace.margin_multiplier;
                 if are_tags_equal {
                     workspace.tag = old_workspace.tag;
+                    workspace.extra_tags = old_workspace.extra_tags.clone();
                 } else {
                     let mut new_tag = old_workspace.tag;
                     // Only retain the tag if it still exists, otherwise default to tag 1
@@ -282,11 +677,24 @@ impl State {
             }
         }
 
+        // Restore the workspaces of screens which are still disconnected.
+        self.detached_workspaces = old_state
+            .detached_workspaces
+            .iter()
+            .filter(|detached| !self.screens.iter().any(|s| s.output == detached.output))
+            .cloned()
+            .collect();
+
         // Restore scratchpads.
         for (scratchpad, id) in &old_state.active_scratchpads {
             self.active_scratchpads
                 .insert(scratchpad.clone(), id.clone());
         }
+        self.scratchpad_groups = old_state.scratchpad_groups.clone();
+
+        // Restore the windows arranged by hand.
+        self.split_trees = old_state.split_trees.clone();
+        self.split_trees.retain(|&id, _| all_tags.get(id).is_some());
 
         // Restore focus.
         self.focus_manager.tags_last_window = old_state.focus_manager.tags_last_window.clone();
@@ -308,7 +716,17 @@ impl State {
         self.focus_tag(&tag_id);
 
         // Restore layout manager
-        self.layout_manager.restore(&old_state.layout_manager);
+        let workspaces = &self.workspaces;
+        lost.extend(
+            self.layout_manager
+                .restore(&old_state.layout_manager, |tag| {
+                    workspaces
+                        .iter()
+                        .find(|ws| ws.tag == Some(tag))
+                        .map(|ws| ws.id)
+                }),
+        );
+        lost
     }
 }
 
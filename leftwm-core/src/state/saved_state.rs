@@ -0,0 +1,223 @@
+//! The versioned format in which the state is saved across a soft reload.
+
+use super::State;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Version of the format of the saved state.
+///
+/// Raise it together with a migration in [`MIGRATIONS`] whenever the state changes in a way which
+/// cannot be handled by the defaults of its fields.
+pub const STATE_VERSION: u32 = 2;
+
+/// The migration at index `n` turns a state of version `n` into one of version `n + 1`.
+const MIGRATIONS: [fn(&mut Map<String, Value>); STATE_VERSION as usize] = [
+    // States saved before they were versioned only lack the version.
+    |_| {},
+    // The windows of the scratchpads were kept by their pid instead of their handle.
+    |state| {
+        let windows = match state.get("windows") {
+            Some(Value::Array(windows)) => windows.clone(),
+            _ => vec![],
+        };
+        let Some(Value::Object(scratchpads)) = state.get_mut("active_scratchpads") else {
+            return;
+        };
+        for scratchpad in scratchpads.values_mut() {
+            let handles = match scratchpad {
+                Value::Array(pids) => pids
+                    .iter()
+                    .filter_map(|pid| windows.iter().find(|w| w.get("pid") == Some(pid)))
+                    .filter_map(|w| w.get("handle").cloned())
+                    .collect(),
+                _ => vec![],
+            };
+            *scratchpad = Value::Array(handles);
+        }
+    },
+];
+
+/// The state as it is saved, together with the version of its format.
+#[derive(Serialize, Debug)]
+pub struct SavedState<'a> {
+    version: u32,
+    state: &'a State,
+}
+
+impl State {
+    /// The state in the versioned format it is saved in across a soft reload.
+    pub fn saved(&self) -> SavedState<'_> {
+        SavedState {
+            version: STATE_VERSION,
+            state: self,
+        }
+    }
+
+    /// Restore a state saved by [`State::saved`], possibly by an older version of leftwm.
+    ///
+    /// The saved state is migrated to the current format first. Parts of it which still cannot
+    /// be read are left out, so that the rest of it is restored. Returns a description of each
+    /// part which could not be restored.
+    pub fn restore_saved(&mut self, saved: Value) -> Vec<String> {
+        let mut lost = vec![];
+        let (version, old) = match saved {
+            Value::Object(mut saved) if saved.contains_key("version") => {
+                let version = saved
+                    .get("version")
+                    .and_then(Value::as_u64)
+                    .unwrap_or_default();
+                (version, saved.remove("state").unwrap_or_default())
+            }
+            // Saved before the state was versioned.
+            old => (0, old),
+        };
+        let Value::Object(mut old) = old else {
+            return vec!["the whole state, it is not an object".to_string()];
+        };
+        if version > u64::from(STATE_VERSION) {
+            lost.push(format!(
+                "what is new in version {version} of the state, it is newer than this leftwm"
+            ));
+        }
+        for migration in MIGRATIONS.iter().skip(version as usize) {
+            migration(&mut old);
+        }
+
+        let old_state = match serde_json::from_value(Value::Object(old.clone())) {
+            Ok(old_state) => old_state,
+            Err(err) => {
+                tracing::debug!("Restoring the readable parts of the old state: {}", err);
+                match self.readable_parts(old, &mut lost) {
+                    Some(old_state) => old_state,
+                    None => {
+                        lost.push("the whole state".to_string());
+                        return lost;
+                    }
+                }
+            }
+        };
+        lost.extend(self.restore_state(&old_state));
+        lost
+    }
+
+    /// Read an old state, with each of its fields which cannot be read replaced by the one of
+    /// this state. The items of a list which can be read are kept, e.g. the readable windows.
+    fn readable_parts(&self, old: Map<String, Value>, lost: &mut Vec<String>) -> Option<State> {
+        let Ok(Value::Object(current)) = serde_json::to_value(&*self) else {
+            return None;
+        };
+        let reads = |candidate: &Map<String, Value>| {
+            serde_json::from_value::<State>(Value::Object(candidate.clone())).is_ok()
+        };
+        let mut merged = current.clone();
+        for (key, value) in old {
+            // Fields which are no longer part of the state.
+            if !current.contains_key(&key) {
+                continue;
+            }
+            let mut candidate = current.clone();
+            candidate.insert(key.clone(), value.clone());
+            if reads(&candidate) {
+                merged.insert(key, value);
+                continue;
+            }
+            if let Value::Array(items) = value {
+                let total = items.len();
+                let readable: Vec<Value> = items
+                    .into_iter()
+                    .filter(|item| {
+                        candidate.insert(key.clone(), Value::Array(vec![item.clone()]));
+                        reads(&candidate)
+                    })
+                    .collect();
+                lost.push(format!("{} of the {total} {key}", total - readable.len()));
+                merged.insert(key, Value::Array(readable));
+            } else {
+                lost.push(format!("the {key}"));
+            }
+        }
+        serde_json::from_value(Value::Object(merged)).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{ScratchPadName, Screen, Window, WindowHandle};
+    use crate::Manager;
+    use serde_json::json;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn scratchpads_of_version_1_are_kept_by_handle() {
+        let mut old = Manager::new_test(vec!["1".to_string()]);
+        old.screen_create_handler(Screen::default());
+        old.window_created_handler(
+            Window::new(WindowHandle::MockHandle(3), None, Some(42)),
+            -1,
+            -1,
+        );
+        let mut saved = serde_json::to_value(old.state.saved()).unwrap();
+        saved["version"] = json!(1);
+        saved["state"]["active_scratchpads"] = json!({ "Terminal": [42, 7] });
+
+        let mut new = Manager::new_test(vec!["1".to_string()]);
+        new.screen_create_handler(Screen::default());
+        new.window_created_handler(
+            Window::new(WindowHandle::MockHandle(3), None, Some(42)),
+            -1,
+            -1,
+        );
+        assert!(new.state.restore_saved(saved).is_empty());
+
+        assert_eq!(
+            new.state
+                .active_scratchpads
+                .get(&ScratchPadName::from("Terminal")),
+            Some(&VecDeque::from([WindowHandle::MockHandle(3)]))
+        );
+    }
+
+    #[test]
+    fn unreadable_parts_of_the_state_are_left_out() {
+        let mut old = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        old.screen_create_handler(Screen::default());
+        for id in 1..=2 {
+            old.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, None),
+                -1,
+                -1,
+            );
+        }
+        old.state.windows[1].tag(&2);
+        let mut saved = serde_json::to_value(old.state.saved()).unwrap();
+        // An older or broken format of the focus manager and of a single window.
+        saved["state"]["focus_manager"] = json!("unknown");
+        saved["state"]["windows"][0]["tag"] = json!("first");
+
+        let mut new = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        new.screen_create_handler(Screen::default());
+        for id in 1..=2 {
+            new.window_created_handler(
+                Window::new(WindowHandle::MockHandle(id), None, None),
+                -1,
+                -1,
+            );
+        }
+        let lost = new.state.restore_saved(saved);
+
+        assert_eq!(
+            lost,
+            vec![
+                "the focus_manager".to_string(),
+                "1 of the 2 windows".to_string()
+            ]
+        );
+        let restored = new
+            .state
+            .windows
+            .iter()
+            .find(|w| w.handle == old.state.windows[1].handle)
+            .unwrap();
+        assert_eq!(restored.tag, Some(2));
+    }
+}
@@ -0,0 +1,79 @@
+use crate::models::{TagId, WindowHandle, WorkspaceId};
+use serde::{Deserialize, Serialize};
+
+/// Something that happened to the state of the window manager.
+///
+/// Handlers push these into `State::events`, from where the event loop forwards them to the
+/// clients subscribed to the `EventSocket`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum StateEvent {
+    WindowCreated {
+        handle: WindowHandle,
+        title: Option<String>,
+        res_class: Option<String>,
+        tag: Option<TagId>,
+    },
+    WindowDestroyed {
+        handle: WindowHandle,
+    },
+    /// `handle` is `None` when no window has the focus anymore.
+    FocusChanged {
+        handle: Option<WindowHandle>,
+    },
+    TagSwitched {
+        tag: TagId,
+    },
+    LayoutChanged {
+        workspace: WorkspaceId,
+        tag: TagId,
+        layout: String,
+    },
+    UrgentRaised {
+        handle: WindowHandle,
+        tag: Option<TagId>,
+    },
+    ScreenAdded {
+        workspace: WorkspaceId,
+        output: String,
+    },
+    ScreenRemoved {
+        workspace: WorkspaceId,
+        output: String,
+    },
+    ScratchpadToggled {
+        name: String,
+        visible: bool,
+    },
+}
+
+/// The kinds of [`StateEvent`], used by clients to filter the events they receive.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum StateEventKind {
+    WindowCreated,
+    WindowDestroyed,
+    FocusChanged,
+    TagSwitched,
+    LayoutChanged,
+    UrgentRaised,
+    ScreenAdded,
+    ScreenRemoved,
+    ScratchpadToggled,
+}
+
+impl StateEvent {
+    pub const fn kind(&self) -> StateEventKind {
+        match self {
+            Self::WindowCreated { .. } => StateEventKind::WindowCreated,
+            Self::WindowDestroyed { .. } => StateEventKind::WindowDestroyed,
+            Self::FocusChanged { .. } => StateEventKind::FocusChanged,
+            Self::TagSwitched { .. } => StateEventKind::TagSwitched,
+            Self::LayoutChanged { .. } => StateEventKind::LayoutChanged,
+            Self::UrgentRaised { .. } => StateEventKind::UrgentRaised,
+            Self::ScreenAdded { .. } => StateEventKind::ScreenAdded,
+            Self::ScreenRemoved { .. } => StateEventKind::ScreenRemoved,
+            Self::ScratchpadToggled { .. } => StateEventKind::ScratchpadToggled,
+        }
+    }
+}
@@ -1,6 +1,8 @@
 //! Various shared functions that `LeftWM` uses.
 pub mod child_process;
 pub mod command_pipe;
+pub mod command_socket;
+pub mod event_socket;
 pub mod helpers;
 pub mod modmask_lookup;
 pub mod return_pipe;
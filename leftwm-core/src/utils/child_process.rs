@@ -312,6 +312,11 @@ impl Children {
     pub fn is_empty(&self) -> bool {
         self.inner.is_empty()
     }
+    /// Returns true if the process was launched by us and not reaped yet.
+    #[must_use]
+    pub fn contains(&self, id: ChildID) -> bool {
+        self.inner.contains_key(&id)
+    }
     /// Insert a `Child` in the `Children`.
     ///
     /// # Returns
@@ -355,6 +360,14 @@ pub fn register_child_hook(flag: Arc<AtomicBool>) {
         .map_err(|err| tracing::error!("Cannot register SIGCHLD signal handler: {:?}", err));
 }
 
+/// The parent of a process, read from `/proc/<pid>/stat`.
+pub fn parent_pid(pid: u32) -> Option<u32> {
+    let stat = fs::read(format!("/proc/{pid}/stat")).ok()?;
+    let ppid_bytes = stat.split(|&c| c == b' ').nth(3)?;
+    let ppid_str = std::str::from_utf8(ppid_bytes).ok()?;
+    ppid_str.parse().ok()
+}
+
 /// Sends command to shell for execution
 /// Assumes STDIN/STDOUT unwanted.
 pub fn exec_shell(command: &str, children: &mut Children) -> Option<ChildID> {
@@ -1,5 +1,6 @@
 //! Creates a pipe to listen for external commands.
-use crate::models::TagId;
+use crate::layouts::SplitDirection;
+use crate::models::{Side, TagId};
 use crate::utils::return_pipe::ReturnPipe;
 use crate::{command, Command, ReleaseScratchPadOption};
 use std::error::Error;
@@ -125,13 +126,21 @@ async fn read_from_pipe(pipe_file: &Path, tx: &mpsc::UnboundedSender<Command>) -
     Some(())
 }
 
-fn parse_command(s: &str) -> Result<Command, Box<dyn std::error::Error>> {
+/// Parse the textual form of a command, as written to the pipe, into a [`Command`].
+/// Unknown commands are passed on as [`Command::Other`].
+/// # Errors
+///
+/// Will error if a known command is given missing or invalid arguments.
+pub fn parse_command(s: &str) -> Result<Command, Box<dyn std::error::Error>> {
     let (head, rest) = s.split_once(' ').unwrap_or((s, ""));
     match head {
         // Move Window
         "MoveWindowDown" => Ok(Command::MoveWindowDown),
         "MoveWindowTop" => build_move_window_top(rest),
         "SwapWindowTop" => build_swap_window_top(rest),
+        "SwapWindowInDirection" => Ok(Command::SwapWindowInDirection {
+            direction: side(rest)?,
+        }),
         "MoveWindowUp" => Ok(Command::MoveWindowUp),
         "MoveWindowToNextTag" => build_move_window_to_next_tag(rest),
         "MoveWindowToPreviousTag" => build_move_window_to_previous_tag(rest),
@@ -143,6 +152,9 @@ fn parse_command(s: &str) -> Result<Command, Box<dyn std::error::Error>> {
         "FocusWindowDown" => Ok(Command::FocusWindowDown),
         "FocusWindowTop" => build_focus_window_top(rest),
         "FocusWindowUp" => Ok(Command::FocusWindowUp),
+        "FocusWindowInDirection" => Ok(Command::FocusWindowInDirection {
+            direction: side(rest)?,
+        }),
         "FocusNextTag" => build_focus_next_tag(rest),
         "FocusPreviousTag" => build_focus_previous_tag(rest),
         "FocusWorkspaceNext" => Ok(Command::FocusWorkspaceNext),
@@ -152,10 +164,18 @@ fn parse_command(s: &str) -> Result<Command, Box<dyn std::error::Error>> {
         "IncreaseMainWidth" | "IncreaseMainSize" => build_increase_main_size(rest), // 'IncreaseMainWidth' deprecated
         "DecreaseMainCount" => Ok(Command::DecreaseMainCount()),
         "IncreaseMainCount" => Ok(Command::IncreaseMainCount()),
+        "IncreaseWindowWeight" => Ok(Command::IncreaseWindowWeight(weight_change(rest)?)),
+        "DecreaseWindowWeight" => Ok(Command::DecreaseWindowWeight(weight_change(rest)?)),
+        "ResetWindowWeights" => Ok(Command::ResetWindowWeights),
         "NextLayout" => Ok(Command::NextLayout),
         "PreviousLayout" => Ok(Command::PreviousLayout),
         "RotateTag" => Ok(Command::RotateTag),
         "SetLayout" => build_set_layout(rest),
+        "SplitWindow" => build_split_window(rest),
+        "ResizeSplit" => build_resize_split(rest),
+        "MoveWindowInTree" => Ok(Command::MoveWindowInTree {
+            direction: side(rest)?,
+        }),
         "SetMarginMultiplier" => build_set_margin_multiplier(rest),
         // Scratchpad
         "ToggleScratchPad" => build_toggle_scratchpad(rest),
@@ -167,6 +187,7 @@ fn parse_command(s: &str) -> Result<Command, Box<dyn std::error::Error>> {
         "PrevScratchPadWindow" => Ok(Command::PrevScratchPadWindow {
             scratchpad: rest.to_owned().into(),
         }),
+        "CycleScratchPadGroup" => build_cycle_scratchpad_group(rest),
         // Floating
         "FloatingToTile" => Ok(Command::FloatingToTile),
         "TileToFloating" => Ok(Command::TileToFloating),
@@ -174,7 +195,13 @@ fn parse_command(s: &str) -> Result<Command, Box<dyn std::error::Error>> {
         // Workspace/Tag
         "GoToTag" => build_go_to_tag(rest),
         "ReturnToLastTag" => Ok(Command::ReturnToLastTag),
+        "ToggleTagView" => build_toggle_tag_view(rest),
+        "ToggleWindowTags" => build_toggle_window_tags(rest),
         "SendWorkspaceToTag" => build_send_workspace_to_tag(rest),
+        "AddTag" => build_add_tag(rest),
+        "RemoveTag" => build_remove_tag(rest),
+        "RenameTag" => build_rename_tag(rest),
+        "MoveTag" => build_move_tag(rest),
         "SwapScreens" => Ok(Command::SwapScreens),
         "ToggleFullScreen" => Ok(Command::ToggleFullScreen),
         "ToggleMaximized" => Ok(Command::ToggleMaximized),
@@ -182,6 +209,12 @@ fn parse_command(s: &str) -> Result<Command, Box<dyn std::error::Error>> {
         // General
         "CloseWindow" => Ok(Command::CloseWindow),
         "CloseAllOtherWindows" => Ok(Command::CloseAllOtherWindows),
+        "SaveSession" => Ok(Command::SaveSession {
+            name: session_name(rest)?,
+        }),
+        "RestoreSession" => Ok(Command::RestoreSession {
+            name: session_name(rest)?,
+        }),
         "SoftReload" => Ok(Command::SoftReload),
         _ => Ok(Command::Other(s.into())),
     }
@@ -218,6 +251,15 @@ fn build_release_scratchpad(raw: &str) -> Command {
     }
 }
 
+fn build_cycle_scratchpad_group(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    if raw.is_empty() {
+        return Err("missing argument group's name".into());
+    }
+    Ok(Command::CycleScratchPadGroup {
+        group: raw.to_owned(),
+    })
+}
+
 fn build_toggle_scratchpad(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
     let name = if raw.is_empty() {
         return Err("missing argument scratchpad's name".into());
@@ -242,6 +284,29 @@ fn build_go_to_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
     Ok(Command::GoToTag { tag, swap })
 }
 
+fn build_toggle_tag_view(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let tag = if raw.is_empty() {
+        return Err("missing argument tag_id".into());
+    } else {
+        match TagId::from_str(raw) {
+            Ok(tag) => tag,
+            Err(_) => Err("argument tag_id was not a valid tag number")?,
+        }
+    };
+    Ok(Command::ToggleTagView { tag })
+}
+
+fn build_toggle_window_tags(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    if raw.is_empty() {
+        return Err("missing argument tag_id".into());
+    }
+    let tags = match raw.split_whitespace().map(TagId::from_str).collect() {
+        Ok(tags) => tags,
+        Err(_) => Err("argument tag_id was not a valid tag number")?,
+    };
+    Ok(Command::ToggleWindowTags { window: None, tags })
+}
+
 fn build_send_window_to_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
     let tag_id = if raw.is_empty() {
         return Err("missing argument tag_id".into());
@@ -277,6 +342,67 @@ fn build_send_workspace_to_tag(raw: &str) -> Result<Command, Box<dyn std::error:
     Ok(Command::SendWorkspaceToTag(ws_index, tag_index))
 }
 
+fn build_add_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    if raw.is_empty() {
+        return Err("missing argument label".into());
+    }
+    Ok(Command::AddTag {
+        label: raw.to_owned(),
+    })
+}
+
+fn build_remove_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let tag = if raw.is_empty() {
+        return Err("missing argument tag_id".into());
+    } else {
+        match TagId::from_str(raw) {
+            Ok(tag) => tag,
+            Err(_) => Err("argument tag_id was not a valid tag number")?,
+        }
+    };
+    Ok(Command::RemoveTag { tag })
+}
+
+fn build_rename_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let (tag, label) = raw.split_once(' ').ok_or("missing argument label")?;
+    let tag: TagId = match tag.parse() {
+        Ok(tag) => tag,
+        Err(_) => Err("argument tag_id was not a valid tag number")?,
+    };
+    let label = label.trim();
+    if label.is_empty() {
+        return Err("missing argument label".into());
+    }
+    Ok(Command::RenameTag {
+        tag,
+        label: label.to_owned(),
+    })
+}
+
+fn build_move_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let mut parts = raw.split(' ');
+    let tag: TagId = match parts.next().ok_or("missing argument tag_id")?.parse() {
+        Ok(tag) => tag,
+        Err(_) => Err("argument tag_id was missing or not a valid tag number")?,
+    };
+    let position: TagId = match parts.next().ok_or("missing argument position")?.parse() {
+        Ok(position) => position,
+        Err(_) => Err("argument position was not a valid tag number")?,
+    };
+    Ok(Command::MoveTag { tag, position })
+}
+
+/// The name of a session is used as its file name.
+fn session_name(raw: &str) -> Result<String, Box<dyn std::error::Error>> {
+    if raw.is_empty() {
+        return Err("missing argument session's name".into());
+    }
+    if raw.contains('/') || raw.starts_with('.') {
+        return Err("argument session's name was not a valid file name".into());
+    }
+    Ok(raw.to_owned())
+}
+
 fn build_set_layout(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
     let layout_name = if raw.is_empty() {
         return Err("missing layout name".into());
@@ -286,6 +412,36 @@ fn build_set_layout(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
     Ok(Command::SetLayout(String::from(layout_name)))
 }
 
+fn build_split_window(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let direction = match raw {
+        "" => return Err("missing argument direction".into()),
+        "Horizontal" => SplitDirection::Horizontal,
+        "Vertical" => SplitDirection::Vertical,
+        _ => Err("argument direction was not Horizontal or Vertical")?,
+    };
+    Ok(Command::SplitWindow { direction })
+}
+
+fn build_resize_split(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let change: i32 = match raw.parse() {
+        Ok(num) => num,
+        Err(_) => Err("argument change was missing or invalid")?,
+    };
+    Ok(Command::ResizeSplit(change))
+}
+
+/// The side a directional command acts towards.
+fn side(raw: &str) -> Result<Side, Box<dyn std::error::Error>> {
+    match raw {
+        "" => Err("missing argument direction".into()),
+        "Left" => Ok(Side::Left),
+        "Right" => Ok(Side::Right),
+        "Top" => Ok(Side::Top),
+        "Bottom" => Ok(Side::Bottom),
+        _ => Err("argument direction was not Left, Right, Top or Bottom".into()),
+    }
+}
+
 fn build_set_margin_multiplier(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
     let margin_multiplier = if raw.is_empty() {
         return Err("missing argument multiplier".into());
@@ -364,6 +520,14 @@ fn build_increase_main_size(raw: &str) -> Result<Command, Box<dyn std::error::Er
     Ok(Command::IncreaseMainSize(change))
 }
 
+/// The percentage a window weight command changes the weight by.
+fn weight_change(raw: &str) -> Result<i32, Box<dyn std::error::Error>> {
+    match raw.parse() {
+        Ok(num) => Ok(num),
+        Err(_) => Err("argument change was missing or invalid".into()),
+    }
+}
+
 fn build_decrease_main_size(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
     let mut parts = raw.split(' ');
     let change: i32 = match parts.next().ok_or("missing argument change")?.parse() {
@@ -529,6 +693,11 @@ mod test {
         assert!(build_toggle_scratchpad("").is_err());
     }
 
+    #[test]
+    fn build_cycle_scratchpad_group_without_parameter() {
+        assert!(build_cycle_scratchpad_group("").is_err());
+    }
+
     #[test]
     fn build_send_window_to_tag_without_parameter() {
         assert!(build_send_window_to_tag("").is_err());
@@ -539,6 +708,96 @@ mod test {
         assert!(build_send_workspace_to_tag("").is_err());
     }
 
+    #[test]
+    fn build_toggle_window_tags_with_several_tags() {
+        assert_eq!(
+            build_toggle_window_tags("2 3").unwrap(),
+            Command::ToggleWindowTags {
+                window: None,
+                tags: vec![2, 3],
+            }
+        );
+        assert!(build_toggle_window_tags("").is_err());
+        assert!(build_toggle_window_tags("2 three").is_err());
+        assert!(build_toggle_tag_view("").is_err());
+    }
+
+    #[test]
+    fn build_tag_commands_without_parameter() {
+        assert!(build_add_tag("").is_err());
+        assert!(build_remove_tag("").is_err());
+        assert!(build_rename_tag("2").is_err());
+        assert!(build_move_tag("2").is_err());
+    }
+
+    #[test]
+    fn build_rename_tag_with_spaces_in_label() {
+        assert_eq!(
+            build_rename_tag("2 web stuff").unwrap(),
+            Command::RenameTag {
+                tag: 2,
+                label: String::from("web stuff"),
+            }
+        );
+    }
+
+    #[test]
+    fn session_name_is_a_file_name() {
+        assert_eq!(session_name("work").unwrap(), "work");
+        assert!(session_name("").is_err());
+        assert!(session_name("../work").is_err());
+    }
+
+    #[test]
+    fn build_split_tree_commands() {
+        assert_eq!(
+            build_split_window("Vertical").unwrap(),
+            Command::SplitWindow {
+                direction: SplitDirection::Vertical
+            }
+        );
+        assert_eq!(
+            parse_command("MoveWindowInTree Left").unwrap(),
+            Command::MoveWindowInTree {
+                direction: Side::Left
+            }
+        );
+        assert!(build_split_window("").is_err());
+        assert!(parse_command("MoveWindowInTree Up").is_err());
+        assert!(build_resize_split("").is_err());
+    }
+
+    #[test]
+    fn directional_commands_take_a_side() {
+        assert_eq!(
+            parse_command("FocusWindowInDirection Left").unwrap(),
+            Command::FocusWindowInDirection {
+                direction: Side::Left
+            }
+        );
+        assert_eq!(
+            parse_command("SwapWindowInDirection Bottom").unwrap(),
+            Command::SwapWindowInDirection {
+                direction: Side::Bottom
+            }
+        );
+        assert!(parse_command("FocusWindowInDirection Up").is_err());
+        assert!(parse_command("FocusWindowInDirection").is_err());
+    }
+
+    #[test]
+    fn window_weight_commands_take_a_percentage() {
+        assert_eq!(
+            parse_command("IncreaseWindowWeight 50").unwrap(),
+            Command::IncreaseWindowWeight(50)
+        );
+        assert_eq!(
+            parse_command("ResetWindowWeights").unwrap(),
+            Command::ResetWindowWeights
+        );
+        assert!(parse_command("DecreaseWindowWeight").is_err());
+    }
+
     #[test]
     fn build_set_layout_without_parameter() {
         assert!(build_set_layout("").is_err());
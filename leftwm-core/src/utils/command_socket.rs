@@ -0,0 +1,323 @@
+//! Creates a Unix socket to receive external commands and reply to them.
+//!
+//! Every line written to the socket is a JSON encoded [`Request`]. Each request is answered on
+//! the same connection with a single line containing a JSON encoded [`Response`] carrying the
+//! id of the request it belongs to, so that several clients can talk to leftwm at once without
+//! stealing each other's results.
+use crate::errors::Result;
+use crate::{Command, Query};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::path::PathBuf;
+use tokio::fs;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::{mpsc, oneshot};
+
+/// A request sent by a client over the [`CommandSocket`].
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Request {
+    /// Id chosen by the client, echoed back in the matching [`Response`].
+    pub id: u64,
+    #[serde(flatten)]
+    pub kind: RequestKind,
+}
+
+/// What a [`Request`] asks leftwm to do.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum RequestKind {
+    /// Execute a command, answered with an empty payload.
+    Command(Command),
+    /// Answered with the requested fragment of the state as payload.
+    Query(Query),
+}
+
+/// Whether a [`Request`] was handled.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Error,
+}
+
+/// The reply to a single [`Request`].
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Response {
+    pub id: u64,
+    pub status: Status,
+    /// Data returned by the request, or the error message if `status` is [`Status::Error`].
+    pub payload: serde_json::Value,
+}
+
+impl Response {
+    pub const fn ok(id: u64, payload: serde_json::Value) -> Self {
+        Self {
+            id,
+            status: Status::Ok,
+            payload,
+        }
+    }
+
+    pub fn error(id: u64, message: impl Into<String>) -> Self {
+        Self {
+            id,
+            status: Status::Error,
+            payload: serde_json::Value::String(message.into()),
+        }
+    }
+}
+
+/// A [`Request`] waiting to be answered by the event loop.
+#[derive(Debug)]
+pub struct PendingRequest {
+    pub request: Request,
+    responder: oneshot::Sender<Response>,
+}
+
+impl PendingRequest {
+    /// Send the response back to the client which issued the request.
+    pub fn respond(self, response: Response) {
+        if self.responder.send(response).is_err() {
+            tracing::debug!("Client disconnected before receiving its response");
+        }
+    }
+}
+
+/// Holds the socket file location, the listener task and a receiver for incoming requests.
+#[derive(Debug)]
+pub struct CommandSocket {
+    listener: Option<tokio::task::JoinHandle<()>>,
+    socket_file: PathBuf,
+    rx: mpsc::UnboundedReceiver<PendingRequest>,
+    tx: mpsc::UnboundedSender<PendingRequest>,
+}
+
+impl Default for CommandSocket {
+    fn default() -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        Self {
+            listener: None,
+            socket_file: PathBuf::default(),
+            rx,
+            tx,
+        }
+    }
+}
+
+impl Drop for CommandSocket {
+    fn drop(&mut self) {
+        assert!(
+            std::thread::panicking() || self.listener.is_none(),
+            "CommandSocket has to be shutdown explicitly before drop"
+        );
+    }
+}
+
+impl CommandSocket {
+    /// Bind to Unix socket and listen.
+    /// # Errors
+    ///
+    /// Will error if the socket cannot be bound, likely a filesystem issue
+    /// such as incorrect permissions or a non-existant directory.
+    pub async fn listen(&mut self, socket_file: PathBuf) -> Result<()> {
+        self.socket_file = socket_file;
+        let listener = self.build_listener().await?;
+        self.listener = Some(listener);
+        Ok(())
+    }
+
+    /// Explicitly shutdown `CommandSocket` to perform cleanup.
+    pub async fn shutdown(&mut self) {
+        if let Some(listener) = self.listener.take() {
+            listener.abort();
+            listener.await.ok();
+            fs::remove_file(self.socket_file.as_path()).await.ok();
+        }
+    }
+
+    pub fn socket_name() -> PathBuf {
+        let display = env::var("DISPLAY")
+            .ok()
+            .and_then(|d| d.rsplit_once(':').map(|(_, r)| r.to_owned()))
+            .unwrap_or_else(|| "0".to_string());
+
+        PathBuf::from(format!("command-{display}.sock"))
+    }
+
+    pub async fn read_request(&mut self) -> Option<PendingRequest> {
+        self.rx.recv().await
+    }
+
+    async fn build_listener(&self) -> Result<tokio::task::JoinHandle<()>> {
+        let tx = self.tx.clone();
+        let listener = if let Ok(m) = UnixListener::bind(&self.socket_file) {
+            m
+        } else {
+            fs::remove_file(&self.socket_file).await?;
+            UnixListener::bind(&self.socket_file)?
+        };
+
+        Ok(tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((peer, _)) => {
+                        tokio::spawn(handle_peer(peer, tx.clone()));
+                    }
+                    Err(e) => tracing::error!("Accept failed = {:?}", e),
+                }
+            }
+        }))
+    }
+}
+
+async fn handle_peer(peer: UnixStream, tx: mpsc::UnboundedSender<PendingRequest>) -> Option<()> {
+    let (reader, mut writer) = peer.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Some(line) = lines.next_line().await.ok()? {
+        let response = match parse_request(&line) {
+            Ok(request) => {
+                let (responder, response) = oneshot::channel();
+                tx.send(PendingRequest { request, responder }).ok()?;
+                response.await.ok()?
+            }
+            Err(response) => response,
+        };
+
+        let mut json = serde_json::to_string(&response).ok()?;
+        json.push('\n');
+        writer.write_all(json.as_bytes()).await.ok()?;
+    }
+
+    Some(())
+}
+
+fn parse_request(line: &str) -> std::result::Result<Request, Response> {
+    let value: serde_json::Value = serde_json::from_str(line)
+        .map_err(|err| Response::error(0, format!("Error parsing request: {err}")))?;
+    // Recover the id first so that even malformed requests get a matching reply.
+    let id = value
+        .get("id")
+        .and_then(serde_json::Value::as_u64)
+        .unwrap_or_default();
+    serde_json::from_value(value)
+        .map_err(|err| Response::error(id, format!("Error parsing request: {err}")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::helpers::test::temp_path;
+    use std::path::Path;
+
+    async fn send(socket_file: &Path, line: &str) -> Response {
+        let mut stream = UnixStream::connect(socket_file).await.unwrap();
+        stream.write_all(line.as_bytes()).await.unwrap();
+        stream.write_all(b"\n").await.unwrap();
+        let mut lines = BufReader::new(stream).lines();
+        let reply = lines.next_line().await.expect("Read next line").unwrap();
+        serde_json::from_str(&reply).unwrap()
+    }
+
+    #[tokio::test]
+    async fn request_gets_matching_response() {
+        let socket_file = temp_path().await.unwrap();
+        let mut command_socket = CommandSocket::default();
+        command_socket.listen(socket_file.clone()).await.unwrap();
+
+        let request = Request {
+            id: 42,
+            kind: RequestKind::Command(Command::SetLayout("Monocle".to_string())),
+        };
+        let line = serde_json::to_string(&request).unwrap();
+        let client = tokio::spawn(async move { send(&socket_file, &line).await });
+
+        let pending = command_socket.read_request().await.unwrap();
+        assert_eq!(pending.request, request);
+        pending.respond(Response::ok(42, serde_json::Value::Null));
+
+        assert_eq!(
+            client.await.unwrap(),
+            Response::ok(42, serde_json::Value::Null)
+        );
+        command_socket.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn concurrent_clients_get_their_own_response() {
+        let socket_file = temp_path().await.unwrap();
+        let mut command_socket = CommandSocket::default();
+        command_socket.listen(socket_file.clone()).await.unwrap();
+
+        let clients: Vec<_> = (1..=3)
+            .map(|id| {
+                let socket_file = socket_file.clone();
+                let line = serde_json::to_string(&Request {
+                    id,
+                    kind: RequestKind::Command(Command::CloseWindow),
+                })
+                .unwrap();
+                tokio::spawn(async move { send(&socket_file, &line).await })
+            })
+            .collect();
+
+        for _ in 0..3 {
+            let pending = command_socket.read_request().await.unwrap();
+            let id = pending.request.id;
+            pending.respond(Response::ok(id, serde_json::Value::from(id)));
+        }
+
+        for (client, id) in clients.into_iter().zip(1..=3_u64) {
+            assert_eq!(
+                client.await.unwrap(),
+                Response::ok(id, serde_json::Value::from(id))
+            );
+        }
+        command_socket.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn malformed_request_gets_error() {
+        let socket_file = temp_path().await.unwrap();
+        let mut command_socket = CommandSocket::default();
+        command_socket.listen(socket_file.clone()).await.unwrap();
+
+        let response = send(&socket_file, r#"{"id":7,"command":"NotACommand"}"#).await;
+        assert_eq!(response.id, 7);
+        assert_eq!(response.status, Status::Error);
+
+        let response = send(&socket_file, "not json").await;
+        assert_eq!(response.id, 0);
+        assert_eq!(response.status, Status::Error);
+
+        command_socket.shutdown().await;
+    }
+
+    #[test]
+    fn parse_request_kinds() {
+        assert_eq!(
+            parse_request(r#"{"id":1,"command":"CloseWindow"}"#).unwrap(),
+            Request {
+                id: 1,
+                kind: RequestKind::Command(Command::CloseWindow),
+            }
+        );
+        assert_eq!(
+            parse_request(r#"{"id":2,"query":{"Windows":{"tag":4}}}"#).unwrap(),
+            Request {
+                id: 2,
+                kind: RequestKind::Query(Query::Windows { tag: Some(4) }),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn socket_cleanup() {
+        let socket_file = temp_path().await.unwrap();
+        let mut command_socket = CommandSocket::default();
+        command_socket.listen(socket_file.clone()).await.unwrap();
+        command_socket.shutdown().await;
+        assert!(!socket_file.exists());
+    }
+}
@@ -0,0 +1,271 @@
+//! Creates a Unix socket streaming [`StateEvent`]s to subscribed clients.
+//!
+//! Every event is written as a single line of JSON. A client receives all events by default,
+//! and may at any time write a line with a JSON list of [`StateEventKind`]s to only receive
+//! events of those kinds. An empty list subscribes to all events again.
+//!
+//! Events are written to each client by a task of its own, so that a client reading slowly does
+//! not hold up the window manager. A client falling behind by more than `PEER_BUFFER` events
+//! is disconnected.
+use crate::errors::Result;
+use crate::{StateEvent, StateEventKind};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::fs;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::UnixListener;
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::sync::Mutex;
+
+/// Number of events buffered for a client, before it is disconnected as too slow.
+const PEER_BUFFER: usize = 256;
+
+#[derive(Debug)]
+struct Peer {
+    id: usize,
+    /// Lines of JSON to be written by the task of the peer.
+    lines: mpsc::Sender<Arc<str>>,
+    /// `None` subscribes to every kind of event.
+    kinds: Option<Vec<StateEventKind>>,
+}
+
+impl Peer {
+    fn wants(&self, kind: StateEventKind) -> bool {
+        self.kinds
+            .as_ref()
+            .map_or(true, |kinds| kinds.contains(&kind))
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    peers: Vec<Peer>,
+    next_id: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct EventSocket {
+    state: Arc<Mutex<State>>,
+    listener: Option<tokio::task::JoinHandle<()>>,
+    socket_file: PathBuf,
+}
+
+impl Drop for EventSocket {
+    fn drop(&mut self) {
+        assert!(
+            std::thread::panicking() || self.listener.is_none(),
+            "EventSocket has to be shutdown explicitly before drop"
+        );
+    }
+}
+
+impl EventSocket {
+    /// Bind to Unix socket and listen.
+    /// # Errors
+    ///
+    /// Will error if the socket cannot be bound, likely a filesystem issue
+    /// such as incorrect permissions or a non-existant directory.
+    pub async fn listen(&mut self, socket_file: PathBuf) -> Result<()> {
+        self.socket_file = socket_file;
+        let listener = self.build_listener().await?;
+        self.listener = Some(listener);
+        Ok(())
+    }
+
+    /// Explicitly shutdown `EventSocket` to perform cleanup.
+    pub async fn shutdown(&mut self) {
+        if let Some(listener) = self.listener.take() {
+            listener.abort();
+            listener.await.ok();
+            fs::remove_file(self.socket_file.as_path()).await.ok();
+        }
+    }
+
+    /// Send the events to every peer subscribed to their kind.
+    /// # Errors
+    ///
+    /// Will return error if an event cannot be serialized.
+    pub async fn write_events(
+        &mut self,
+        events: impl IntoIterator<Item = StateEvent>,
+    ) -> Result<()> {
+        if self.listener.is_none() {
+            return Ok(());
+        }
+        let mut state = self.state.lock().await;
+        for event in events {
+            let mut json = serde_json::to_string(&event)?;
+            json.push('\n');
+            let json: Arc<str> = json.into();
+            let kind = event.kind();
+
+            state.peers.retain(|peer| {
+                if !peer.wants(kind) {
+                    return true;
+                }
+                match peer.lines.try_send(json.clone()) {
+                    Ok(()) => true,
+                    Err(TrySendError::Full(_)) => {
+                        tracing::warn!(
+                            "Disconnecting event socket client {}, it is too slow",
+                            peer.id
+                        );
+                        false
+                    }
+                    Err(TrySendError::Closed(_)) => false,
+                }
+            });
+        }
+        Ok(())
+    }
+
+    async fn build_listener(&self) -> Result<tokio::task::JoinHandle<()>> {
+        let state = self.state.clone();
+        let listener = if let Ok(m) = UnixListener::bind(&self.socket_file) {
+            m
+        } else {
+            fs::remove_file(&self.socket_file).await?;
+            UnixListener::bind(&self.socket_file)?
+        };
+
+        Ok(tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((peer, _)) => {
+                        let (reader, writer) = peer.into_split();
+                        let (lines, pending) = mpsc::channel(PEER_BUFFER);
+                        let mut guard = state.lock().await;
+                        let id = guard.next_id;
+                        guard.next_id += 1;
+                        guard.peers.push(Peer {
+                            id,
+                            lines,
+                            kinds: None,
+                        });
+                        drop(guard);
+                        tokio::spawn(write_lines(writer, pending));
+                        tokio::spawn(read_subscriptions(id, reader, state.clone()));
+                    }
+                    Err(e) => tracing::error!("Accept failed = {:?}", e),
+                }
+            }
+        }))
+    }
+}
+
+/// Write the lines sent to a peer, until it disconnects or is dropped.
+async fn write_lines(mut writer: OwnedWriteHalf, mut pending: mpsc::Receiver<Arc<str>>) {
+    while let Some(line) = pending.recv().await {
+        if writer.write_all(line.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+async fn read_subscriptions(id: usize, reader: OwnedReadHalf, state: Arc<Mutex<State>>) {
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let kinds = match serde_json::from_str::<Vec<StateEventKind>>(&line) {
+            Ok(kinds) if kinds.is_empty() => None,
+            Ok(kinds) => Some(kinds),
+            Err(e) => {
+                tracing::warn!("Invalid event subscription {:?}: {}", line, e);
+                continue;
+            }
+        };
+        if let Some(peer) = state.lock().await.peers.iter_mut().find(|p| p.id == id) {
+            peer.kinds = kinds;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::WindowHandle;
+    use crate::utils::helpers::test::temp_path;
+    use tokio::net::UnixStream;
+
+    async fn wait_for_peers(socket: &EventSocket, count: usize) {
+        while socket.state.lock().await.peers.len() < count {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn peers_receive_events() {
+        let socket_file = temp_path().await.unwrap();
+        let mut event_socket = EventSocket::default();
+        event_socket.listen(socket_file.clone()).await.unwrap();
+
+        let mut lines = BufReader::new(UnixStream::connect(socket_file).await.unwrap()).lines();
+        wait_for_peers(&event_socket, 1).await;
+
+        let event = StateEvent::TagSwitched { tag: 2 };
+        event_socket.write_events([event.clone()]).await.unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            lines.next_line().await.expect("Read next line").unwrap()
+        );
+
+        event_socket.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn peers_can_filter_events() {
+        let socket_file = temp_path().await.unwrap();
+        let mut event_socket = EventSocket::default();
+        event_socket.listen(socket_file.clone()).await.unwrap();
+
+        let mut stream = UnixStream::connect(socket_file).await.unwrap();
+        stream.write_all(b"[\"focus_changed\"]\n").await.unwrap();
+        wait_for_peers(&event_socket, 1).await;
+        while event_socket.state.lock().await.peers[0].kinds.is_none() {
+            tokio::task::yield_now().await;
+        }
+
+        let focus = StateEvent::FocusChanged {
+            handle: Some(WindowHandle::MockHandle(1)),
+        };
+        event_socket
+            .write_events([StateEvent::TagSwitched { tag: 2 }, focus.clone()])
+            .await
+            .unwrap();
+
+        let mut lines = BufReader::new(stream).lines();
+        assert_eq!(
+            serde_json::to_string(&focus).unwrap(),
+            lines.next_line().await.expect("Read next line").unwrap()
+        );
+
+        event_socket.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn slow_peers_are_disconnected() {
+        let socket_file = temp_path().await.unwrap();
+        let mut event_socket = EventSocket::default();
+        event_socket.listen(socket_file.clone()).await.unwrap();
+
+        // The peer never reads its events.
+        let _stream = UnixStream::connect(socket_file).await.unwrap();
+        wait_for_peers(&event_socket, 1).await;
+
+        let events = (0..=PEER_BUFFER).map(|tag| StateEvent::TagSwitched { tag });
+        event_socket.write_events(events).await.unwrap();
+        assert!(event_socket.state.lock().await.peers.is_empty());
+
+        event_socket.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn socket_cleanup() {
+        let socket_file = temp_path().await.unwrap();
+        let mut event_socket = EventSocket::default();
+        event_socket.listen(socket_file.clone()).await.unwrap();
+        event_socket.shutdown().await;
+        assert!(!socket_file.exists());
+    }
+}
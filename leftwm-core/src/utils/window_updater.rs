@@ -14,11 +14,22 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             .iter_mut()
             .for_each(|w| w.set_visible(w.tag.is_none()));
 
+        let focused = self
+            .state
+            .focus_manager
+            .window(&self.state.windows)
+            .map(|w| w.handle);
         for ws in &self.state.workspaces {
             let windows = &mut self.state.windows;
             let all_tags = &self.state.tags;
             if let Some(Some(tag)) = ws.tag.map(|tag_id| all_tags.get(tag_id)) {
-                tag.update_windows(windows, ws, &mut self.state.layout_manager);
+                tag.update_windows(
+                    windows,
+                    ws,
+                    &mut self.state.layout_manager,
+                    &mut self.state.split_trees,
+                    focused,
+                );
             }
         }
     }
@@ -1,6 +1,11 @@
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use clap::{arg, command};
 use leftwm::{Config, ThemeSetting};
+use leftwm_core::layouts::validate;
+use leftwm_core::models::WindowHandle;
+use leftwm_core::utils::command_socket::{Request, RequestKind, Response, Status};
+use leftwm_core::{CommandSocket, Config as _, Query};
+use leftwm_layouts::geometry::Rect;
 use ron::{
     extensions::Extensions,
     ser::{to_string_pretty, PrettyConfig},
@@ -10,9 +15,13 @@ use std::env;
 use std::fs;
 use std::fs::File;
 use std::io::Write;
+use std::os::raw::c_ulong;
 use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
 use std::path::PathBuf;
+use std::process;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
 use xdg::BaseDirectories;
 
 #[tokio::main]
@@ -23,6 +32,11 @@ async fn main() -> Result<()> {
         .args(&[
             arg!(-v --verbose "Outputs received configuration file."),
             arg!(migrate: -m --"migrate-toml-to-ron" "Migrates an exesting `toml` based config to a `ron` based one.\nKeeps the old file for reference, please delete it manually."),
+            arg!(explain: --"explain-rules" [WINDOW] "Explains which window rule matches the window with the given id.\nAsks to click on a window if no id is given."),
+            arg!(layout: --layout <NAME> "Validates the layout with the given name and previews how it arranges the windows."),
+            arg!(windows: --windows <N> "Sets the number of windows to preview the layout with, 3 by default."),
+            arg!(size: --size <WxH> "Sets the size of the area to preview the layout in, 1920x1080 by default."),
+            arg!(json: --json "Prints the previewed windows as JSON instead of drawing them."),
             arg!([INPUT] "Sets the input file to use. Uses first in PATH otherwise."),
         ])
         .get_matches();
@@ -30,6 +44,26 @@ async fn main() -> Result<()> {
     let config_file = matches.get_one::<String>("INPUT").map(String::as_str);
     let verbose = matches.get_flag("verbose");
 
+    if let Some(name) = matches.get_one::<String>("layout") {
+        let config = load_from_file(config_file, verbose)?;
+        let windows = match matches.get_one::<String>("windows") {
+            Some(windows) => windows
+                .parse()
+                .with_context(|| format!("Invalid number of windows: {windows}"))?,
+            None => 3,
+        };
+        let area = match matches.get_one::<String>("size") {
+            Some(size) => parse_size(size)?,
+            None => Rect {
+                x: 0,
+                y: 0,
+                w: 1920,
+                h: 1080,
+            },
+        };
+        return preview_layout(&config, name, windows, &area, matches.get_flag("json"));
+    }
+
     println!(
         "\x1b[0;94m::\x1b[0m LeftWM version: {}",
         env!("CARGO_PKG_VERSION")
@@ -51,6 +85,15 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    if matches.contains_id("explain") {
+        let handle = match matches.get_one::<String>("explain") {
+            Some(id) => parse_window_id(id)?,
+            None => select_window()?,
+        };
+        print!("{}", query_explanation(handle).await?);
+        return Ok(());
+    }
+
     match check_enabled_features(verbose) {
         Ok(()) => {}
         Err(err) => {
@@ -136,6 +179,149 @@ pub fn load_from_file(fspath: Option<&str>, verbose: bool) -> Result<Config> {
     }
 }
 
+/// Parses a window id, either hexadecimal with a `0x` prefix or decimal.
+fn parse_window_id(id: &str) -> Result<WindowHandle> {
+    let id = match id.strip_prefix("0x") {
+        Some(hex) => c_ulong::from_str_radix(hex, 16),
+        None => id.parse(),
+    }
+    .with_context(|| format!("Invalid window id: {id}"))?;
+    Ok(WindowHandle::XlibHandle(id))
+}
+
+/// Parses a size given as `WIDTHxHEIGHT`.
+fn parse_size(size: &str) -> Result<Rect> {
+    let parsed = size
+        .split_once('x')
+        .and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)));
+    match parsed {
+        Some((w, h)) if w > 0 && h > 0 => Ok(Rect { x: 0, y: 0, w, h }),
+        _ => bail!("Invalid size: {size}, expected WIDTHxHEIGHT"),
+    }
+}
+
+/// Validates the layout with the given name and prints how it arranges `windows` windows in
+/// `area`, either drawn or as JSON.
+fn preview_layout(
+    config: &Config,
+    name: &str,
+    windows: usize,
+    area: &Rect,
+    json: bool,
+) -> Result<()> {
+    let definitions = config.layout_definitions();
+    let Some(def) = definitions.iter().find(|def| def.name == name) else {
+        bail!("There is no layout with the name {name:?}");
+    };
+    let rects =
+        validate(def, windows, area).with_context(|| format!("The layout {name} is invalid"))?;
+    if json {
+        let rects: Vec<serde_json::Value> = rects
+            .iter()
+            .map(|rect| serde_json::json!({ "x": rect.x, "y": rect.y, "w": rect.w, "h": rect.h }))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&rects)?);
+        return Ok(());
+    }
+    println!("\x1b[0;92m    -> Layout {name} OK \x1b[0m");
+    println!("{}", draw_rects(&rects, area));
+    if rects.len() < windows {
+        println!("{} of the windows are hidden", windows - rects.len());
+    }
+    Ok(())
+}
+
+/// Draws the rects as boxes of characters, numbered in the order of the windows.
+fn draw_rects(rects: &[Rect], area: &Rect) -> String {
+    const COLUMNS: usize = 64;
+    // Characters are about twice as high as they are wide.
+    let rows = (COLUMNS * area.h as usize / area.w as usize / 2).max(1);
+    let mut grid = vec![vec![' '; COLUMNS]; rows];
+    // The cell a position falls into, along an axis of `cells` cells.
+    let to_cell = |position: i64, start: i32, length: u32, cells: usize| {
+        let cell = (position - i64::from(start)) * cells as i64 / i64::from(length);
+        cell.clamp(0, cells as i64 - 1) as usize
+    };
+    for (index, rect) in rects.iter().enumerate() {
+        let (x, y) = (i64::from(rect.x), i64::from(rect.y));
+        let left = to_cell(x, area.x, area.w, COLUMNS);
+        let right = to_cell(x + i64::from(rect.w) - 1, area.x, area.w, COLUMNS);
+        let top = to_cell(y, area.y, area.h, rows);
+        let bottom = to_cell(y + i64::from(rect.h) - 1, area.y, area.h, rows);
+        for (row, line) in grid.iter_mut().enumerate().take(bottom + 1).skip(top) {
+            let on_edge = row == top || row == bottom;
+            for (column, cell) in line.iter_mut().enumerate().take(right + 1).skip(left) {
+                *cell = match (column == left || column == right, on_edge) {
+                    (true, true) => '+',
+                    (true, false) => '|',
+                    (false, true) => '-',
+                    (false, false) => ' ',
+                };
+            }
+        }
+        let label = (index + 1).to_string();
+        let column = (left + right + 1).saturating_sub(label.len()) / 2;
+        for (offset, c) in label.chars().enumerate() {
+            if let Some(cell) = grid[(top + bottom) / 2].get_mut(column + offset) {
+                *cell = c;
+            }
+        }
+    }
+    grid.iter()
+        .map(|line| line.iter().collect::<String>().trim_end().to_owned())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Lets the user click on a window using `xwininfo`.
+fn select_window() -> Result<WindowHandle> {
+    println!("\x1b[0;94m::\x1b[0m Click on the window to explain . . .");
+    let output = process::Command::new("xwininfo")
+        .output()
+        .context("Could not run `xwininfo`, please pass the window id instead")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let Some(id) = stdout
+        .lines()
+        .find_map(|line| line.split("Window id: ").nth(1))
+        .and_then(|rest| rest.split_whitespace().next())
+    else {
+        bail!("Could not read the window id from `xwininfo`");
+    };
+    parse_window_id(id)
+}
+
+/// Asks the running LeftWM to explain its window rules for the window.
+async fn query_explanation(handle: WindowHandle) -> Result<String> {
+    let file_name = CommandSocket::socket_name();
+    let file_path = BaseDirectories::with_prefix("leftwm")?
+        .find_runtime_file(&file_name)
+        .with_context(|| format!("Couldn't find {}, is LeftWM running?", file_name.display()))?;
+    let stream = UnixStream::connect(file_path)
+        .await
+        .with_context(|| format!("Couldn't connect to {}", file_name.display()))?;
+    let (reader, mut writer) = stream.into_split();
+
+    let request = Request {
+        id: 1,
+        kind: RequestKind::Query(Query::ExplainWindowRules { handle }),
+    };
+    let mut json = serde_json::to_string(&request)?;
+    json.push('\n');
+    writer.write_all(json.as_bytes()).await?;
+
+    let Some(reply) = BufReader::new(reader).lines().next_line().await? else {
+        bail!("Connection closed before the explanation was received");
+    };
+    match serde_json::from_str::<Response>(&reply)? {
+        Response {
+            status: Status::Ok,
+            payload,
+            ..
+        } => Ok(serde_json::from_value(payload)?),
+        Response { payload, .. } => bail!("Could not explain the window rules: {}", payload),
+    }
+}
+
 fn write_to_file(ron_file: &Path, config: &Config) -> Result<(), anyhow::Error> {
     let ron_pretty_conf = PrettyConfig::new()
         .depth_limit(2)
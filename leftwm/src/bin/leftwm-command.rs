@@ -1,51 +1,68 @@
 use anyhow::{Context, Result};
 use clap::{arg, command};
 use leftwm::BaseCommand;
-use leftwm_core::CommandPipe;
-use leftwm_core::ReturnPipe;
-use std::fs::OpenOptions;
-use std::io::prelude::*;
-use std::path::{Path, PathBuf};
+use leftwm_core::utils::command_pipe::parse_command;
+use leftwm_core::utils::command_socket::{Request, RequestKind, Response, Status};
+use leftwm_core::{CommandSocket, Query};
 use std::process::exit;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
 use xdg::BaseDirectories;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let matches = get_command().get_matches();
 
-    let file_name = CommandPipe::pipe_name();
-    let file_path = BaseDirectories::with_prefix("leftwm")?
-        .find_runtime_file(&file_name)
-        .with_context(|| format!("ERROR: Couldn't find {}", file_name.display()))?;
-    let mut file = OpenOptions::new()
-        .append(true)
-        .open(file_path)
-        .with_context(|| format!("ERROR: Couldn't open {}", file_name.display()))?;
     let mut exit_code = 0;
+    let query_mode = matches.get_flag("query");
     if let Some(commands) = matches.get_many::<String>("COMMAND") {
-        let mut ret_pipe = get_return_pipe().await?;
-        for command in commands {
-            if let Err(e) = writeln!(file, "{command}") {
-                eprintln!("ERROR: Couldn't write to commands.pipe: {e}");
+        let file_name = CommandSocket::socket_name();
+        let file_path = BaseDirectories::with_prefix("leftwm")?
+            .find_runtime_file(&file_name)
+            .with_context(|| format!("ERROR: Couldn't find {}", file_name.display()))?;
+        let stream = UnixStream::connect(file_path)
+            .await
+            .with_context(|| format!("ERROR: Couldn't connect to {}", file_name.display()))?;
+        let (reader, mut writer) = stream.into_split();
+        let mut replies = BufReader::new(reader).lines();
+
+        for (id, command) in (1..).zip(commands) {
+            let kind = if query_mode {
+                command.parse::<Query>().map(RequestKind::Query)
+            } else {
+                parse_command(command).map(RequestKind::Command)
+            };
+            let request = match kind {
+                Ok(kind) => Request { id, kind },
+                Err(e) => {
+                    eprintln!(
+                        "{command}: Error parsing {}: {e}",
+                        if query_mode { "query" } else { "command" }
+                    );
+                    exit_code = 1;
+                    continue;
+                }
+            };
+            let mut json = serde_json::to_string(&request)?;
+            json.push('\n');
+            if let Err(e) = writer.write_all(json.as_bytes()).await {
+                eprintln!("ERROR: Couldn't write to {}: {e}", file_name.display());
+                exit_code = 1;
                 continue;
             }
             tokio::select! {
-                Some(res) = ret_pipe.read_return() => {
-                if let Some((result, msg)) = res.split_once(' '){
-                        match result{
-                            "OK:" => println!("{command}: {msg}"),
-                            "ERROR:" => {eprintln!("{command}: {msg}");exit_code = 1;},
-                            _ => println!("{command}: {res}"),
-                        }
-                    }else{
-                        println!("{command}: {res}");
+                Ok(Some(reply)) = replies.next_line() => {
+                    match serde_json::from_str::<Response>(&reply) {
+                        Ok(Response { status: Status::Ok, payload, .. }) if query_mode => println!("{payload}"),
+                        Ok(Response { status: Status::Ok, .. }) => println!("{command}: command executed successfully"),
+                        Ok(Response { status: Status::Error, payload, .. }) => {eprintln!("{command}: {}", payload.as_str().unwrap_or("unknown error")); exit_code = 1;},
+                        Err(e) => {eprintln!("{command}: invalid reply: {e}"); exit_code = 1;},
                     }
-            }
-                () = timeout(5000) => {eprintln!(" WARN: timeout connecting to return pipe. Command may have executed, but errors will not be displayed."); exit_code = 1;},
-                else => {eprintln!("WARN: timeout connection to return pipe. Command may have executed, but errors will not be displayed."); exit_code = 1;},
+                }
+                () = timeout(5000) => {eprintln!("WARN: timeout waiting for a reply. Command may have executed, but errors will not be displayed."); exit_code = 1;},
+                else => {eprintln!("WARN: connection closed before a reply was received. Command may have executed, but errors will not be displayed."); exit_code = 1;},
             }
         }
-        drop(ret_pipe);
     }
 
     if matches.get_flag("list") {
@@ -60,6 +77,7 @@ fn get_command() -> clap::Command {
         .help_template(leftwm::utils::get_help_template())
         .args(&[
             arg!(-l --list "Print a list of available commands with their arguments."),
+            arg!(-q --query "Send the arguments as queries and print the JSON answers."),
             arg!([COMMAND] ... "The command to be sent. See 'list' flag."),
         ])
 }
@@ -76,6 +94,15 @@ fn print_commandlist() {
             Use quotations for the command and arguments, like this:
             leftwm-command \"<command> <args>\"
 
+    Available Queries (use with --query):
+        Windows                Args: [tag_index] (int)
+        FocusedWindow
+        Tags
+        Workspaces             Args: [workspace_id] (int)
+        Layouts                Args: [workspace_id] (int)
+        Scratchpads
+        ExplainWindowRules     Args: <window_id> (int)
+
     For more information please visit:
     https://github.com/leftwm/leftwm/wiki/External-Commands
          ",
@@ -83,33 +110,6 @@ fn print_commandlist() {
     );
 }
 
-#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq, Hash)]
-pub enum Error {
-    #[error("Couldn't create the file: '{0}'")]
-    CreateFile(PathBuf),
-
-    #[error("Couldn't connect to file: '{0}'")]
-    ConnectToFile(PathBuf),
-}
-
-async fn get_return_pipe() -> Result<ReturnPipe, Error> {
-    let file_name = ReturnPipe::pipe_name();
-
-    let pipe_file =
-        place_runtime_file(&file_name).map_err(|_| Error::CreateFile(file_name.clone()))?;
-
-    ReturnPipe::new(pipe_file)
-        .await
-        .map_err(|_| Error::ConnectToFile(file_name))
-}
-
-fn place_runtime_file<P>(path: P) -> std::io::Result<PathBuf>
-where
-    P: AsRef<Path>,
-{
-    xdg::BaseDirectories::with_prefix("leftwm")?.place_runtime_file(path)
-}
-
 async fn timeout(mills: u64) {
     use tokio::time::{sleep, Duration};
     sleep(Duration::from_millis(mills)).await;
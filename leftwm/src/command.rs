@@ -28,11 +28,17 @@ pub enum BaseCommand {
     PrevScratchPadWindow,
     /// Args: <ScratchpadName>
     ToggleScratchPad,
+    /// Args: <GroupName>
+    CycleScratchPadGroup,
     ToggleFullScreen,
     ToggleMaximized,
     ToggleSticky,
     GotoTag,
     ReturnToLastTag,
+    /// Args: <tag_index> (int)
+    ToggleTagView,
+    /// Args: <tag_index> (int), several separated by spaces
+    ToggleWindowTags,
     FloatingToTile,
     TileToFloating,
     ToggleFloating,
@@ -40,6 +46,8 @@ pub enum BaseCommand {
     MoveWindowDown,
     MoveWindowTop,
     SwapWindowTop,
+    /// Args: <direction> (Left, Right, Top or Bottom)
+    SwapWindowInDirection,
     /// Args: <behavior> (string, optional)
     FocusNextTag,
     /// Args: <behavior> (string, optional)
@@ -48,6 +56,8 @@ pub enum BaseCommand {
     FocusWindow,
     FocusWindowUp,
     FocusWindowDown,
+    /// Args: <direction> (Left, Right, Top or Bottom)
+    FocusWindowInDirection,
     FocusWindowTop,
     FocusWorkspaceNext,
     FocusWorkspacePrevious,
@@ -63,6 +73,12 @@ pub enum BaseCommand {
     PreviousLayout,
     /// Args: <LayoutName>
     SetLayout,
+    /// Args: <direction> (Horizontal or Vertical)
+    SplitWindow,
+    /// Args: <change> (int)
+    ResizeSplit,
+    /// Args: <direction> (Left, Right, Top or Bottom)
+    MoveWindowInTree,
     RotateTag,
     /// Note: This is deprecated and will be dropped in a future release.
     IncreaseMainWidth, //deprecated
@@ -72,9 +88,28 @@ pub enum BaseCommand {
     DecreaseMainSize,
     IncreaseMainCount,
     DecreaseMainCount,
+    /// Args: <change> (int, percent)
+    IncreaseWindowWeight,
+    /// Args: <change> (int, percent)
+    DecreaseWindowWeight,
+    ResetWindowWeights,
     /// Args: <multiplier-value> (float)
     SetMarginMultiplier,
+    /// Args: <label>
+    AddTag,
+    /// Args: <tag_index> (int)
+    RemoveTag,
+    /// Args: <tag_index> (int) <label>
+    RenameTag,
+    /// Args: <tag_index> (int) <position> (int)
+    MoveTag,
+    /// Args: <SessionName>
+    SaveSession,
+    /// Args: <SessionName>
+    RestoreSession,
     UnloadTheme,
+    /// Note: Only the layouts, their definitions and the layouts of the tags are reloaded.
+    ReloadLayouts,
     /// Args: <Path_to/theme.ron>
     /// Note: `theme.toml` will be deprecated but stays for backwards compatibility for a while
     LoadTheme,
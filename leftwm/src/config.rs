@@ -11,11 +11,14 @@ use super::BaseCommand;
 use super::ThemeSetting;
 #[cfg(feature = "lefthk")]
 use crate::config::keybind::Keybind;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use leftwm_core::{
-    config::{InsertBehavior, ScratchPad, Workspace},
-    layouts::LayoutMode,
-    models::{FocusBehaviour, Gutter, Margins, Size, Window, WindowState, WindowType},
+    config::{InsertBehavior, ScratchPad, TagLayouts, Workspace, WorkspaceProfile},
+    layouts::{validate, LayoutMode, CHECKED_WINDOWS},
+    models::{
+        BorderColors, FloatingGeometry, FocusBehaviour, Gutter, Margins, ScratchPadName, Size,
+        Window, WindowHandle, WindowState, WindowType,
+    },
     state::State,
     DisplayAction, DisplayServer, Manager, ReturnPipe,
 };
@@ -64,6 +67,13 @@ const STATE_FILE: &str = "/tmp/leftwm.state";
 /// ```
 ///
 /// windows whose `WM_CLASS` is "krita" will spawn on tag 3 (1-indexed) and not floating.
+///
+/// Rules may also style and place the window, e.g.
+///
+/// ```ron
+/// (window_class: "pavucontrol", floating_geometry: (width: 0.4, height: 0.5), center: true,
+///     border_colors: (focused: "#ff8800"), focus_on_map: false),
+/// ```
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct WindowHook {
     // Use serde default field attribute to fallback to None option in case of missing field in
@@ -83,6 +93,30 @@ pub struct WindowHook {
         serialize_with = "to_config_string"
     )]
     pub window_title: Option<Regex>,
+    /// `WM_WINDOW_ROLE` in X11
+    #[serde(
+        default,
+        deserialize_with = "from_regex",
+        serialize_with = "to_config_string"
+    )]
+    pub window_role: Option<Regex>,
+    /// `_NET_WM_WINDOW_TYPE` in X11
+    pub window_type: Option<WindowType>,
+    /// Whether the window is transient for another one, like a dialog
+    pub transient: Option<bool>,
+    /// Name of the executable of the window's process, read from `/proc/<pid>/exe`
+    #[serde(
+        default,
+        deserialize_with = "from_regex",
+        serialize_with = "to_config_string"
+    )]
+    pub executable: Option<Regex>,
+    /// The workspace the window appeared on
+    pub on_workspace: Option<usize>,
+    /// The output of the workspace the window appeared on
+    pub on_output: Option<String>,
+    /// Windows matched by this rule are excluded, only its matchers are used
+    pub exclude: Option<Box<WindowHook>>,
     pub spawn_on_tag: Option<usize>,
     pub spawn_on_workspace: Option<usize>,
     pub spawn_floating: Option<bool>,
@@ -90,6 +124,33 @@ pub struct WindowHook {
     pub spawn_fullscreen: Option<bool>,
     /// Handle the window as if it was of this `_NET_WM_WINDOW_TYPE`
     pub spawn_as_type: Option<WindowType>,
+    /// Let the window take the place of the terminal it was launched from, or prevent it.
+    pub swallow: Option<bool>,
+    /// Also apply the rule when the title or `WM_CLASS` of a window changes to match it.
+    pub reevaluate_on_change: Option<bool>,
+    /// Float the window with this geometry, relative to its workspace
+    pub floating_geometry: Option<FloatingGeometry>,
+    /// Float the window in the center of its workspace
+    pub center: Option<bool>,
+    pub border_width: Option<i32>,
+    pub border_colors: Option<BorderColors>,
+    pub margin: Option<crate::CustomMargins>,
+    /// Focus the window once it appears, or prevent it, regardless of `focus_new_windows`
+    pub focus_on_map: Option<bool>,
+    /// Never give the input focus to the window
+    pub never_focus: Option<bool>,
+    /// Put the window in the scratchpad with this name
+    pub scratchpad: Option<String>,
+}
+
+/// The outcome of one matcher of a [`WindowHook`] for a window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatcherOutcome {
+    /// The name of the matcher in the config.
+    pub name: &'static str,
+    pub matched: bool,
+    /// Added to the score of the rule if the matcher matched.
+    pub weight: u8,
 }
 
 impl WindowHook {
@@ -97,30 +158,99 @@ impl WindowHook {
     ///
     /// Multiple [`WindowHook`]s might match a `WM_CLASS` but we want the most
     /// specific one to apply: matches by title are scored greater than by `WM_CLASS`.
-    fn score_window(&self, window: &Window) -> u8 {
-        // returns true if any of the items in the provided `Vec<&Option<String>>` is Some and matches the `&Regex`
-        let matches_any = |re: &Regex, strs: Vec<&Option<String>>| {
-            strs.iter().any(|str| {
-                str.as_ref().map_or(false, |s| {
-                    // we match the class/title to the window rule by checking if replacing the text
-                    // with the regex makes the string empty. if the original string is already
-                    // empty, this will match it to every regex, so we need to check for that.
-                    // however, if the window rule is explicitly for empty strings, we still
-                    // want empty strings to match to it.
-                    re.replace(s, "") == "" && (!s.is_empty() || re.as_str().is_empty())
-                })
-            })
+    ///
+    /// If given, at least one of `window_class` and `window_title` has to match, and every
+    /// other matcher has to match. A score of 0 means the rule does not match.
+    fn score_window(
+        &self,
+        state: &State,
+        window: &Window,
+        executable_name: ExecutableLookup,
+    ) -> u8 {
+        let outcomes = self.match_window(state, window, executable_name);
+        let (names, others): (Vec<_>, Vec<_>) = outcomes
+            .iter()
+            .partition(|o| o.name == "window_class" || o.name == "window_title");
+        if !names.is_empty() && !names.iter().any(|o| o.matched) {
+            return 0;
+        }
+        if others.iter().any(|o| !o.matched) {
+            return 0;
+        }
+        outcomes
+            .iter()
+            .filter(|o| o.matched)
+            .map(|o| o.weight)
+            .sum()
+    }
+
+    /// Run every matcher given in the rule against the window. `executable_name` looks up
+    /// the executable of the process of the window.
+    pub fn match_window(
+        &self,
+        state: &State,
+        window: &Window,
+        executable_name: ExecutableLookup,
+    ) -> Vec<MatcherOutcome> {
+        let mut outcomes = vec![];
+        let mut push = |name, weight, matched: Option<bool>| {
+            if let Some(matched) = matched {
+                outcomes.push(MatcherOutcome {
+                    name,
+                    matched,
+                    weight,
+                });
+            }
         };
 
-        let class_score = self.window_class.as_ref().map_or(0, |re| {
-            u8::from(matches_any(re, vec![&window.res_class, &window.res_name]))
+        let class = self
+            .window_class
+            .as_ref()
+            .map(|re| matches_any(re, &[&window.res_class, &window.res_name]));
+        push("window_class", 1, class);
+        let title = self
+            .window_title
+            .as_ref()
+            .map(|re| matches_any(re, &[&window.legacy_name, &window.name]));
+        push("window_title", 2, title);
+        let role = self
+            .window_role
+            .as_ref()
+            .map(|re| matches_any(re, &[&window.role]));
+        push("window_role", 2, role);
+        let r#type = self.window_type.as_ref().map(|t| t == &window.r#type);
+        push("window_type", 1, r#type);
+        let transient = self.transient.map(|t| t == window.transient.is_some());
+        push("transient", 1, transient);
+        let executable = self.executable.as_ref().map(|re| {
+            let executable = window.pid.and_then(executable_name);
+            matches_any(re, &[&executable])
         });
-
-        let title_score = self.window_title.as_ref().map_or(0, |re| {
-            u8::from(matches_any(re, vec![&window.legacy_name, &window.name]))
+        push("executable", 1, executable);
+
+        // A new window does not have a tag yet, it appears on the focused workspace.
+        let workspace = state
+            .workspaces
+            .iter()
+            .find(|ws| window.tag.is_some() && ws.is_displaying(window))
+            .or_else(|| state.focus_manager.workspace(&state.workspaces));
+        let on_workspace = self
+            .on_workspace
+            .map(|id| workspace.map_or(false, |ws| ws.id == id));
+        push("on_workspace", 1, on_workspace);
+        let on_output = self.on_output.as_ref().map(|output| {
+            workspace
+                .and_then(|ws| state.screens.iter().find(|s| s.id == Some(ws.id)))
+                .map_or(false, |screen| &screen.output == output)
         });
+        push("on_output", 1, on_output);
 
-        class_score + 2 * title_score
+        let exclude = self
+            .exclude
+            .as_ref()
+            .map(|exclude| exclude.score_window(state, window, executable_name) == 0);
+        push("exclude", 0, exclude);
+        outcomes
     }
 
     fn apply(&self, state: &mut State, window: &mut Window) {
@@ -171,9 +301,75 @@ impl WindowHook {
         if let Some(w_type) = self.spawn_as_type.clone() {
             window.r#type = w_type;
         }
+        self.apply_overrides(window);
+        if let Some(never_focus) = self.never_focus {
+            window.never_focus = never_focus;
+        }
+        if let Some(scratchpad) = &self.scratchpad {
+            add_to_scratchpad(state, window, scratchpad);
+        }
+    }
+
+    /// Set the settings of the window which take precedence over the config.
+    fn apply_overrides(&self, window: &mut Window) {
+        let overrides = &mut window.overrides;
+        if let Some(geometry) = self.floating_geometry {
+            overrides.floating_geometry = Some(geometry);
+        }
+        if let Some(center) = self.center {
+            overrides.center = center;
+        }
+        if let Some(border_width) = self.border_width {
+            overrides.border_width = Some(border_width);
+        }
+        if let Some(border_colors) = &self.border_colors {
+            overrides.border_colors = border_colors.clone();
+        }
+        if let Some(margin) = self.margin.clone() {
+            match margin.try_into() {
+                Ok(margin) => overrides.margin = Some(margin),
+                Err(err) => tracing::warn!("Could not read margin of window rule: {}", err),
+            }
+        }
+        if let Some(focus_on_map) = self.focus_on_map {
+            overrides.focus_on_map = Some(focus_on_map);
+        }
+        if self.floating_geometry.is_some() || self.center == Some(true) {
+            window.set_floating(true);
+        }
     }
 }
 
+/// Add the window to a scratchpad, as if the scratchpad had launched it.
+fn add_to_scratchpad(state: &mut State, window: &mut Window, scratchpad: &str) {
+    if !state.add_to_scratchpad(window, &ScratchPadName::from(scratchpad)) {
+        tracing::warn!("Window rule refers to unknown scratchpad {:?}", scratchpad);
+    }
+}
+
+/// Returns true if any of the provided strings is Some and matches the regex.
+fn matches_any(re: &Regex, strs: &[&Option<String>]) -> bool {
+    strs.iter().any(|str| {
+        str.as_ref().map_or(false, |s| {
+            // we match the class/title to the window rule by checking if replacing the text
+            // with the regex makes the string empty. if the original string is already
+            // empty, this will match it to every regex, so we need to check for that.
+            // however, if the window rule is explicitly for empty strings, we still
+            // want empty strings to match to it.
+            re.replace(s, "") == "" && (!s.is_empty() || re.as_str().is_empty())
+        })
+    })
+}
+
+/// Looks up the file name of the executable of a process.
+pub type ExecutableLookup = fn(u32) -> Option<String>;
+
+/// The file name of the executable of a process, read from `/proc/<pid>/exe`.
+fn executable_name(pid: u32) -> Option<String> {
+    let path = fs::read_link(format!("/proc/{pid}/exe")).ok()?;
+    Some(path.file_name()?.to_string_lossy().into_owned())
+}
+
 /// General configuration
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Serialize, Deserialize, Debug)]
@@ -182,11 +378,13 @@ pub struct Config {
     pub modkey: String,
     pub mousekey: Option<Modifier>,
     pub workspaces: Option<Vec<Workspace>>,
+    pub workspace_profiles: Vec<WorkspaceProfile>,
     pub tags: Option<Vec<String>>,
     pub max_window_width: Option<Size>,
     pub layouts: Vec<String>,
     pub layout_definitions: Vec<Layout>,
     pub layout_mode: LayoutMode,
+    pub tag_layouts: Vec<TagLayouts>,
     pub insert_behavior: InsertBehavior,
     pub scratchpad: Option<Vec<ScratchPad>>,
     pub window_rules: Option<Vec<WindowHook>>,
@@ -201,6 +399,10 @@ pub struct Config {
     pub create_follows_cursor: Option<bool>,
     pub auto_derive_workspaces: bool,
     pub disable_cursor_reposition_on_resize: bool,
+    /// Windows launched from a terminal take its place, unless a window rule prevents it.
+    pub swallow: bool,
+    /// `WM_CLASS` of the terminals which windows are looked up in, e.g. for swallowing.
+    pub terminal_classes: Vec<String>,
     #[cfg(feature = "lefthk")]
     pub keybind: Vec<Keybind>,
     pub state_path: Option<PathBuf>,
@@ -208,6 +410,13 @@ pub struct Config {
     //       at least when `TOML` is used as config language
     #[serde(skip)]
     pub theme_setting: ThemeSetting,
+    /// Looks up the executable of a window for the `executable` matcher of window rules.
+    #[serde(skip, default = "default_executable_lookup")]
+    pub(crate) executable_name: ExecutableLookup,
+}
+
+fn default_executable_lookup() -> ExecutableLookup {
+    executable_name
 }
 
 #[must_use]
@@ -342,6 +551,25 @@ fn exit_strategy<'s>() -> &'s str {
     "pkill leftwm"
 }
 
+/// Load the layouts from the config file again. They are only used if every definition is
+/// valid on each of the current workspaces.
+fn reload_layouts<SERVER: DisplayServer>(manager: &mut Manager<Config, SERVER>) -> Result<()> {
+    let config = load_from_file()?;
+    for def in &config.layout_definitions {
+        for workspace in &manager.state.workspaces {
+            validate(def, CHECKED_WINDOWS, &workspace.rect())
+                .with_context(|| format!("invalid layout definition {:?}", def.name))?;
+        }
+    }
+    manager.config.layouts = config.layouts;
+    manager.config.layout_definitions = config.layout_definitions;
+    manager.config.tag_layouts = config.tag_layouts;
+    for lost in manager.reload_layouts() {
+        tracing::warn!("Could not keep {lost}, it is no longer available");
+    }
+    Ok(())
+}
+
 fn absolute_path(path: &str) -> Option<PathBuf> {
     let exp_path = shellexpand::full(path).ok()?;
     std::fs::canonicalize(exp_path.as_ref()).ok()
@@ -398,6 +626,10 @@ impl leftwm_core::Config for Config {
         self.workspaces.clone()
     }
 
+    fn workspace_profiles(&self) -> Vec<WorkspaceProfile> {
+        self.workspace_profiles.clone()
+    }
+
     fn focus_behaviour(&self) -> FocusBehaviour {
         self.focus_behaviour
     }
@@ -429,6 +661,10 @@ impl leftwm_core::Config for Config {
         layouts
     }
 
+    fn tag_layouts(&self) -> Vec<TagLayouts> {
+        self.tag_layouts.clone()
+    }
+
     fn layout_mode(&self) -> LayoutMode {
         self.layout_mode
     }
@@ -445,10 +681,20 @@ impl leftwm_core::Config for Config {
         self.focus_new_windows
     }
 
+    fn terminal_classes(&self) -> Vec<String> {
+        self.terminal_classes.clone()
+    }
+
+    fn swallow(&self, state: &State, window: &Window) -> bool {
+        self.best_window_rule(state, window)
+            .and_then(|hook| hook.swallow)
+            .unwrap_or(self.swallow)
+    }
+
     fn command_handler<SERVER: DisplayServer>(
         command: &str,
         manager: &mut Manager<Self, SERVER>,
-    ) -> bool {
+    ) -> Result<bool, String> {
         let mut return_pipe = get_return_pipe();
         if let Some((command, value)) = command.split_once(' ') {
             match command {
@@ -456,21 +702,23 @@ impl leftwm_core::Config for Config {
                     if let Some(absolute) = absolute_path(value.trim()) {
                         manager.config.theme_setting.load(absolute);
                         write_to_pipe(&mut return_pipe, "OK: Command executed successfully");
+                        Ok(manager.reload_config())
                     } else {
                         tracing::warn!("Path submitted does not exist.");
                         write_to_pipe(&mut return_pipe, "ERROR: Path submitted does not exist");
+                        manager.reload_config();
+                        Err("Path submitted does not exist".to_string())
                     }
-                    manager.reload_config()
                 }
                 "UnloadTheme" => {
                     manager.config.theme_setting = ThemeSetting::default();
                     write_to_pipe(&mut return_pipe, "OK: Command executed successfully");
-                    manager.reload_config()
+                    Ok(manager.reload_config())
                 }
                 _ => {
                     tracing::warn!("Command not recognized: {}", command);
                     write_to_pipe(&mut return_pipe, "ERROR: Command not recognized");
-                    false
+                    Err(format!("Command not recognized: {command}"))
                 }
             }
         } else {
@@ -478,17 +726,31 @@ impl leftwm_core::Config for Config {
                 "LoadTheme" => {
                     tracing::warn!("Missing parameter theme_path");
                     write_to_pipe(&mut return_pipe, "ERROR: Missing parameter theme_path");
-                    false
+                    Err("Missing parameter theme_path".to_string())
                 }
                 "UnloadTheme" => {
                     manager.config.theme_setting = ThemeSetting::default();
                     write_to_pipe(&mut return_pipe, "OK: Command executed successfully");
-                    manager.reload_config()
+                    Ok(manager.reload_config())
                 }
+                "ReloadLayouts" => match reload_layouts(manager) {
+                    Ok(()) => {
+                        write_to_pipe(&mut return_pipe, "OK: Command executed successfully");
+                        Ok(true)
+                    }
+                    Err(err) => {
+                        tracing::warn!("Layouts not reloaded: {err:#}");
+                        write_to_pipe(
+                            &mut return_pipe,
+                            &format!("ERROR: Layouts not reloaded: {err:#}"),
+                        );
+                        Err(format!("Layouts not reloaded: {err:#}"))
+                    }
+                },
                 _ => {
                     tracing::warn!("Command not recognized: {}", command);
                     write_to_pipe(&mut return_pipe, "ERROR: Command not recognized");
-                    false
+                    Err(format!("Command not recognized: {command}"))
                 }
             }
         }
@@ -596,18 +858,25 @@ impl leftwm_core::Config for Config {
                 return;
             }
         };
-        if let Err(err) = ron::ser::to_writer(state_file, state) {
+        if let Err(err) = serde_json::to_writer(state_file, &state.saved()) {
             tracing::error!("Cannot save state: {}", err);
         }
     }
 
     fn load_state(&self, state: &mut State) {
         let path = self.state_file().to_owned();
-        match File::open(&path) {
-            Ok(file) => {
-                match ron::de::from_reader(file) {
-                    Ok(old_state) => state.restore_state(&old_state),
-                    Err(err) => tracing::error!("Cannot load old state: {}", err),
+        match fs::read(&path) {
+            Ok(bytes) => {
+                // The state was saved as RON before it was versioned, it is read into the same
+                // form so that it is migrated and restored partially as well.
+                let saved = serde_json::from_slice::<serde_json::Value>(&bytes)
+                    .or_else(|_| ron::de::from_bytes::<serde_json::Value>(&bytes));
+                let lost = match saved {
+                    Ok(saved) => state.restore_saved(saved),
+                    Err(err) => vec![format!("the whole state, it cannot be parsed: {err}")],
+                };
+                if !lost.is_empty() {
+                    tracing::warn!("Could not restore {}", lost.join("; "));
                 }
 
                 // Clean old state.
@@ -621,36 +890,62 @@ impl leftwm_core::Config for Config {
 
     /// Pick the best matching [`WindowHook`], if any, and apply its config.
     fn setup_predefined_window(&self, state: &mut State, window: &mut Window) -> bool {
-        if let Some(window_rules) = &self.window_rules {
-            let best_match = window_rules
-                .iter()
-                // map first instead of using max_by_key directly...
-                .map(|wh| (wh, wh.score_window(window)))
-                // ...since this filter is required (0 := non-match)
-                .filter(|(_wh, score)| score != &0)
-                .max_by_key(|(_wh, score)| *score);
-            if let Some((hook, _)) = best_match {
-                hook.apply(state, window);
-                tracing::trace!(
-                    "Window [[ TITLE={:?}, {:?}; WM_CLASS={:?}, {:?} ]] spawned in tag={:?} on workspace={:?} as type={:?} with floating={:?}, sticky={:?} and fullscreen={:?}",
-                    window.name,
-                    window.legacy_name,
-                    window.res_name,
-                    window.res_class,
-                    hook.spawn_as_type,
-                    hook.spawn_on_tag,
-                    hook.spawn_on_workspace,
-                    hook.spawn_floating,
-                    hook.spawn_sticky,
-                    hook.spawn_fullscreen,
-                );
-                return true;
-            }
-            return false;
+        if let Some(hook) = self.best_window_rule(state, window) {
+            hook.apply(state, window);
+            tracing::trace!(
+                "Window [[ TITLE={:?}, {:?}; WM_CLASS={:?}, {:?} ]] spawned in tag={:?} on workspace={:?} as type={:?} with floating={:?}, sticky={:?} and fullscreen={:?}",
+                window.name,
+                window.legacy_name,
+                window.res_name,
+                window.res_class,
+                hook.spawn_as_type,
+                hook.spawn_on_tag,
+                hook.spawn_on_workspace,
+                hook.spawn_floating,
+                hook.spawn_sticky,
+                hook.spawn_fullscreen,
+            );
+            return true;
         }
         false
     }
 
+    fn reevaluate_predefined_window(
+        &self,
+        state: &mut State,
+        old: &Window,
+        window: &mut Window,
+    ) -> bool {
+        let Some(hook) = self.best_window_rule(state, window) else {
+            return false;
+        };
+        if !hook.reevaluate_on_change.unwrap_or_default() {
+            return false;
+        }
+        // Only apply the rule once, not on every change of a window it already matched.
+        if self
+            .best_window_rule(state, old)
+            .map_or(false, |old_hook| std::ptr::eq(old_hook, hook))
+        {
+            return false;
+        }
+        hook.apply(state, window);
+        tracing::trace!(
+            "Window [[ TITLE={:?}; WM_CLASS={:?} ]] matched a window rule after a change",
+            window.name,
+            window.res_class,
+        );
+        true
+    }
+
+    fn explain_window_rules(&self, state: &State, handle: WindowHandle) -> String {
+        let mut out = vec![];
+        if let Err(err) = self.write_window_rules_explanation(&mut out, state, handle) {
+            return format!("Could not explain the window rules: {err}");
+        }
+        String::from_utf8_lossy(&out).into_owned()
+    }
+
     fn sloppy_mouse_follows_focus(&self) -> bool {
         self.sloppy_mouse_follows_focus
     }
@@ -678,6 +973,19 @@ impl Config {
         self.keybind.clear();
     }
 
+    /// The most specific [`WindowHook`] matching the window, if any.
+    fn best_window_rule(&self, state: &State, window: &Window) -> Option<&WindowHook> {
+        self.window_rules
+            .as_ref()?
+            .iter()
+            // map first instead of using max_by_key directly...
+            .map(|wh| (wh, wh.score_window(state, window, self.executable_name)))
+            // ...since this filter is required (0 := non-match)
+            .filter(|(_wh, score)| score != &0)
+            .max_by_key(|(_wh, score)| *score)
+            .map(|(wh, _)| wh)
+    }
+
     fn state_file(&self) -> &Path {
         self.state_path
             .as_deref()
@@ -721,6 +1029,214 @@ fn write_to_pipe(return_pipe: &mut Result<File, Box<dyn Error>>, msg: &str) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use leftwm_core::display_servers::MockDisplayServer;
+    use leftwm_core::models::{Screen, WindowHandle};
+
+    fn state_with_screen() -> State {
+        let mut manager = Manager::<Config, MockDisplayServer>::new(Config::default());
+        manager.screen_create_handler(Screen {
+            output: "HDMI-1".to_string(),
+            ..Screen::default()
+        });
+        manager.state
+    }
+
+    fn firefox() -> Window {
+        let mut window = Window::new(
+            WindowHandle::MockHandle(1),
+            Some("Mozilla Firefox".to_string()),
+            Some(1),
+        );
+        window.res_class = Some("firefox".to_string());
+        window.role = Some("browser".to_string());
+        window
+    }
+
+    /// The process of [`firefox`] runs the executable `firefox`.
+    fn firefox_executable(pid: u32) -> Option<String> {
+        (pid == 1).then(|| "firefox".to_string())
+    }
+
+    fn regex(re: &str) -> Option<Regex> {
+        Some(Regex::new(re).unwrap())
+    }
+
+    fn class_rule(class: &str) -> WindowHook {
+        WindowHook {
+            window_class: regex(class),
+            ..WindowHook::default()
+        }
+    }
+
+    #[test]
+    fn every_matcher_matches_only_its_windows() {
+        let state = state_with_screen();
+        let window = firefox();
+        let cases = [
+            (
+                "window_role",
+                WindowHook {
+                    window_role: regex("browser"),
+                    ..class_rule("firefox")
+                },
+                WindowHook {
+                    window_role: regex("pop-up"),
+                    ..class_rule("firefox")
+                },
+            ),
+            (
+                "window_type",
+                WindowHook {
+                    window_type: Some(WindowType::Normal),
+                    ..class_rule("firefox")
+                },
+                WindowHook {
+                    window_type: Some(WindowType::Dialog),
+                    ..class_rule("firefox")
+                },
+            ),
+            (
+                "transient",
+                WindowHook {
+                    transient: Some(false),
+                    ..class_rule("firefox")
+                },
+                WindowHook {
+                    transient: Some(true),
+                    ..class_rule("firefox")
+                },
+            ),
+            (
+                "executable",
+                WindowHook {
+                    executable: regex("^firefox$"),
+                    ..class_rule("firefox")
+                },
+                WindowHook {
+                    executable: regex("^fire$"),
+                    ..class_rule("firefox")
+                },
+            ),
+            (
+                "on_workspace",
+                WindowHook {
+                    on_workspace: Some(1),
+                    ..class_rule("firefox")
+                },
+                WindowHook {
+                    on_workspace: Some(2),
+                    ..class_rule("firefox")
+                },
+            ),
+            (
+                "on_output",
+                WindowHook {
+                    on_output: Some("HDMI-1".to_string()),
+                    ..class_rule("firefox")
+                },
+                WindowHook {
+                    on_output: Some("DP-1".to_string()),
+                    ..class_rule("firefox")
+                },
+            ),
+            (
+                "exclude",
+                WindowHook {
+                    exclude: Some(Box::new(class_rule("mpv"))),
+                    ..class_rule("firefox")
+                },
+                WindowHook {
+                    exclude: Some(Box::new(class_rule("fire"))),
+                    ..class_rule("firefox")
+                },
+            ),
+        ];
+
+        for (name, matching, other) in cases {
+            let matched = |rule: &WindowHook| {
+                rule.match_window(&state, &window, firefox_executable)
+                    .iter()
+                    .find(|outcome| outcome.name == name)
+                    .map(|outcome| outcome.matched)
+            };
+            assert_eq!(matched(&matching), Some(true), "{name} should match");
+            assert_ne!(
+                matching.score_window(&state, &window, firefox_executable),
+                0,
+                "{name}"
+            );
+            assert_eq!(matched(&other), Some(false), "{name} should not match");
+            assert_eq!(
+                other.score_window(&state, &window, firefox_executable),
+                0,
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn a_name_and_every_other_matcher_have_to_match() {
+        let state = state_with_screen();
+        let window = firefox();
+
+        let either_name = WindowHook {
+            window_title: regex("Firefox"),
+            ..class_rule("mpv")
+        };
+        assert_eq!(
+            either_name.score_window(&state, &window, firefox_executable),
+            2
+        );
+        let no_name = WindowHook {
+            window_title: regex("mpv"),
+            ..class_rule("mpv")
+        };
+        assert_eq!(no_name.score_window(&state, &window, firefox_executable), 0);
+        let not_every_other = WindowHook {
+            window_role: regex("browser"),
+            transient: Some(true),
+            ..class_rule("firefox")
+        };
+        assert_eq!(
+            not_every_other.score_window(&state, &window, firefox_executable),
+            0
+        );
+        let without_names = WindowHook {
+            window_role: regex("browser"),
+            transient: Some(false),
+            ..WindowHook::default()
+        };
+        assert_eq!(
+            without_names.score_window(&state, &window, firefox_executable),
+            3
+        );
+    }
+
+    #[test]
+    fn a_rule_matching_the_title_beats_one_matching_the_class() {
+        let state = state_with_screen();
+        let config = Config {
+            window_rules: Some(vec![
+                WindowHook {
+                    spawn_on_tag: Some(2),
+                    ..class_rule("firefox")
+                },
+                WindowHook {
+                    window_title: regex("Mozilla"),
+                    spawn_on_tag: Some(3),
+                    ..WindowHook::default()
+                },
+                WindowHook {
+                    spawn_on_tag: Some(4),
+                    ..class_rule("mpv")
+                },
+            ]),
+            ..Config::default()
+        };
+
+        let best = config.best_window_rule(&state, &firefox());
+        assert_eq!(best.and_then(|rule| rule.spawn_on_tag), Some(3));
+    }
 
     #[test]
     fn config_serializes_to_valid_ron_test() {
@@ -736,4 +1252,44 @@ mod tests {
         let ron_config = ron::from_str::<'_, Config>(ron.unwrap().as_str());
         assert!(ron_config.is_ok(), "Could not deserialize default config");
     }
+
+    #[test]
+    fn a_window_rule_hides_its_window_in_the_scratchpad() {
+        let mut manager = Manager::<Config, MockDisplayServer>::new(Config {
+            scratchpad: Some(vec![ScratchPad {
+                name: "Browser".into(),
+                value: String::new(),
+                class: None,
+                title: None,
+                x: None,
+                y: None,
+                height: Some(Size::Ratio(0.5)),
+                width: Some(Size::Ratio(0.5)),
+                anchor: None,
+                workspace: None,
+                follow_focus: false,
+                group: None,
+            }]),
+            window_rules: Some(vec![WindowHook {
+                scratchpad: Some("Browser".to_string()),
+                ..class_rule("firefox")
+            }]),
+            ..Config::default()
+        });
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(firefox(), -1, -1);
+
+        let state = &manager.state;
+        let window = &state.windows[0];
+        let nsp_tag = state.tags.get_hidden_by_label("NSP").unwrap().id;
+        assert_eq!(
+            state.scratchpad_of(window).map(|s| s.name.clone()),
+            Some("Browser".into())
+        );
+        assert!(window.has_tag(&nsp_tag));
+        assert!(!window.visible());
+        assert!(window.floating());
+        let placed = state.scratchpads[0].xyhw(&state.workspaces[0].xyhw);
+        assert_eq!(window.exact_xyhw(), placed);
+    }
 }
@@ -1,8 +1,11 @@
 use super::Config;
 #[cfg(feature = "lefthk")]
 use lefthk_core::xkeysym_lookup;
+use leftwm_core::models::{Window, WindowHandle};
+use leftwm_core::State;
 #[cfg(feature = "lefthk")]
 use std::collections::HashSet;
+use std::io::{self, Write};
 
 impl Config {
     pub fn check_mousekey(&self, verbose: bool) {
@@ -92,4 +95,188 @@ impl Config {
             }
         }
     }
+
+    /// Write how every window rule scores for a managed window, which one wins and what
+    /// applying it changes. The rule is applied the same way as to a newly mapped window,
+    /// but to a copy of the state.
+    pub(super) fn write_window_rules_explanation(
+        &self,
+        out: &mut impl Write,
+        state: &State,
+        handle: WindowHandle,
+    ) -> io::Result<()> {
+        writeln!(out, "\x1b[0;94m::\x1b[0m Explaining window rules . . .")?;
+        let Some(window) = state.windows.iter().find(|w| w.handle == handle).cloned() else {
+            writeln!(
+                out,
+                "\x1b[1;91mERROR:\x1b[0m\x1b[1m Window {handle:?} is not managed by LeftWM \x1b[0m"
+            )?;
+            return Ok(());
+        };
+        writeln!(
+            out,
+            "    WM_CLASS: {:?}, {:?}",
+            window.res_class, window.res_name
+        )?;
+        writeln!(
+            out,
+            "    Title: {:?}, {:?}",
+            window.name, window.legacy_name
+        )?;
+        writeln!(out, "    Role: {:?}", window.role)?;
+        writeln!(
+            out,
+            "    Type: {:?}, transient: {}",
+            window.r#type,
+            window.transient.is_some()
+        )?;
+
+        let rules = self.window_rules.as_deref().unwrap_or_default();
+        if rules.is_empty() {
+            writeln!(out, "\x1b[1;93m    -> No window rules configured\x1b[0m")?;
+            return Ok(());
+        }
+        for (index, rule) in rules.iter().enumerate() {
+            let score = rule.score_window(state, &window, self.executable_name);
+            writeln!(out, "    Rule #{}: score {score}", index + 1)?;
+            for outcome in rule.match_window(state, &window, self.executable_name) {
+                let result = if outcome.matched {
+                    "\x1b[0;92mmatched\x1b[0m"
+                } else {
+                    "\x1b[0;91mnot matched\x1b[0m"
+                };
+                writeln!(
+                    out,
+                    "        {}: {result} (weight {})",
+                    outcome.name, outcome.weight
+                )?;
+            }
+        }
+
+        let Some(best) = self.best_window_rule(state, &window) else {
+            writeln!(out, "\x1b[1;93m    -> No rule matches this window\x1b[0m")?;
+            return Ok(());
+        };
+        let index = rules
+            .iter()
+            .position(|rule| std::ptr::eq(rule, best))
+            .unwrap_or_default();
+        writeln!(out, "\x1b[0;92m    -> Rule #{} wins\x1b[0m", index + 1)?;
+
+        // `State` is not `Clone`, so the copy is made through its serialized form.
+        let mut state: State = serde_json::from_value(serde_json::to_value(state)?)?;
+        state.actions.clear();
+        let mut applied = window.clone();
+        leftwm_core::Config::setup_predefined_window(self, &mut state, &mut applied);
+        writeln!(out, "    Changes to the window as it is now:")?;
+        write_window_changes(out, &window, &applied)?;
+        for action in &state.actions {
+            writeln!(out, "        action: {action:?}")?;
+        }
+        Ok(())
+    }
+}
+
+fn write_window_changes(out: &mut impl Write, before: &Window, after: &Window) -> io::Result<()> {
+    let changes = [
+        (
+            "tag",
+            format!("{:?}", before.tag),
+            format!("{:?}", after.tag),
+        ),
+        (
+            "floating",
+            before.floating().to_string(),
+            after.floating().to_string(),
+        ),
+        (
+            "type",
+            format!("{:?}", before.r#type),
+            format!("{:?}", after.r#type),
+        ),
+        (
+            "never_focus",
+            before.never_focus.to_string(),
+            after.never_focus.to_string(),
+        ),
+        (
+            "overrides",
+            format!("{:?}", before.overrides),
+            format!("{:?}", after.overrides),
+        ),
+    ];
+    let mut unchanged = true;
+    for (name, before, after) in changes.iter().filter(|(_, b, a)| b != a) {
+        writeln!(out, "        {name}: {before} -> {after}")?;
+        unchanged = false;
+    }
+    if unchanged {
+        writeln!(out, "        none")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::WindowHook;
+    use leftwm_core::display_servers::MockDisplayServer;
+    use leftwm_core::models::Screen;
+    use leftwm_core::Manager;
+    use regex::Regex;
+
+    #[test]
+    fn every_matcher_of_every_rule_is_explained() {
+        let config = Config {
+            window_rules: Some(vec![
+                WindowHook {
+                    window_class: Some(Regex::new("firefox").unwrap()),
+                    window_title: Some(Regex::new("Mozilla").unwrap()),
+                    spawn_on_tag: Some(2),
+                    ..WindowHook::default()
+                },
+                WindowHook {
+                    window_class: Some(Regex::new("mpv").unwrap()),
+                    transient: Some(true),
+                    ..WindowHook::default()
+                },
+            ]),
+            ..Config::default()
+        };
+        let mut manager = Manager::<Config, MockDisplayServer>::new(Config::default());
+        manager.screen_create_handler(Screen::default());
+        let mut window = Window::new(
+            WindowHandle::MockHandle(1),
+            Some("Mozilla Firefox".to_string()),
+            None,
+        );
+        window.res_class = Some("firefox".to_string());
+        manager.window_created_handler(window, -1, -1);
+
+        let mut out = vec![];
+        config
+            .write_window_rules_explanation(&mut out, &manager.state, WindowHandle::MockHandle(1))
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        let matched = "\x1b[0;92mmatched\x1b[0m";
+        let not_matched = "\x1b[0;91mnot matched\x1b[0m";
+        let expected = [
+            "    Rule #1: score 3".to_string(),
+            format!("        window_class: {matched} (weight 1)"),
+            format!("        window_title: {matched} (weight 2)"),
+            "    Rule #2: score 0".to_string(),
+            format!("        window_class: {not_matched} (weight 1)"),
+            format!("        transient: {not_matched} (weight 1)"),
+            "\x1b[0;92m    -> Rule #1 wins\x1b[0m".to_string(),
+            "        tag: Some(1) -> Some(2)".to_string(),
+        ];
+        let lines: Vec<&str> = out.lines().collect();
+        for line in &expected {
+            assert!(
+                lines.contains(&line.as_str()),
+                "missing {line:?} in:\n{out}"
+            );
+        }
+    }
 }
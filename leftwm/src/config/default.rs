@@ -2,7 +2,7 @@ use leftwm_core::models::{ScratchPad, Size};
 
 #[cfg(feature = "lefthk")]
 use super::{default_terminal, exit_strategy, BaseCommand, Keybind};
-use super::{Config, Default, FocusBehaviour, LayoutMode, ThemeSetting};
+use super::{executable_name, Config, Default, FocusBehaviour, LayoutMode, ThemeSetting};
 
 impl Default for Config {
     // We allow this because this function would be difficult to reduce. If someone would like to
@@ -204,20 +204,28 @@ impl Default for Config {
         let scratchpad = ScratchPad {
             name: "Alacritty".into(),
             value: "alacritty".to_string(),
+            class: None,
+            title: None,
             x: Some(Size::Pixel(860)),
             y: Some(Size::Pixel(390)),
             height: Some(Size::Pixel(300)),
             width: Some(Size::Pixel(200)),
+            anchor: None,
+            workspace: None,
+            follow_focus: false,
+            group: None,
         };
 
         let layouts = leftwm_layouts::layouts::Layouts::default();
 
         Self {
             workspaces: Some(vec![]),
+            workspace_profiles: vec![],
             tags: Some(tags),
             layouts: layouts.names(),
             layout_definitions: layouts.layouts,
             layout_mode: LayoutMode::Tag,
+            tag_layouts: vec![],
             // TODO: add sane default for scratchpad config.
             // Currently default values are set in sane_dimension fn.
             scratchpad: Some(vec![scratchpad]),
@@ -240,6 +248,9 @@ impl Default for Config {
             create_follows_cursor: None,
             disable_cursor_reposition_on_resize: false,
             auto_derive_workspaces: true,
+            swallow: false,
+            terminal_classes: vec![],
+            executable_name,
         }
     }
 }
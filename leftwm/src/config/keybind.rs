@@ -33,7 +33,11 @@ impl Keybind {
     ) -> Result<lefthk_core::config::Keybind> {
         let value_is_some = !self.value.is_empty();
         match &self.command {
-            BaseCommand::Execute | BaseCommand::LoadTheme => {
+            BaseCommand::Execute
+            | BaseCommand::LoadTheme
+            | BaseCommand::AddTag
+            | BaseCommand::SaveSession
+            | BaseCommand::RestoreSession => {
                 ensure!(value_is_some, "value must not be empty");
             }
             BaseCommand::ToggleScratchPad
@@ -45,6 +49,12 @@ impl Keybind {
                     "Value should be a correct scratchpad name"
                 );
             }
+            BaseCommand::CycleScratchPadGroup => {
+                ensure!(
+                    is_valid_scratchpad_group(config, self.value.as_str()),
+                    "Value should be the group of a scratchpad"
+                );
+            }
             BaseCommand::ReleaseScratchPad => {
                 ensure!(
                     self.value.is_empty()
@@ -56,6 +66,15 @@ impl Keybind {
             BaseCommand::GotoTag => {
                 usize::from_str(&self.value).context("invalid index value for GotoTag")?;
             }
+            BaseCommand::ToggleTagView => {
+                usize::from_str(&self.value).context("invalid index value for ToggleTagView")?;
+            }
+            BaseCommand::ToggleWindowTags => {
+                ensure!(value_is_some, "value must not be empty");
+                for tag in self.value.split_whitespace() {
+                    usize::from_str(tag).context("invalid index value for ToggleWindowTags")?;
+                }
+            }
             BaseCommand::FocusWindowTop if value_is_some => {
                 bool::from_str(&self.value).context("invalid boolean value for FocusWindowTop")?;
             }
@@ -65,12 +84,45 @@ impl Keybind {
             BaseCommand::MoveToTag => {
                 usize::from_str(&self.value).context("invalid index value for SendWindowToTag")?;
             }
+            BaseCommand::RemoveTag => {
+                usize::from_str(&self.value).context("invalid index value for RemoveTag")?;
+            }
+            BaseCommand::RenameTag => {
+                let (tag, label) = self.value.split_once(' ').unwrap_or((&self.value, ""));
+                usize::from_str(tag).context("invalid index value for RenameTag")?;
+                ensure!(!label.trim().is_empty(), "label must not be empty");
+            }
+            BaseCommand::MoveTag => {
+                let (tag, position) = self.value.split_once(' ').unwrap_or((&self.value, ""));
+                usize::from_str(tag).context("invalid index value for MoveTag")?;
+                usize::from_str(position).context("invalid position value for MoveTag")?;
+            }
             BaseCommand::SetLayout => {
                 ensure!(
                     config.layouts.contains(&self.value),
                     "could not parse layout for command SetLayout"
                 );
             }
+            BaseCommand::SplitWindow => {
+                ensure!(
+                    matches!(self.value.as_str(), "Horizontal" | "Vertical"),
+                    "Value should be one of 'Horizontal', 'Vertical'"
+                );
+            }
+            BaseCommand::ResizeSplit => {
+                i32::from_str(&self.value).context("invalid change value for ResizeSplit")?;
+            }
+            BaseCommand::IncreaseWindowWeight | BaseCommand::DecreaseWindowWeight => {
+                i32::from_str(&self.value).context("invalid change value for window weight")?;
+            }
+            BaseCommand::MoveWindowInTree
+            | BaseCommand::FocusWindowInDirection
+            | BaseCommand::SwapWindowInDirection => {
+                ensure!(
+                    matches!(self.value.as_str(), "Left" | "Right" | "Top" | "Bottom"),
+                    "Value should be one of 'Left', 'Right', 'Top', 'Bottom'"
+                );
+            }
             BaseCommand::IncreaseMainWidth => {
                 i8::from_str(&self.value).context("invalid width value for IncreaseMainWidth")?;
             }
@@ -196,3 +248,12 @@ fn is_valid_scratchpad_name(config: &Config, scratchpad_name: &str) -> bool {
         .and_then(|scratchpads| scratchpads.iter().find(|s| s.name == scratchpad_name))
         .is_some()
 }
+
+#[cfg(feature = "lefthk")]
+fn is_valid_scratchpad_group(config: &Config, group: &str) -> bool {
+    config
+        .scratchpad
+        .iter()
+        .flatten()
+        .any(|s| s.group.as_deref() == Some(group))
+}